@@ -0,0 +1,63 @@
+//! Accessible output (`--a11y`): short text announcements of what just happened, shown in a
+//! status line at the bottom of the screen so screen readers pick them up.
+
+use crate::game::{GameEvent, GameState};
+
+/// Latest announcement, rebuilt from each frame's game events.
+#[derive(Debug, Default)]
+pub struct Announcer {
+    line: String,
+}
+
+impl Announcer {
+    /// Text to show (empty until the first event).
+    pub fn line(&self) -> &str {
+        &self.line
+    }
+
+    /// Announce one frame's events (positions and score read from `state`). Several moves in a
+    /// frame give one position; a frame without events keeps the previous line.
+    pub fn on_events(&mut self, state: &GameState, events: &[GameEvent]) {
+        let mut parts = Vec::new();
+        let mut position = None;
+        for &event in events {
+            match event {
+                GameEvent::Move | GameEvent::Rotate => position = Some(describe(state, event)),
+                _ => parts.push(describe(state, event)),
+            }
+        }
+        parts.extend(position);
+        if !parts.is_empty() {
+            self.line = parts.join("; ");
+        }
+    }
+}
+
+fn describe(state: &GameState, event: GameEvent) -> String {
+    let column = || {
+        state
+            .piece_column()
+            .map_or_else(String::new, |c| format!("column {}", c + 1))
+    };
+    match event {
+        GameEvent::Spawn(kind) => format!("{kind:?} piece, {}", column()),
+        GameEvent::Move => column(),
+        GameEvent::Rotate => format!("rotated, {}", column()),
+        GameEvent::Lock => format!(
+            "landed, stack {} of {} rows",
+            state.stack_height(),
+            state.playfield.height
+        ),
+        GameEvent::Clear => format!("clear, score {}", state.score),
+        GameEvent::Combo(n) => format!("combo x{n}, score {}", state.score),
+        GameEvent::ComboEnd { peak, bonus } => {
+            format!("combo x{peak} ended, bonus {bonus}, score {}", state.score)
+        }
+        GameEvent::LevelUp(level) => format!("level {level}"),
+        GameEvent::Breather => "breather, double points".to_string(),
+        GameEvent::Mission { bonus } => {
+            format!("mission done, bonus {bonus}, score {}", state.score)
+        }
+        GameEvent::GameOver => format!("game over, score {}", state.score),
+    }
+}
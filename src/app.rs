@@ -1,26 +1,101 @@
 //! App: terminal init, main loop, tick and key handling.
 
-use crate::game::GameState;
-use crate::input::{Action, key_to_action};
+use crate::audio::{BellCues, MusicCue};
+use crate::game::{GameEvent, GameState};
+use crate::input::{Action, Chord, key_to_action};
 use crate::theme::Theme;
 use crate::{Args, GameConfig};
 use anyhow::Result;
-use crossterm::event::{self, Event, KeyCode, KeyEventKind};
-use ratatui::DefaultTerminal;
+use clap::ValueEnum;
+use crossterm::event::{
+    self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEvent,
+    MouseEventKind,
+};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::{Duration, Instant};
 use tachyonfx::Effect;
 
-/// DAS (Delayed Auto-Shift): delay before movement starts repeating when you hold a key.
-const REPEAT_DELAY_MS: u64 = 80;
-/// ARR (Auto-Repeat Rate): time between repeated moves while holding.
-const REPEAT_INTERVAL_MS: u64 = 38;
+/// Next (or previous) variant of a CLI enum, wrapping around (settings screen).
+pub fn cycle<T: ValueEnum + PartialEq + Copy>(current: T, forward: bool) -> T {
+    let variants = T::value_variants();
+    let n = variants.len();
+    let i = variants.iter().position(|&v| v == current).unwrap_or(0);
+    variants[(i + if forward { 1 } else { n - 1 }) % n]
+}
+
+/// Undo `App::enter_terminal`. Safe to call more than once (panic hook, then normal exit).
+fn restore_terminal() -> Result<()> {
+    use crossterm::{
+        event::{DisableFocusChange, DisableMouseCapture, PopKeyboardEnhancementFlags},
+        execute,
+        terminal::{LeaveAlternateScreen, disable_raw_mode},
+    };
+
+    let mut stdout = std::io::stdout();
+    let _ = execute!(stdout, PopKeyboardEnhancementFlags);
+    let _ = execute!(stdout, DisableMouseCapture);
+    let _ = execute!(stdout, DisableFocusChange);
+    execute!(stdout, LeaveAlternateScreen)?;
+    disable_raw_mode()?;
+    Ok(())
+}
+
+/// Terminals without key release events: a second press of the same key within this
+/// window is an OS auto-repeat, so the key is being held...
+const OS_REPEAT_WINDOW_MS: u64 = 700;
+/// ...and it counts as released once the OS repeats stop for this long.
+const OS_REPEAT_GAP_MS: u64 = 100;
+
+/// Two left clicks within this many ms hard-drop instead of rotating twice.
+const DOUBLE_CLICK_MS: u64 = 300;
+
+/// `--hard-drop-safety double-tap`: the second press must follow within this many ms.
+const HARD_DROP_DOUBLE_TAP_MS: u64 = 300;
+/// `--hard-drop-safety hold`: how long the key must stay down.
+const HARD_DROP_HOLD_MS: u64 = 100;
+
+/// Timed mode: a chain still paying out at zero gets up to this much overtime.
+const OVERTIME_SECS: u64 = 10;
+
+/// `--show-inputs`: how many recent actions the overlay shows.
+const SHOWN_INPUTS: usize = 8;
+
+/// Consecutive loop passes over the `--frame-rate` budget before suggesting `doctor`.
+const SLOW_PASSES: u32 = 30;
+/// How long the slow rendering hint stays up.
+const SLOW_HINT_SECS: u64 = 6;
+/// Consecutive loop passes under half the frame budget before reduced quality ends.
+const QUALITY_RECOVER_PASSES: u32 = 1000;
+/// How long a notice (F10 state dump path, clipboard or save failure) stays up.
+const NOTICE_SECS: u64 = 8;
+
+/// `--low-bandwidth`: least time between frames when no key was pressed.
+const LOW_BANDWIDTH_FRAME_MS: u64 = 100;
+/// Terminal output buffer: room for a full frame of a large window.
+const FRAME_BUFFER_BYTES: usize = 1 << 16;
+
+/// Game over: the board drains away over this long before the popup shows.
+const DRAIN_MS: usize = 2000;
+
+/// The game's terminal: ratatui on buffered stdout.
+type GameTerminal =
+    ratatui::Terminal<ratatui::backend::CrosstermBackend<std::io::BufWriter<std::io::Stdout>>>;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Screen {
+    /// Logo forming from falling sand (see `crate::title`), until a key is pressed.
+    Title,
     Menu,
     Playing,
     GameOver,
+    /// `--hot-seat`: scores so far and whose turn is next, between games.
+    Standings,
     QuitMenu,
+    /// Handling settings (DAS / ARR / soft drop), opened from the menu.
+    Settings,
+    /// Custom mode form (see `crate::custom::CustomForm`), opened from the menu.
+    CustomMode,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -34,27 +109,78 @@ pub enum QuitOption {
 pub enum GameOverReason {
     StackOverflow,
     TimeUp,
+    /// Clear mode target reached.
+    Cleared,
+}
+
+/// Why the game is paused (picks the overlay text).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PauseReason {
+    User,
+    /// The terminal lost focus (auto-pause).
+    FocusLost,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum MenuTab {
     Difficulty,
     Mode,
+    /// Timed: time limit; Clear: target lines; Custom: which saved mode.
+    ModeGoal,
+    BoardWidth,
     Autoplay,
     AutoRestart,
+    Handling,
+    /// Typed or pasted seed for a shared piece sequence.
+    Seed,
     Start,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+/// Clickable menu item (filled in by `ui::draw_menu` with its on-screen rect).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MenuTarget {
+    Difficulty(crate::Difficulty),
+    Mode(crate::GameMode),
+    Custom,
+    ModeGoal,
+    BoardWidth,
+    Autoplay,
+    AutoRestart,
+    Handling,
+    Seed,
+    Start,
+}
+
+/// Longest seed: 64 bits in hex.
+const SEED_DIGITS: usize = 16;
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct MenuState {
     pub current_tab: MenuTab,
     pub selected_difficulty: crate::Difficulty,
+    /// Difficulty presets, for the playfield size line.
+    pub profiles: crate::difficulty::DifficultyProfiles,
     pub selected_mode: crate::GameMode,
     pub animation_start: Instant,
     pub ratman_typed: String,
     pub ratman_unlocked: bool,
     pub autoplay_enabled: bool,
     pub auto_restart_enabled: bool,
+    /// Timed and Clear option rows: time limit (secs), target lines and board width (None =
+    /// as wide as the terminal allows).
+    pub time_limit: u32,
+    pub clear_lines: u32,
+    pub board_width: Option<u16>,
+    /// `+` / `-`: steps below the biggest board the terminal fits (see `zoomed_size`).
+    pub zoom_out: u16,
+    /// Seed row: hex digits typed or pasted (empty = random).
+    pub seed: String,
+    /// Custom modes saved in the config file.
+    pub custom_modes: Vec<crate::custom::CustomMode>,
+    /// Custom picked in the mode row: index into `custom_modes`, or its length for a new one.
+    pub custom: Option<usize>,
+    /// Screen rects of the clickable items from the last draw.
+    pub click_targets: Vec<(ratatui::layout::Rect, MenuTarget)>,
 }
 
 impl Default for MenuState {
@@ -62,13 +188,157 @@ impl Default for MenuState {
         Self {
             current_tab: MenuTab::Difficulty,
             selected_difficulty: crate::Difficulty::Easy,
+            profiles: crate::difficulty::DifficultyProfiles::default(),
             selected_mode: crate::GameMode::Endless,
             animation_start: Instant::now(),
             ratman_typed: String::new(),
             ratman_unlocked: false,
             autoplay_enabled: false,
             auto_restart_enabled: false,
+            time_limit: 180,
+            clear_lines: 40,
+            board_width: None,
+            zoom_out: 0,
+            seed: String::new(),
+            custom_modes: Vec::new(),
+            custom: None,
+            click_targets: Vec::new(),
+        }
+    }
+}
+
+/// Menu time limit steps and range, in seconds.
+pub const MENU_TIME_STEP: u32 = 30;
+pub const MENU_TIME_RANGE: (u32, u32) = (30, 900);
+
+/// Menu target line steps and range.
+pub const MENU_LINES_STEP: u32 = 10;
+pub const MENU_LINES_RANGE: (u32, u32) = (10, 200);
+
+/// Narrowest board the menu offers.
+const MENU_WIDTH_MIN: u16 = 6;
+
+/// Shortest board menu zoom goes down to (at `MENU_WIDTH_MIN` columns).
+const MENU_HEIGHT_MIN: u16 = 16;
+
+impl MenuState {
+    /// Timed, Clear and Custom show their option rows below the mode.
+    pub const fn has_mode_options(&self) -> bool {
+        self.custom.is_some() || !matches!(self.selected_mode, crate::GameMode::Endless)
+    }
+
+    /// The saved custom mode picked in the mode row, if any.
+    pub fn custom_mode(&self) -> Option<&crate::custom::CustomMode> {
+        self.custom.and_then(|index| self.custom_modes.get(index))
+    }
+
+    /// Step the mode row: Endless, Timed, Clear, Custom.
+    fn cycle_mode(&mut self, forward: bool) {
+        let modes = crate::GameMode::value_variants();
+        let n = modes.len() + 1;
+        let i = match self.custom {
+            Some(_) => modes.len(),
+            None => modes
+                .iter()
+                .position(|&m| m == self.selected_mode)
+                .unwrap_or(0),
+        };
+        match modes.get((i + if forward { 1 } else { n - 1 }) % n) {
+            Some(&mode) => {
+                self.selected_mode = mode;
+                self.custom = None;
+            }
+            None => self.select_custom(0),
+        }
+    }
+
+    /// Pick custom mode `index` (`custom_modes.len()` = a new one); the goal picks the mode.
+    fn select_custom(&mut self, index: usize) {
+        self.custom = Some(index);
+        if let Some(mode) = self.custom_mode() {
+            self.selected_mode = mode.mode();
+        }
+    }
+
+    /// Step the time limit or target lines of the selected mode, or the saved custom mode.
+    fn adjust_goal(&mut self, forward: bool) {
+        if let Some(index) = self.custom {
+            let n = self.custom_modes.len() + 1;
+            self.select_custom((index + if forward { 1 } else { n - 1 }) % n);
+            return;
+        }
+        let step = |value: u32, by: u32, (min, max): (u32, u32)| {
+            let value = if forward {
+                value + by
+            } else {
+                value.saturating_sub(by)
+            };
+            value.clamp(min, max)
+        };
+        match self.selected_mode {
+            crate::GameMode::Endless => {}
+            crate::GameMode::Timed => {
+                self.time_limit = step(self.time_limit, MENU_TIME_STEP, MENU_TIME_RANGE);
+            }
+            crate::GameMode::Clear => {
+                self.clear_lines = step(self.clear_lines, MENU_LINES_STEP, MENU_LINES_RANGE);
+            }
+        }
+    }
+
+    /// Step the board width; "auto" sits past both ends.
+    fn adjust_width(&mut self, forward: bool) {
+        let (min, max) = (MENU_WIDTH_MIN, crate::ui::MAX_PLAYFIELD_WIDTH);
+        self.board_width = match (self.board_width, forward) {
+            (None, true) => Some(min),
+            (None, false) => Some(max),
+            (Some(w), true) if w < max => Some(w + 1),
+            (Some(w), false) if w > min => Some(w - 1),
+            _ => None,
+        };
+    }
+
+    /// Zoom the board in (`+`, bigger) or out (`-`, smaller) by a step.
+    fn adjust_zoom(&mut self, bigger: bool) {
+        self.zoom_out = if bigger {
+            self.zoom_out.saturating_sub(1)
+        } else {
+            (self.zoom_out + 1).min(crate::ui::MAX_PLAYFIELD_WIDTH - MENU_WIDTH_MIN)
+        };
+    }
+
+    /// Board size `zoom_out` steps below `(w, h)`, the biggest the terminal fits: each step is
+    /// one column and two rows, down to `MENU_WIDTH_MIN` × `MENU_HEIGHT_MIN`.
+    pub fn zoomed_size(&self, (w, h): (u16, u16)) -> (u16, u16) {
+        let shrink = |n: u16, by: u16, min: u16| n.saturating_sub(by).max(min.min(n));
+        (
+            shrink(w, self.zoom_out, MENU_WIDTH_MIN),
+            shrink(h, 2 * self.zoom_out, MENU_HEIGHT_MIN),
+        )
+    }
+}
+
+/// Game over: a copy of the final board draining off the bottom before the popup shows.
+struct Drain {
+    board: GameState,
+    started: Instant,
+    steps: usize,
+}
+
+impl Drain {
+    /// Run the drain steps due by `now`, paced so a full board is gone after `DRAIN_MS`.
+    /// False once the board is empty.
+    fn tick(&mut self, now: Instant) -> bool {
+        let (_, gh) = self.board.playfield.grain_dims();
+        let elapsed = now.saturating_duration_since(self.started).as_millis() as usize;
+        let due = (elapsed * gh / DRAIN_MS).min(gh);
+        while self.steps < due {
+            self.steps += 1;
+            if !self.board.drain_step() {
+                return false;
+            }
         }
+        elapsed < DRAIN_MS
     }
 }
 
@@ -81,7 +351,42 @@ pub struct App {
     effective_playfield_height: u16,
     state: GameState,
     screen: Screen,
-    paused: bool,
+    paused: Option<PauseReason>,
+    audio: crate::audio::Audio,
+    /// `--bell-cues`.
+    bell_cues: Option<BellCues>,
+    /// `--a11y` announcements.
+    announcer: Option<crate::a11y::Announcer>,
+    /// `--event-log`.
+    feed: Option<crate::feed::EventFeed>,
+    /// `--state-socket`.
+    state_stream: Option<crate::stream::StateStream>,
+    /// The board summary for `state_stream`, kept up to date every frame.
+    board_view: crate::view::BoardView,
+    /// `--input-fifo`.
+    remote_input: Option<crate::remote::RemoteInput>,
+    /// Screen when game events were last handled (to sound game over once).
+    sounded_screen: Screen,
+    /// `--hard-drop-safety`: first tap of a double tap / start of a hold, if waiting.
+    hard_drop_armed: Option<Instant>,
+    hard_drop_held: Option<Instant>,
+    /// Set by the SIGTSTP handler; the loop then suspends at a safe point.
+    suspend_requested: Arc<AtomicBool>,
+    /// F12: save a screenshot of the next in-game frame.
+    screenshot_requested: bool,
+    /// `--screenshot-on-exit`: latest in-game frame and its game area.
+    last_frame: Option<(ratatui::buffer::Buffer, ratatui::layout::Rect)>,
+    /// Screenshots taken (or failed) this session, reported on exit.
+    screenshots: Vec<Result<std::path::PathBuf>>,
+    /// F10: state dumps saved this session, reported on exit.
+    dumps: Vec<std::path::PathBuf>,
+    /// A line for the top of the screen and when it went up: where the F10 state dump went,
+    /// or why a paste, copy or save failed. The terminal is ours, so errors can't go to stderr.
+    notice: Option<(Instant, String)>,
+    /// `--load-state`: the game came from a dump; keep its board size.
+    state_loaded: bool,
+    /// Flags the game was started with (a loaded dump's own with `--load-state`), for F10.
+    command_line: Vec<String>,
     game_start: Instant,
     game_over_reason: Option<GameOverReason>,
     last_tick: Instant,
@@ -89,27 +394,86 @@ pub struct App {
     base_tick_rate: f64,
     repeat_state: Option<(Action, Instant)>,
     last_repeat_fire: Option<Instant>,
+    /// The terminal reports key releases (keyboard enhancement flags). Without them held
+    /// keys are tracked from OS auto-repeat presses instead.
+    key_releases: bool,
+    /// Last press of a game key when `key_releases` is false (OS repeats refresh it).
+    os_press: Option<(Action, Instant)>,
+    /// Chorded presses (both rotate keys, Left+Right); None with `--no-chords`.
+    chords: Option<crate::input::ChordDetector>,
+    /// Last rotation pressed while no piece was controllable; applied on the next spawn (IRS).
+    buffered_rotation: Option<Action>,
+    /// Movement key currently held down (cleared on release); shifts each new piece on spawn.
+    held_direction: Option<Action>,
+    /// Whether the piece was controllable last frame (to detect the moment it becomes so).
+    was_controllable: bool,
     last_input_time: Instant,
+    /// First key press in play since the last frame, for `--log-file` input latency.
+    unshown_input: Option<(Action, Instant)>,
+    /// This game's render and logic frame times, for the game over screen.
+    frame_times: crate::diagnostics::FrameTimes,
     line_clear_started: Option<Instant>,
     /// `TachyonFX` fade effect for line-clear (created when animation starts).
     line_clear_effect: Option<Effect>,
     /// Last time we processed the line-clear effect (for delta).
     line_clear_effect_process_time: Option<Instant>,
+    /// New high score effects on the game over screen (created when it is first drawn).
+    celebration: Option<crate::ui::Celebration>,
+    /// Game over board drain, while it runs.
+    drain: Option<Drain>,
     menu_state: MenuState,
     quit_selected: QuitOption,
+    /// Selected row on the settings screen (index into `HandlingConfig::FIELDS`).
+    settings_selected: usize,
+    /// Custom mode form, while open.
+    custom_form: Option<crate::custom::CustomForm>,
+    /// Time of the last left click on the playfield (double-click detection).
+    last_click: Option<Instant>,
     high_score_endless: u32,
     high_score_timed: u32,
     high_score_clear: u32,
+    /// Custom mode high scores, by mode name.
+    custom_high_scores: std::collections::BTreeMap<String, u32>,
+    /// Lifetime statistics per mode and difficulty (menu records panel).
+    records: crate::records::Records,
     /// High scores at the start of the current game (for "New record!").
     high_score_at_game_start: (u32, u32, u32),
     /// True if this game set a new record for the current mode (used on game over screen).
     new_high_score_this_game: bool,
     /// When in Clear40: time (secs) when player first reached 40 lines; None until then.
     time_to_40_secs: Option<u64>,
+    /// Clear mode: time at every 10 lines, against the best run.
+    splits: crate::splits::Splits,
+    /// `--show-inputs`: the last `SHOWN_INPUTS` actions and when they were pressed.
+    inputs: Vec<(Action, Instant)>,
+    /// F3 diagnostics overlay, while shown.
+    diagnostics: Option<crate::diagnostics::Diagnostics>,
+    /// Loop passes in a row that went over the frame budget.
+    slow_passes: u32,
+    /// When the slow rendering hint was shown (once per run).
+    slow_hint: Option<Instant>,
+    /// Rendering fell behind: flat sand, no popups, half the sand steps (see `SLOW_PASSES`).
+    low_quality: bool,
+    /// Loop passes in a row well within the frame budget, to end `low_quality`.
+    fast_passes: u32,
+    /// `low_quality` with one sand step per tick: this tick's step was skipped.
+    sand_step_skipped: bool,
+    /// `--tutorial` progress.
+    tutorial: Option<crate::tutorial::Tutorial>,
+    /// `--missions` progress in this game.
+    missions: Option<crate::missions::Missions>,
+    /// `--rewind` snapshots and uses left this run.
+    rewind: Option<crate::rewind::Rewind>,
+    /// `--hot-seat` players and their scores.
+    hot_seat: Option<crate::hotseat::HotSeat>,
+    /// Title screen logo, while on it.
+    title: Option<crate::title::Title>,
     /// Playfield size from current terminal when on menu (zoom out = bigger). Used when starting from menu; during play size is fixed.
     menu_playfield_width: u16,
     menu_playfield_height: u16,
     last_frame_time: Instant,
+    /// When the last frame was drawn (`--low-bandwidth` skips passes).
+    last_draw: Instant,
     autoplay: bool,
     autoplay_moves: std::collections::VecDeque<crate::input::Action>,
     last_autoplay_action: Instant,
@@ -118,45 +482,71 @@ pub struct App {
     auto_restart: bool,
 }
 
-const fn default_tick_rate_for_difficulty(d: crate::Difficulty) -> f64 {
-    match d {
-        crate::Difficulty::Easy => 30.0,
-        crate::Difficulty::Medium => 50.0,
-        crate::Difficulty::Hard => 90.0,
-    }
-}
-
 impl App {
     #[allow(clippy::needless_pass_by_value, clippy::unnecessary_wraps)]
     pub fn new(args: Args, config: GameConfig, theme: Theme) -> Result<Self> {
         let (high_score_endless, high_score_timed, high_score_clear) =
             crate::highscores::load_high_scores();
-        let width = crate::effective_playfield_width(args.difficulty, args.width);
-        let height = args.height;
+        let (width, height) = config
+            .profiles
+            .get(args.difficulty)
+            .playfield_size(args.width, args.height);
 
         let autoplay = if args.no_menu { args.autoplay } else { false };
         let auto_restart = if args.no_menu { args.auto_restart } else { false };
 
         #[allow(clippy::needless_borrow)]
         let state = GameState::new(theme.clone(), width, height, &config);
+        let (volume, music) = (config.volume, config.music);
+        let bell_cues = args.bell_cues.then(BellCues::default);
+        let announcer = args.a11y.then(crate::a11y::Announcer::default);
+        let feed = args.event_log.then(crate::feed::EventFeed::default);
+        let chords = (!args.no_chords).then(crate::input::ChordDetector::default);
+        let splits = crate::splits::Splits::new(args.clear_lines);
+        // One snapshot per tick at the requested rate
+        let snapshot_interval = Duration::from_secs_f64(1.0 / args.tick_rate);
+        let state_stream = args
+            .state_socket
+            .as_deref()
+            .map(|path| crate::stream::StateStream::open(path, snapshot_interval))
+            .transpose()?;
+        let remote_input = args
+            .input_fifo
+            .as_deref()
+            .map(crate::remote::RemoteInput::open)
+            .transpose()?;
         #[allow(clippy::float_cmp)]
         let tick_rate = if args.tick_rate == 18.0 {
-            default_tick_rate_for_difficulty(args.difficulty)
+            config.profiles.get(args.difficulty).tick_rate
         } else {
             args.tick_rate
         };
+        let tutorial = args.tutorial.then(crate::tutorial::Tutorial::default);
+        let missions = args
+            .missions
+            .then(|| crate::missions::Missions::new(&state));
+        let rewind = args.rewind.then(crate::rewind::Rewind::default);
+        let hot_seat = (!args.hot_seat.is_empty())
+            .then(|| crate::hotseat::HotSeat::new(&args.hot_seat));
         let screen = if args.no_menu {
             Screen::Playing
         } else {
-            Screen::Menu
+            Screen::Title
         };
         let now = Instant::now();
+        let title = (screen == Screen::Title)
+            .then(|| crate::title::Title::new(config.colors().count(), args.reduced_motion, now));
 
         let mut menu_state = MenuState::default();
         menu_state.autoplay_enabled = args.autoplay;
         menu_state.auto_restart_enabled = args.auto_restart;
         menu_state.selected_difficulty = args.difficulty;
+        menu_state.profiles = config.profiles;
         menu_state.selected_mode = args.mode;
+        menu_state.time_limit = args.time_limit;
+        menu_state.clear_lines = args.clear_lines;
+        menu_state.seed = config.seed.map(|s| format!("{s:x}")).unwrap_or_default();
+        menu_state.custom_modes.clone_from(&config.custom_modes);
 
         Ok(Self {
             args,
@@ -166,28 +556,75 @@ impl App {
             effective_playfield_height: height,
             state,
             screen,
-            paused: false,
+            paused: None,
+            audio: crate::audio::Audio::new(volume, music),
+            bell_cues,
+            announcer,
+            feed,
+            state_stream,
+            board_view: crate::view::BoardView::default(),
+            remote_input,
+            sounded_screen: screen,
+            key_releases: true,
+            os_press: None,
+            chords,
+            hard_drop_armed: None,
+            hard_drop_held: None,
+            suspend_requested: Arc::new(AtomicBool::new(false)),
+            screenshot_requested: false,
+            last_frame: None,
+            screenshots: Vec::new(),
+            dumps: Vec::new(),
+            notice: None,
+            state_loaded: false,
+            command_line: std::env::args().skip(1).collect(),
             game_start: now,
             game_over_reason: None,
             last_tick: now,
             base_tick_rate: tick_rate,
             repeat_state: None,
             last_repeat_fire: None,
+            buffered_rotation: None,
+            held_direction: None,
+            was_controllable: false,
             last_input_time: now,
+            unshown_input: None,
+            frame_times: crate::diagnostics::FrameTimes::default(),
             line_clear_started: None,
             line_clear_effect: None,
             line_clear_effect_process_time: None,
+            celebration: None,
+            drain: None,
             menu_state,
             quit_selected: QuitOption::Resume,
+            settings_selected: 0,
+            custom_form: None,
+            last_click: None,
             high_score_endless,
             high_score_timed,
             high_score_clear,
+            custom_high_scores: crate::highscores::load_custom_high_scores(),
+            records: crate::records::Records::load(),
             high_score_at_game_start: (high_score_endless, high_score_timed, high_score_clear),
             new_high_score_this_game: false,
             time_to_40_secs: None,
+            splits,
+            inputs: Vec::new(),
+            diagnostics: None,
+            slow_passes: 0,
+            slow_hint: None,
+            low_quality: false,
+            fast_passes: 0,
+            sand_step_skipped: false,
+            tutorial,
+            missions,
+            rewind,
+            hot_seat,
+            title,
             menu_playfield_width: width,
             menu_playfield_height: height,
             last_frame_time: now,
+            last_draw: now,
             autoplay,
             autoplay_moves: std::collections::VecDeque::new(),
             last_autoplay_action: now,
@@ -196,6 +633,36 @@ impl App {
         })
     }
 
+    /// Menu seed row: hex digits type, Backspace deletes, Ctrl-V or V pastes.
+    fn edit_seed(&mut self, key: KeyEvent) {
+        let seed = &mut self.menu_state.seed;
+        match key.code {
+            KeyCode::Char('v' | 'V') => match crate::clipboard::paste() {
+                Ok(text) => match crate::parse_seed(&text) {
+                    Ok(s) => *seed = format!("{s:x}"),
+                    Err(e) => self.notify(format!(" {e} ")),
+                },
+                Err(e) => self.notify(format!(" Paste failed: {e:#} ")),
+            },
+            KeyCode::Char(c) if c.is_ascii_hexdigit() && seed.len() < SEED_DIGITS => {
+                seed.push(c.to_ascii_lowercase());
+            }
+            KeyCode::Backspace => {
+                seed.pop();
+            }
+            _ => {}
+        }
+    }
+
+    /// Start a new game straight from play (`Ctrl-R`); `same_seed` replays this game's pieces
+    /// and colours, otherwise the seed is fresh even with `--seed`.
+    fn restart(&mut self, same_seed: bool) {
+        let seed = self.config.seed;
+        self.config.seed = same_seed.then_some(self.state.seed);
+        self.reset_game(true);
+        self.config.seed = seed;
+    }
+
     /// Reset game to initial state. If `to_playing` is true, transitions to Playing screen.
     pub fn reset_game(&mut self, to_playing: bool) {
         let prev_screen = self.screen;
@@ -206,16 +673,32 @@ impl App {
 
 
         // Recalculate base tick rate according to current difficulty
-        self.base_tick_rate = default_tick_rate_for_difficulty(self.args.difficulty);
+        self.base_tick_rate = self.config.profiles.get(self.args.difficulty).tick_rate;
 
         self.state = GameState::new(self.theme.clone(), width, height, &self.config);
-        self.paused = false;
+        if let Some(tutorial) = &mut self.tutorial {
+            tutorial.restart();
+        }
+        if self.missions.is_some() {
+            self.missions = Some(crate::missions::Missions::new(&self.state));
+        }
+        if self.rewind.is_some() {
+            self.rewind = Some(crate::rewind::Rewind::default());
+        }
+        self.paused = None;
         self.game_start = now;
         self.game_over_reason = None;
         self.last_tick = now;
         self.last_input_time = now;
+        self.frame_times = crate::diagnostics::FrameTimes::default();
         self.repeat_state = None;
         self.last_repeat_fire = None;
+        self.buffered_rotation = None;
+        self.held_direction = None;
+        self.os_press = None;
+        self.hard_drop_armed = None;
+        self.hard_drop_held = None;
+        self.was_controllable = false;
         self.line_clear_started = None;
         self.line_clear_effect = None;
         self.line_clear_effect_process_time = None;
@@ -227,6 +710,8 @@ impl App {
         );
         self.new_high_score_this_game = false;
         self.time_to_40_secs = None;
+        self.splits.reset();
+        self.inputs.clear();
         self.autoplay_moves.clear();
         self.autoplay_settling = false;
 
@@ -238,7 +723,7 @@ impl App {
                 self.effective_playfield_height,
                 &self.config,
             );
-            self.state.high_color = true;
+            self.state.colors = crate::game::ColorScheme::new(6);
             self.base_tick_rate *= 1.5;
         }
 
@@ -252,8 +737,20 @@ impl App {
     }
 
     fn apply_action(&mut self, action: Action, now: Instant) {
+        if let Some(tutorial) = &mut self.tutorial {
+            tutorial.on_action(action);
+        }
         match action {
-            Action::Quit | Action::Pause | Action::None => {}
+            Action::Quit | Action::Pause | Action::Restart | Action::Retry | Action::None => {}
+            Action::Reroll => {
+                self.state.reroll(now);
+            }
+            Action::Focus => {
+                self.state.activate_focus();
+            }
+            Action::Zone => {
+                self.state.activate_zone();
+            }
             Action::MoveLeft => self.state.move_left(now),
             Action::MoveRight => self.state.move_right(now),
             Action::RotateCw => self.state.rotate_cw(now),
@@ -261,13 +758,540 @@ impl App {
             Action::SoftDrop => self.state.soft_drop(now),
             Action::HardDrop => {
                 self.state.hard_drop(now);
+                self.drop_soft_drop_repeat();
+            }
+        }
+    }
+
+    /// Co-op: player 2's move, applied to the partner piece.
+    fn apply_partner_action(&mut self, action: Action, now: Instant) {
+        self.record_input(action, now);
+        self.state.swap_seats();
+        if self.state.is_controllable(now) {
+            // Not `apply_action` for hard drops: player 1's soft drop repeat isn't theirs
+            if action == Action::HardDrop {
+                self.state.hard_drop(now);
+            } else {
+                self.apply_action(action, now);
+            }
+            if matches!(
+                action,
+                Action::MoveLeft | Action::MoveRight | Action::RotateCw | Action::RotateCcw
+            ) {
+                self.state.on_move_or_rotate(now);
+            }
+        }
+        self.state.swap_seats();
+    }
+
+    fn apply_chord(&mut self, chord: Chord, now: Instant) {
+        match chord {
+            // Between pieces the first press is already buffered (IRS only turns once)
+            Chord::Rotate180(direction) => {
+                if self.state.is_controllable(now) {
+                    self.apply_action(direction, now);
+                    self.state.on_move_or_rotate(now);
+                }
+            }
+            Chord::CancelShift => {
+                self.held_direction = None;
                 self.repeat_state = None;
+                self.last_repeat_fire = None;
+            }
+        }
+    }
+
+    /// Mouse input. In play the pointer column steers the piece, left click rotates and
+    /// right click / double-click hard drops; on the menu items are clickable.
+    fn handle_mouse(&mut self, mouse: MouseEvent) {
+        let now = Instant::now();
+        match self.screen {
+            Screen::Menu => {
+                if mouse.kind != MouseEventKind::Down(MouseButton::Left) {
+                    return;
+                }
+                let pos = ratatui::layout::Position::new(mouse.column, mouse.row);
+                let Some(target) = self
+                    .menu_state
+                    .click_targets
+                    .iter()
+                    .find(|(rect, _)| rect.contains(pos))
+                    .map(|&(_, t)| t)
+                else {
+                    return;
+                };
+                match target {
+                    MenuTarget::Difficulty(d) => {
+                        self.menu_state.current_tab = MenuTab::Difficulty;
+                        self.menu_state.selected_difficulty = d;
+                    }
+                    MenuTarget::Mode(m) => {
+                        self.menu_state.current_tab = MenuTab::Mode;
+                        self.menu_state.selected_mode = m;
+                        self.menu_state.custom = None;
+                    }
+                    MenuTarget::Custom => {
+                        self.menu_state.current_tab = MenuTab::Mode;
+                        let index = self.menu_state.custom.unwrap_or(0);
+                        self.menu_state.select_custom(index);
+                    }
+                    MenuTarget::ModeGoal => self.menu_state.current_tab = MenuTab::ModeGoal,
+                    MenuTarget::BoardWidth => self.menu_state.current_tab = MenuTab::BoardWidth,
+                    MenuTarget::Autoplay => {
+                        self.menu_state.current_tab = MenuTab::Autoplay;
+                        self.menu_activate();
+                    }
+                    MenuTarget::AutoRestart => {
+                        self.menu_state.current_tab = MenuTab::AutoRestart;
+                        self.menu_activate();
+                    }
+                    MenuTarget::Handling => {
+                        self.menu_state.current_tab = MenuTab::Handling;
+                        self.menu_activate();
+                    }
+                    MenuTarget::Seed => self.menu_state.current_tab = MenuTab::Seed,
+                    MenuTarget::Start => {
+                        self.menu_state.current_tab = MenuTab::Start;
+                        self.menu_activate();
+                    }
+                }
+            }
+            Screen::Playing if self.paused.is_none() && !self.autoplay => match mouse.kind {
+                MouseEventKind::Moved | MouseEventKind::Drag(_) => {
+                    let (cols, rows) = crossterm::terminal::size().unwrap_or((80, 24));
+                    let area = ratatui::layout::Rect::new(0, 0, cols, rows);
+                    if let Some(column) =
+                        crate::ui::playfield_column_at(area, &self.state, mouse.column)
+                    {
+                        if self.state.shift_toward(column, now) {
+                            self.state.on_move_or_rotate(now);
+                        }
+                    }
+                }
+                MouseEventKind::Down(MouseButton::Left) => {
+                    let double = self.last_click.is_some_and(|t| {
+                        now.duration_since(t) < Duration::from_millis(DOUBLE_CLICK_MS)
+                    });
+                    if double {
+                        self.last_click = None;
+                        self.apply_action(Action::HardDrop, now);
+                    } else {
+                        self.last_click = Some(now);
+                        self.apply_action(Action::RotateCw, now);
+                        self.state.on_move_or_rotate(now);
+                    }
+                }
+                MouseEventKind::Down(MouseButton::Right) => {
+                    self.apply_action(Action::HardDrop, now);
+                }
+                _ => {}
+            },
+            _ => {}
+        }
+    }
+
+    /// Sound (and announce) the game events since the last frame. The menu's background
+    /// autoplay stays quiet.
+    fn handle_game_events(&mut self) -> Result<()> {
+        let now = Instant::now();
+        let mut events = self.state.drain_events();
+        if self.screen == Screen::GameOver && self.sounded_screen != Screen::GameOver {
+            events.push(GameEvent::GameOver);
+            self.drain = (!self.no_animation()).then(|| Drain {
+                board: self.state.clone(),
+                started: now,
+                steps: 0,
+            });
+            if !self.autoplay && !self.rewound() {
+                // Custom games keep only their own high score
+                if self.config.custom.is_none() {
+                    let _ = self.records.add_game(
+                        self.args.mode,
+                        self.args.difficulty,
+                        self.state.score,
+                        self.state.lines_cleared,
+                        self.game_start.elapsed(),
+                    );
+                }
+                if let Some(hot_seat) = &mut self.hot_seat {
+                    hot_seat.record(self.state.score);
+                }
+            }
+        }
+        self.sounded_screen = self.screen;
+        if self.screen != Screen::Menu {
+            for &event in &events {
+                crate::logging::game_event(event);
+            }
+        }
+        if let Some(tutorial) = &mut self.tutorial {
+            tutorial.on_events(&events);
+            if tutorial.bridge_due && !self.state.line_clear_in_progress {
+                tutorial.bridge_due = false;
+                self.state.build_bridge();
+            }
+        }
+        if let Some(rewind) = &mut self.rewind {
+            let spawned = events.iter().any(|e| matches!(e, GameEvent::Spawn(_)));
+            if spawned && self.screen == Screen::Playing && !self.state.game_over {
+                rewind.record(&self.state, now.duration_since(self.game_start), now);
+            }
+        }
+        if let Some(missions) = &mut self.missions {
+            if self.screen == Screen::Playing && self.paused.is_none() {
+                if let Some(bonus) = missions.update(&self.state, &events, now) {
+                    self.state.award_mission(bonus);
+                }
+            }
+        }
+        if matches!(self.screen, Screen::Playing | Screen::GameOver) {
+            if let Some(announcer) = &mut self.announcer {
+                announcer.on_events(&self.state, &events);
+            }
+            if let Some(feed) = &mut self.feed {
+                feed.on_events(&self.state, &events);
+            }
+            let settled = events
+                .iter()
+                .any(|e| matches!(e, GameEvent::Lock | GameEvent::Clear));
+            for &event in &events {
+                self.audio.play(event);
+                if let Some(cues) = &mut self.bell_cues {
+                    cues.on_event(event, now);
+                }
+            }
+            if settled {
+                if let Some(cues) = &mut self.bell_cues {
+                    cues.set_danger(self.state.playfield.in_danger_zone(), now);
+                }
+            }
+        }
+        if let Some(cues) = &mut self.bell_cues {
+            cues.tick(now)?;
+        }
+        if let Some(stream) = &mut self.state_stream {
+            let status = match self.screen {
+                Screen::Title | Screen::Menu | Screen::Settings | Screen::CustomMode => "menu",
+                Screen::Playing if self.paused.is_some() => "paused",
+                Screen::Playing => "playing",
+                Screen::GameOver => "game-over",
+                Screen::Standings => "standings",
+                Screen::QuitMenu => "quit-menu",
+            };
+            self.board_view.update(&self.state);
+            stream.update(&self.state, &self.board_view, status, &events, now);
+        }
+        self.audio.update_music(match self.screen {
+            Screen::Playing if self.paused.is_some() => MusicCue::Pause,
+            Screen::Playing => MusicCue::Play {
+                level: self.state.level,
+            },
+            Screen::QuitMenu => MusicCue::Pause,
+            Screen::GameOver | Screen::Standings => MusicCue::Duck,
+            Screen::Title | Screen::Menu | Screen::Settings | Screen::CustomMode => MusicCue::Stop,
+        });
+        Ok(())
+    }
+
+    /// Line clears happen instantly, without the fade (`--no-animation`, implied by `--a11y`
+    /// and `--reduced-motion`).
+    const fn no_animation(&self) -> bool {
+        self.args.no_animation
+            || self.args.a11y
+            || self.args.reduced_motion
+            || self.args.low_bandwidth
+    }
+
+    /// `--show-inputs`: remember a gameplay action for the overlay, keeping the last
+    /// `SHOWN_INPUTS`.
+    fn record_input(&mut self, action: Action, now: Instant) {
+        if !self.args.show_inputs
+            || matches!(
+                action,
+                Action::Pause | Action::Quit | Action::Restart | Action::Retry | Action::None
+            )
+        {
+            return;
+        }
+        if self.inputs.len() == SHOWN_INPUTS {
+            self.inputs.remove(0);
+        }
+        self.inputs.push((action, now));
+    }
+
+    /// Timed mode: the clock has run out and no chain is still paying out (or the overtime is
+    /// used up).
+    fn timed_out(&self, now: Instant) -> bool {
+        let elapsed = now.duration_since(self.game_start).as_secs();
+        let limit = u64::from(self.args.time_limit);
+        elapsed >= limit && (!self.state.chain_active() || elapsed >= limit + OVERTIME_SECS)
+    }
+
+    /// `--hard-drop-safety`: whether this press of the hard-drop key drops now. Double tap
+    /// waits for a second press; hold fires from `tick_repeat` once the key has been down long
+    /// enough (double tap instead on terminals without key release events).
+    fn hard_drop_confirmed(&mut self, now: Instant) -> bool {
+        match self.config.hard_drop_safety {
+            crate::HardDropSafety::Off => true,
+            crate::HardDropSafety::Hold if self.key_releases => {
+                self.hard_drop_held = Some(now);
+                false
+            }
+            crate::HardDropSafety::Hold | crate::HardDropSafety::DoubleTap => {
+                let window = Duration::from_millis(HARD_DROP_DOUBLE_TAP_MS);
+                if self
+                    .hard_drop_armed
+                    .take()
+                    .is_some_and(|at| now.duration_since(at) < window)
+                {
+                    true
+                } else {
+                    self.hard_drop_armed = Some(now);
+                    false
+                }
+            }
+        }
+    }
+
+    /// Pause without a key press (focus lost, resumed from suspension). Held keys are
+    /// dropped too: their releases go elsewhere.
+    fn auto_pause(&mut self, reason: PauseReason) {
+        if self.screen != Screen::Playing
+            || self.paused.is_some()
+            || self.autoplay
+            || self.state.game_over
+        {
+            return;
+        }
+        self.paused = Some(reason);
+        self.hard_drop_held = None;
+        self.repeat_state = None;
+        self.last_repeat_fire = None;
+        self.held_direction = None;
+    }
+
+    /// Go to the main menu, with the attract demo playing behind it (not with
+    /// `--reduced-motion` or `--a11y`). A game in progress is abandoned.
+    fn open_menu(&mut self) {
+        self.screen = Screen::Menu;
+        self.auto_restart = false;
+        self.autoplay = !self.args.reduced_motion && !self.args.a11y;
+        if self.autoplay {
+            self.restart_demo();
+        }
+    }
+
+    /// A fresh demo game on the menu's board size.
+    fn restart_demo(&mut self) {
+        self.effective_playfield_width = self.menu_playfield_width;
+        self.effective_playfield_height = self.menu_playfield_height;
+        self.reset_game(false);
+    }
+
+    /// Confirm the selected menu item (Enter / click): start, toggle autopilot, open handling or
+    /// the custom mode form (the custom row, or start with a new custom mode picked).
+    fn menu_activate(&mut self) {
+        let custom = self.menu_state.custom_mode().cloned();
+        let open_form = match self.menu_state.current_tab {
+            MenuTab::ModeGoal => self.menu_state.custom.is_some(),
+            MenuTab::Start => self.menu_state.custom.is_some() && custom.is_none(),
+            _ => false,
+        };
+        if open_form {
+            self.custom_form = Some(crate::custom::CustomForm::new(custom.unwrap_or_default()));
+            self.screen = Screen::CustomMode;
+        } else if self.menu_state.current_tab == MenuTab::Start {
+            let (time_limit, clear_lines) = match custom.as_ref().map(|custom| custom.goal) {
+                Some(crate::custom::Goal::Timed(secs)) => (secs, self.menu_state.clear_lines),
+                Some(crate::custom::Goal::Lines(lines)) => (self.menu_state.time_limit, lines),
+                _ => (self.menu_state.time_limit, self.menu_state.clear_lines),
+            };
+            self.args.difficulty = self.menu_state.selected_difficulty;
+            self.args.mode = self.menu_state.selected_mode;
+            self.args.time_limit = time_limit;
+            if self.args.clear_lines != clear_lines {
+                self.args.clear_lines = clear_lines;
+                self.splits = crate::splits::Splits::new(self.args.clear_lines);
+            }
+            self.config.custom = custom;
+            self.config.difficulty = self.args.difficulty;
+            self.config.mode = self.args.mode;
+            self.config.seed = crate::parse_seed(&self.menu_state.seed).ok();
+            self.effective_playfield_width = self.menu_playfield_width;
+            self.effective_playfield_height = self.menu_playfield_height;
+            // Apply autoplay setting from menu
+            self.autoplay = self.menu_state.autoplay_enabled;
+            self.auto_restart = self.menu_state.auto_restart_enabled;
+            self.reset_game(true);
+        } else if self.menu_state.current_tab == MenuTab::Autoplay {
+            // Toggle autoplay with Enter/HardDrop
+            self.menu_state.autoplay_enabled = !self.menu_state.autoplay_enabled;
+        } else if self.menu_state.current_tab == MenuTab::AutoRestart {
+            self.menu_state.auto_restart_enabled = !self.menu_state.auto_restart_enabled;
+        } else if self.menu_state.current_tab == MenuTab::Handling {
+            self.settings_selected = 0;
+            self.screen = Screen::Settings;
+        } else {
+            self.menu_state.current_tab = MenuTab::Start;
+        }
+    }
+
+    /// Custom mode form keys: typing edits the name on its row; elsewhere the menu keys move
+    /// between rows and step values, and Enter on Save stores the mode. Esc goes back.
+    fn custom_form_key(&mut self, key: KeyEvent) {
+        let Some(form) = &mut self.custom_form else {
+            return;
+        };
+        match key.code {
+            KeyCode::Char(c)
+                if form.on_name() && !key.modifiers.contains(KeyModifiers::CONTROL) =>
+            {
+                form.type_char(c);
+            }
+            KeyCode::Backspace if form.on_name() => form.backspace(),
+            _ => match key_to_action(key) {
+                Action::SoftDrop => form.select(true),
+                Action::RotateCw | Action::RotateCcw => form.select(false),
+                Action::MoveLeft => form.adjust(false),
+                Action::MoveRight => form.adjust(true),
+                Action::HardDrop if form.selected == crate::custom::CustomForm::SAVE_ROW => {
+                    self.save_custom_mode();
+                }
+                Action::HardDrop => form.select(true),
+                Action::Quit => {
+                    self.custom_form = None;
+                    self.screen = Screen::Menu;
+                }
+                _ => {}
+            },
+        }
+    }
+
+    /// Save the form's mode to the config file, replacing a saved one of the same name, and
+    /// pick it on the menu. Without a name the form stays open on the name row.
+    fn save_custom_mode(&mut self) {
+        let Some(form) = &mut self.custom_form else {
+            return;
+        };
+        let name = form.draft.name.trim().to_string();
+        if name.is_empty() {
+            form.select_name();
+            return;
+        }
+        form.draft.name = name;
+        let mode = form.draft.clone();
+        let modes = &mut self.menu_state.custom_modes;
+        let index = if let Some(index) = modes.iter().position(|saved| saved.name == mode.name) {
+            modes[index] = mode;
+            index
+        } else {
+            modes.push(mode);
+            modes.len() - 1
+        };
+        let saved = crate::config::save_custom_modes(modes);
+        self.config.custom_modes.clone_from(modes);
+        if let Err(e) = saved {
+            self.notify(format!(" Saving custom modes failed: {e:#} "));
+        }
+        self.menu_state.select_custom(index);
+        self.custom_form = None;
+        self.screen = Screen::Menu;
+    }
+
+    /// Custom mode: keep this game's score as the mode's best if it beats it.
+    fn update_custom_high_score(&mut self) {
+        if self.rewound() {
+            return;
+        }
+        let Some(custom) = &self.config.custom else {
+            return;
+        };
+        let best = self
+            .custom_high_scores
+            .entry(custom.name.clone())
+            .or_default();
+        if self.state.score > *best {
+            *best = self.state.score;
+            self.new_high_score_this_game = true;
+            if !self.autoplay {
+                let _ = crate::highscores::save_custom_high_scores(&self.custom_high_scores);
+            }
+        }
+    }
+
+    /// Bests per mode for the game screens, with the custom mode's best in its goal's place.
+    fn high_scores(&self) -> crate::ui::HighScores {
+        let mut scores = (
+            self.high_score_endless,
+            self.high_score_timed,
+            self.high_score_clear,
+        );
+        if let Some(custom) = &self.config.custom {
+            let best = self
+                .custom_high_scores
+                .get(&custom.name)
+                .copied()
+                .unwrap_or(0);
+            match custom.mode() {
+                crate::GameMode::Endless => scores.0 = best,
+                crate::GameMode::Timed => scores.1 = best,
+                crate::GameMode::Clear => scores.2 = best,
+            }
+        }
+        scores
+    }
+
+    /// Apply the buffered rotation and held direction the instant a new piece becomes
+    /// controllable, so inputs made during spawn delay or a clear aren't lost.
+    fn flush_buffered_input(&mut self, now: Instant) {
+        let controllable = self.state.is_controllable(now);
+        if controllable && !self.was_controllable && !self.autoplay {
+            if let Some(rotation) = self.buffered_rotation.take() {
+                self.apply_action(rotation, now);
+                self.state.on_move_or_rotate(now);
+            }
+            if let Some(direction) = self.held_direction {
+                self.apply_action(direction, now);
+                self.state.on_move_or_rotate(now);
+                // A charged DAS carries over: keep repeating at ARR from this shift.
+                if self.repeat_state.map(|(a, _)| a) == Some(direction) {
+                    self.last_repeat_fire = Some(now);
+                }
             }
         }
+        self.was_controllable = controllable;
+    }
+
+    /// Stop a held soft drop when the piece locks so it doesn't carry into the next piece.
+    /// Held movement keeps its DAS charge across spawns.
+    fn drop_soft_drop_repeat(&mut self) {
+        if self.repeat_state.map(|(a, _)| a) == Some(Action::SoftDrop) {
+            self.repeat_state = None;
+            self.last_repeat_fire = None;
+        }
     }
 
     fn tick_repeat(&mut self) {
         let now = Instant::now();
+        if let Some(since) = self.hard_drop_held {
+            if now.duration_since(since) >= Duration::from_millis(HARD_DROP_HOLD_MS) {
+                self.hard_drop_held = None;
+                self.apply_action(Action::HardDrop, now);
+            }
+        }
+        if let Some((action, at)) = self.os_press {
+            if now.duration_since(at) >= Duration::from_millis(OS_REPEAT_GAP_MS) {
+                // OS repeats stopped: treat as the release we never get
+                self.os_press = None;
+                if self.repeat_state.map(|(a, _)| a) == Some(action) {
+                    self.repeat_state = None;
+                    self.last_repeat_fire = None;
+                }
+                if self.held_direction == Some(action) {
+                    self.held_direction = None;
+                }
+            }
+        }
         let Some((action, first)) = self.repeat_state else {
             return;
         };
@@ -282,11 +1306,18 @@ impl App {
         // Removed safety fallback that assumed sticky keys after 100ms;
         // now relying on KeyEventKind::Release and standard DAS/ARR logic.
 
-        if first.elapsed() < Duration::from_millis(REPEAT_DELAY_MS) {
+        let handling = self.config.handling;
+        if first.elapsed() < Duration::from_millis(handling.das_ms) {
+            return;
+        }
+        // Between pieces the charge is held, not spent.
+        if !self.state.is_controllable(now) {
             return;
         }
-        let next =
-            self.last_repeat_fire.unwrap_or(first) + Duration::from_millis(REPEAT_INTERVAL_MS);
+        let Some(interval_ms) = handling.repeat.interval_ms(action) else {
+            return;
+        };
+        let next = self.last_repeat_fire.unwrap_or(first) + Duration::from_millis(interval_ms);
         if now >= next {
             self.apply_action(action, now);
             if matches!(
@@ -296,18 +1327,21 @@ impl App {
                 self.state.on_move_or_rotate(now);
             }
             self.last_repeat_fire = Some(now);
+            if self.state.line_clear_in_progress || self.state.piece.is_none() {
+                self.drop_soft_drop_repeat();
+            }
         }
     }
 
-    pub fn run(&mut self) -> Result<()> {
+    /// Raw mode, alternate screen, and the optional input reporting (key release, mouse, focus).
+    fn enter_terminal(&self) -> Result<()> {
         use crossterm::{
             event::{
-                KeyboardEnhancementFlags, PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags,
+                EnableFocusChange, EnableMouseCapture, KeyboardEnhancementFlags,
+                PushKeyboardEnhancementFlags,
             },
             execute,
-            terminal::{
-                EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode, size,
-            },
+            terminal::{EnterAlternateScreen, enable_raw_mode},
         };
 
         enable_raw_mode()?;
@@ -319,20 +1353,160 @@ impl App {
             stdout,
             PushKeyboardEnhancementFlags(KeyboardEnhancementFlags::REPORT_EVENT_TYPES)
         );
+        if !self.args.no_mouse {
+            execute!(stdout, EnableMouseCapture)?;
+        }
+        let _ = execute!(stdout, EnableFocusChange);
+        Ok(())
+    }
+
+    /// Ctrl-Z / SIGTSTP: give the terminal back and stop. After SIGCONT take it over
+    /// again, pause the game and redraw everything.
+    #[cfg(unix)]
+    fn suspend(&mut self, terminal: &mut GameTerminal) -> Result<()> {
+        restore_terminal()?;
+        signal_hook::low_level::emulate_default_handler(signal_hook::consts::SIGTSTP)?;
+        // Stopped until SIGCONT
+        self.enter_terminal()?;
+        terminal.clear()?;
+        self.auto_pause(PauseReason::User);
+        Ok(())
+    }
+
+    #[cfg(not(unix))]
+    fn suspend(&mut self, _terminal: &mut GameTerminal) -> Result<()> {
+        Ok(())
+    }
+
+    /// `--load-state`: play on from a state dump.
+    pub fn load_state(&mut self, dump: &crate::dump::StateDump) -> Result<()> {
+        let now = Instant::now();
+        self.state = dump.restore(self.theme.clone(), &self.config, now)?;
+        self.game_start = now.checked_sub(dump.elapsed()).unwrap_or(now);
+        self.state_loaded = true;
+        self.command_line = dump.command_line().to_vec();
+        if self.missions.is_some() {
+            self.missions = Some(crate::missions::Missions::new(&self.state));
+        }
+        Ok(())
+    }
+
+    /// `--rewind` on the game over screen: only after topping out, and not for autoplay or hot
+    /// seat games.
+    fn rewind_offered(&self) -> bool {
+        self.rewind.is_some()
+            && !self.autoplay
+            && self.hot_seat.is_none()
+            && self.game_over_reason == Some(GameOverReason::StackOverflow)
+    }
+
+    /// This run went back with `--rewind`, so it sets no records.
+    fn rewound(&self) -> bool {
+        self.rewind
+            .as_ref()
+            .is_some_and(crate::rewind::Rewind::used)
+    }
+
+    /// Game over `U`: back to the game `REWIND_PIECES` pieces ago, paused so the player can get
+    /// ready.
+    fn rewind_game(&mut self) {
+        let now = Instant::now();
+        if !self.rewind_offered() {
+            return;
+        }
+        let Some((state, elapsed)) = self.rewind.as_mut().and_then(|rewind| rewind.rewind(now))
+        else {
+            return;
+        };
+        self.state = state;
+        self.game_start = now.checked_sub(elapsed).unwrap_or(now);
+        self.game_over_reason = None;
+        self.drain = None;
+        self.celebration = None;
+        self.last_tick = now;
+        self.line_clear_started = None;
+        self.line_clear_effect = None;
+        self.line_clear_effect_process_time = None;
+        self.paused = Some(PauseReason::User);
+        self.screen = Screen::Playing;
+    }
+
+    /// F10: save the game state for a bug report and show where it went.
+    fn dump_state(&mut self) {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.game_start);
+        let saved = crate::dump::save(
+            &self.state,
+            &self.command_line,
+            &self.args,
+            &self.config,
+            elapsed,
+            now,
+        );
+        let notice = match saved {
+            Ok(path) => {
+                let notice = format!(" State saved to {} ", path.display());
+                self.dumps.push(path);
+                notice
+            }
+            Err(e) => format!(" State dump failed: {e:#} "),
+        };
+        self.notice = Some((now, notice));
+    }
+
+    /// Show `text` at the top of the screen for `NOTICE_SECS`.
+    fn notify(&mut self, text: String) {
+        self.notice = Some((Instant::now(), text));
+    }
+
+    pub fn run(&mut self) -> Result<()> {
+        use crossterm::terminal::size;
 
-        let mut terminal =
-            ratatui::DefaultTerminal::new(ratatui::backend::CrosstermBackend::new(stdout))?;
+        // A panic mid-game must not leave the shell in raw mode on the alternate screen:
+        // restore first, then let the default hook print the message and backtrace.
+        let default_hook = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            let _ = restore_terminal();
+            default_hook(info);
+        }));
+
+        #[cfg(unix)]
+        signal_hook::flag::register(
+            signal_hook::consts::SIGTSTP,
+            Arc::clone(&self.suspend_requested),
+        )?;
+
+        self.enter_terminal()?;
+        // Plain xterm and friends: no release events, fall back to OS repeats. The Windows
+        // console reports releases natively (and has no enhancement flags to query).
+        self.key_releases = cfg!(windows)
+            || crossterm::terminal::supports_keyboard_enhancement().unwrap_or(false);
+        // Buffered so each frame goes out in one write rather than a line buffer's worth at a time
+        let mut terminal = ratatui::Terminal::new(ratatui::backend::CrosstermBackend::new(
+            std::io::BufWriter::with_capacity(FRAME_BUFFER_BYTES, std::io::stdout()),
+        ))?;
 
         // Size playfield to fit terminal (no squeeze); respect --width/--height when they fit
         let (term_cols, term_rows) = size()?;
+        crate::logging::terminal(term_cols, term_rows, self.key_releases);
         let (fit_w, fit_h) = crate::ui::playfield_size_for_terminal_clamped(term_cols, term_rows);
-        let requested_w = crate::effective_playfield_width(self.args.difficulty, self.args.width);
-        let requested_h = self.args.height;
-        self.effective_playfield_width = requested_w.min(fit_w).max(1);
-        self.effective_playfield_height = requested_h.min(fit_h).max(1);
+        let (requested_w, requested_h) = self
+            .config
+            .profiles
+            .get(self.args.difficulty)
+            .playfield_size(self.args.width.min(fit_w), self.args.height.min(fit_h));
+        self.effective_playfield_width = requested_w.max(1);
+        self.effective_playfield_height = requested_h.max(1);
         self.menu_playfield_width = self.effective_playfield_width;
         self.menu_playfield_height = self.effective_playfield_height;
-        let need_resize = self.state.playfield.width != self.effective_playfield_width as usize
+        // --dual: both boards' width
+        let boards = if self.config.dual { 2 } else { 1 };
+        if self.state_loaded {
+            self.effective_playfield_width = self.state.playfield.width as u16 * boards;
+            self.effective_playfield_height = self.state.playfield.height as u16;
+        }
+        let board_width = GameState::board_width(self.effective_playfield_width, self.config.dual);
+        let need_resize = self.state.playfield.width != usize::from(board_width)
             || self.state.playfield.height != self.effective_playfield_height as usize;
         if need_resize {
             self.state = GameState::new(
@@ -343,67 +1517,209 @@ impl App {
             );
         }
 
-        let result = self.run_loop(&mut terminal);
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            self.run_loop(&mut terminal)
+        }));
 
         // Restore
-        let _ = execute!(std::io::stdout(), PopKeyboardEnhancementFlags);
-        execute!(std::io::stdout(), LeaveAlternateScreen)?;
-        disable_raw_mode()?;
+        restore_terminal()?;
 
-        result
+        if let Some((buffer, area)) = self.last_frame.take() {
+            self.screenshots.push(crate::screenshot::save(&buffer, area));
+        }
+        for shot in &self.screenshots {
+            match shot {
+                Ok(path) => eprintln!("setrixtui: screenshot saved to {}", path.display()),
+                Err(e) => eprintln!("setrixtui: screenshot failed: {e:#}"),
+            }
+        }
+        for path in &self.dumps {
+            eprintln!("setrixtui: state dump saved to {}", path.display());
+        }
+
+        match result {
+            Ok(result) => result,
+            Err(panic) => std::panic::resume_unwind(panic),
+        }
     }
 
     #[allow(clippy::too_many_lines)]
-    fn run_loop(&mut self, terminal: &mut DefaultTerminal) -> Result<()> {
+    fn run_loop(&mut self, terminal: &mut GameTerminal) -> Result<()> {
         loop {
+            if self.suspend_requested.swap(false, Ordering::Relaxed) {
+                self.suspend(terminal)?;
+            }
+            self.handle_game_events()?;
             let now = Instant::now();
             let dt_secs = now.duration_since(self.last_frame_time).as_secs_f32();
             self.last_frame_time = now;
             self.state.tick_piece_visual(dt_secs);
+            if let Some(title) = &mut self.title {
+                title.tick(now);
+            }
+            if self.drain.as_mut().is_some_and(|drain| !drain.tick(now)) {
+                self.drain = None;
+            }
             if self.screen == Screen::Menu {
                 let (c, r) = crossterm::terminal::size().unwrap_or((80, 24));
-                let (w, h) = crate::ui::playfield_size_for_terminal_clamped(c, r);
+                let (w, h) = self
+                    .menu_state
+                    .zoomed_size(crate::ui::playfield_size_for_terminal_clamped(c, r));
+                let w = match self.menu_state.board_width {
+                    Some(width) if self.menu_state.has_mode_options() => w.min(width),
+                    _ => w,
+                };
+                let (w, h) = self
+                    .config
+                    .profiles
+                    .get(self.menu_state.selected_difficulty)
+                    .playfield_size(w, h);
                 self.menu_playfield_width = w;
                 self.menu_playfield_height = h;
+                // The demo follows the board this terminal would give (after a resize too)
+                let demo_size = (self.state.playfield.width, self.state.playfield.height);
+                let board_width = GameState::board_width(w, self.config.dual);
+                if self.autoplay && demo_size != (usize::from(board_width), usize::from(h)) {
+                    self.restart_demo();
+                }
+            }
+            // --low-bandwidth: a frame every LOW_BANDWIDTH_FRAME_MS, or straight after input
+            let redraw = !self.args.low_bandwidth
+                || now.duration_since(self.last_draw)
+                    >= Duration::from_millis(LOW_BANDWIDTH_FRAME_MS)
+                || self.last_input_time > self.last_draw;
+            if redraw {
+                self.last_draw = now;
+                let menu_size = (self.screen == Screen::Menu)
+                    .then_some((self.menu_playfield_width, self.menu_playfield_height));
+                let tutorial = self
+                    .tutorial
+                    .as_ref()
+                    .map(|tutorial| tutorial.prompt(&self.config.keys));
+                let rewind_offered = self.rewind_offered();
+                let mission = self
+                    .missions
+                    .as_ref()
+                    .filter(|_| self.screen != Screen::Menu)
+                    .map(crate::missions::Missions::card);
+                let options = crate::ui::RenderOptions {
+                    no_animation: self.no_animation(),
+                    // Every tint change repaints the whole board
+                    dynamic_background: self.args.dynamic_background && !self.args.low_bandwidth,
+                    reduced_motion: self.args.reduced_motion || self.args.low_bandwidth,
+                    reduce_flashing: self.args.reduce_flashing,
+                    symbols: self.args.symbols,
+                    blend_sand: self.args.blend_sand,
+                    high_contrast_board: self.args.a11y,
+                    announcement: self.announcer.as_ref().map(crate::a11y::Announcer::line),
+                    events: self.feed.as_ref().map(crate::feed::EventFeed::lines),
+                    inputs: &self.inputs,
+                    diagnostics: self
+                        .diagnostics
+                        .as_ref()
+                        .map(crate::diagnostics::Diagnostics::stats),
+                    frame_times: Some(&self.frame_times),
+                    low_quality: self.low_quality || self.args.low_bandwidth,
+                    slow_hint: self.slow_hint.is_some_and(|shown| {
+                        now.duration_since(shown) < Duration::from_secs(SLOW_HINT_SECS)
+                    }),
+                    notice: self
+                        .notice
+                        .as_ref()
+                        .filter(|(shown, _)| {
+                            now.duration_since(*shown) < Duration::from_secs(NOTICE_SECS)
+                        })
+                        .map(|(_, notice)| notice.as_str()),
+                    tutorial: tutorial.as_deref(),
+                    mission: mission.as_ref(),
+                    rewind: self.rewind.as_ref().filter(|_| rewind_offered),
+                    hot_seat: self.hot_seat.as_ref(),
+                    player: self
+                        .hot_seat
+                        .as_ref()
+                        .filter(|_| matches!(self.screen, Screen::Playing | Screen::QuitMenu))
+                        .map(crate::hotseat::HotSeat::current),
+                    title: self.title.as_ref(),
+                    draining: self.drain.is_some(),
+                    custom_form: self.custom_form.as_ref(),
+                };
+                if self.screen != Screen::GameOver {
+                    self.celebration = None;
+                    self.drain = None;
+                }
+                let high_scores = self.high_scores();
+                let draw_start = Instant::now();
+                let frame = terminal.draw(|f| {
+                    crate::ui::draw(
+                        f,
+                        self.screen,
+                        self.drain
+                            .as_ref()
+                            .map_or(&self.state, |drain| &drain.board),
+                        self.paused,
+                        self.game_over_reason,
+                        self.args.mode,
+                        self.args.clear_lines,
+                        self.args.time_limit,
+                        self.game_start,
+                        f.area(),
+                        &mut self.line_clear_effect,
+                        &mut self.line_clear_effect_process_time,
+                        &mut self.menu_state,
+                        now,
+                        options,
+                        if self.screen == Screen::QuitMenu {
+                            Some(self.quit_selected)
+                        } else {
+                            None
+                        },
+                        (self.screen == Screen::Settings).then_some((
+                            self.config.handling,
+                            self.config.keys.profile,
+                            self.config.music,
+                            self.settings_selected,
+                        )),
+                        menu_size,
+                        high_scores,
+                        self.new_high_score_this_game,
+                        &mut self.celebration,
+                        self.time_to_40_secs,
+                        &self.splits,
+                        &self.records,
+                        self.autoplay,
+                    );
+                })?;
+                if let Some(diagnostics) = &mut self.diagnostics {
+                    diagnostics.frame();
+                }
+                let draw_time = draw_start.elapsed();
+                crate::logging::frame(draw_time);
+                if let Some((action, read_at)) = self.unshown_input.take() {
+                    crate::logging::input(action, read_at.elapsed());
+                }
+                if matches!(self.screen, Screen::Playing | Screen::GameOver) {
+                    let area = crate::ui::game_area(frame.area, &self.state, &options);
+                    if std::mem::take(&mut self.screenshot_requested) {
+                        self.screenshots
+                            .push(crate::screenshot::save(frame.buffer, area));
+                    }
+                    if self.args.screenshot_on_exit {
+                        match &mut self.last_frame {
+                            Some((buffer, last_area)) => {
+                                buffer.clone_from(frame.buffer);
+                                *last_area = area;
+                            }
+                            None => self.last_frame = Some((frame.buffer.clone(), area)),
+                        }
+                    }
+                }
+                if self.screen == Screen::Playing && self.paused.is_none() {
+                    self.frame_times.render.record(draw_time);
+                }
             }
-            let menu_size = (self.screen == Screen::Menu)
-                .then_some((self.menu_playfield_width, self.menu_playfield_height));
-            terminal.draw(|f| {
-                crate::ui::draw(
-                    f,
-                    self.screen,
-                    &self.state,
-                    self.paused,
-                    self.game_over_reason,
-                    self.args.mode,
-                    self.args.clear_lines,
-                    self.args.time_limit,
-                    self.game_start,
-                    f.area(),
-                    &mut self.line_clear_effect,
-                    &mut self.line_clear_effect_process_time,
-                    &mut self.menu_state,
-                    now,
-                    self.args.no_animation,
-                    if self.screen == Screen::QuitMenu {
-                        Some(self.quit_selected)
-                    } else {
-                        None
-                    },
-                    menu_size,
-                    (
-                        self.high_score_endless,
-                        self.high_score_timed,
-                        self.high_score_clear,
-                    ),
-                    self.new_high_score_this_game,
-                    self.time_to_40_secs,
-                    self.autoplay,
-                );
-            })?;
 
             if self.state.line_clear_in_progress
-                && !self.args.no_animation
+                && !self.no_animation()
                 && self.line_clear_effect.as_ref().is_some_and(Effect::done)
             {
                 self.state.finish_line_clear();
@@ -415,35 +1731,72 @@ impl App {
             let mut rate = if self.args.relaxed {
                 self.base_tick_rate
             } else {
-                self.base_tick_rate * (1.0 + f64::from(self.state.level.saturating_sub(1)) * 0.1)
+                let levels = f64::from(self.state.level.saturating_sub(1));
+                self.base_tick_rate * (1.0 + levels * self.state.profile.level_speedup)
             };
 
             if self.menu_state.ratman_unlocked {
                 rate *= 2.0;
             }
+            rate *= self.config.speed;
+            if self.state.focus_active() {
+                rate /= crate::game::FOCUS_SLOWDOWN;
+            }
 
             let tick_interval = Duration::from_secs_f64(1.0 / rate);
 
             // Higher refresh rate for smooth movement and responsive input (4ms ≈ 250 FPS)
             let frame_duration = Duration::from_millis(4);
             let loop_elapsed = now.elapsed();
+            if let Some(diagnostics) = &mut self.diagnostics {
+                diagnostics.pass(loop_elapsed, Instant::now());
+            }
+            let budget_used = loop_elapsed.as_secs_f64() * self.args.frame_rate;
+            if budget_used > 1.0 {
+                self.slow_passes += 1;
+                self.fast_passes = 0;
+                if self.slow_passes >= SLOW_PASSES && self.slow_hint.is_none() {
+                    self.slow_hint = Some(now);
+                }
+                if self.slow_passes >= SLOW_PASSES
+                    && !self.low_quality
+                    && !self.args.no_adaptive_quality
+                {
+                    self.low_quality = true;
+                    crate::logging::quality(true);
+                }
+            } else {
+                self.slow_passes = 0;
+                self.fast_passes = if budget_used < 0.5 {
+                    self.fast_passes + 1
+                } else {
+                    0
+                };
+                if self.low_quality && self.fast_passes >= QUALITY_RECOVER_PASSES {
+                    self.low_quality = false;
+                    crate::logging::quality(false);
+                }
+            }
             let timeout = frame_duration.saturating_sub(loop_elapsed);
 
 
             // Tick popups
-            self.state.tick_popups(16);
+            self.state.tick_popups(16, !self.args.reduced_motion);
 
             // Timed mode check
-            if self.screen == Screen::Playing && self.args.mode == crate::GameMode::Timed {
-                let elapsed = now.duration_since(self.game_start).as_secs();
-                if elapsed >= u64::from(self.args.time_limit) {
-                    self.screen = Screen::GameOver;
-                    self.game_over_reason = Some(GameOverReason::TimeUp);
-                }
+            if self.screen == Screen::Playing
+                && self.args.mode == crate::GameMode::Timed
+                && self.timed_out(now)
+            {
+                self.screen = Screen::GameOver;
+                self.game_over_reason = Some(GameOverReason::TimeUp);
             }
 
             // High score update (during play for Endless/Timed; Clear is updated on win below)
             match self.args.mode {
+                // The menu demo and rewound runs never set records
+                _ if self.screen == Screen::Menu || self.rewound() => {}
+                _ if self.config.custom.is_some() => self.update_custom_high_score(),
                 crate::GameMode::Endless => {
                     if self.state.score > self.high_score_endless {
                         self.high_score_endless = self.state.score;
@@ -473,10 +1826,96 @@ impl App {
                 crate::GameMode::Clear => {}
             }
 
-            if event::poll(timeout)? {
-                while event::poll(Duration::ZERO)? {
-                    if let Event::Key(key) = event::read()? {
-                        let action = key_to_action(key);
+            // `--input-fifo` actions go through the key handling below as taps of a blank key
+            let mut remote_actions = self
+                .remote_input
+                .as_ref()
+                .map(crate::remote::RemoteInput::drain)
+                .unwrap_or_default();
+            if !remote_actions.is_empty() || event::poll(timeout)? {
+                loop {
+                    let remote_action = remote_actions.pop_front();
+                    let ev = if remote_action.is_some() {
+                        Event::Key(KeyEvent::new(KeyCode::Null, KeyModifiers::NONE))
+                    } else if event::poll(Duration::ZERO)? {
+                        event::read()?
+                    } else {
+                        break;
+                    };
+                    if let Event::Mouse(mouse) = ev {
+                        self.handle_mouse(mouse);
+                        continue;
+                    }
+                    if ev == Event::FocusLost {
+                        self.auto_pause(PauseReason::FocusLost);
+                        continue;
+                    }
+                    if let Event::Key(key) = ev {
+                        // Raw mode turns off ISIG, so Ctrl-Z arrives as a key rather than SIGTSTP
+                        if key.kind == KeyEventKind::Press
+                            && key.code == KeyCode::Char('z')
+                            && key.modifiers == KeyModifiers::CONTROL
+                        {
+                            self.suspend(terminal)?;
+                            continue;
+                        }
+                        if key.kind == KeyEventKind::Press && key.code == KeyCode::F(3) {
+                            self.diagnostics = self
+                                .diagnostics
+                                .is_none()
+                                .then(|| crate::diagnostics::Diagnostics::new(Instant::now()));
+                            continue;
+                        }
+                        if key.kind == KeyEventKind::Press && key.code == KeyCode::F(12) {
+                            self.screenshot_requested =
+                                matches!(self.screen, Screen::Playing | Screen::GameOver);
+                            continue;
+                        }
+                        if key.kind == KeyEventKind::Press && key.code == KeyCode::F(10) {
+                            if matches!(self.screen, Screen::Playing | Screen::GameOver) {
+                                self.dump_state();
+                            }
+                            continue;
+                        }
+                    }
+                    if let Event::Key(key) = ev {
+                        // Any key leaves the title screen
+                        if self.screen == Screen::Title {
+                            if key.kind == KeyEventKind::Press {
+                                self.title = None;
+                                self.menu_state.animation_start = Instant::now();
+                                self.open_menu();
+                            }
+                            continue;
+                        }
+                        if self.screen == Screen::CustomMode {
+                            if key.kind == KeyEventKind::Press {
+                                self.custom_form_key(key);
+                            }
+                            continue;
+                        }
+                        // Co-op: player 2's keys steer the partner piece. Their OS repeats
+                        // count as presses; DAS and chords are player 1's.
+                        if let Some(action) = crate::input::partner_action(key.code).filter(|_| {
+                            self.args.coop
+                                && self.screen == Screen::Playing
+                                && self.paused.is_none()
+                                && remote_action.is_none()
+                                && key.modifiers.is_empty()
+                        }) {
+                            self.last_input_time = Instant::now();
+                            if key.kind != KeyEventKind::Release {
+                                self.apply_partner_action(action, now);
+                            }
+                            continue;
+                        }
+                        let action = if let Some(action) = remote_action {
+                            action
+                        } else if self.screen == Screen::Playing {
+                            self.config.keys.action(key)
+                        } else {
+                            key_to_action(key)
+                        };
                         self.last_input_time = Instant::now();
 
                         // Ignore OS repeats and only process first Press.
@@ -488,13 +1927,43 @@ impl App {
                                 self.repeat_state = None;
                                 self.last_repeat_fire = None;
                             }
+                            if key.kind == KeyEventKind::Release
+                                && self.held_direction == Some(action)
+                            {
+                                self.held_direction = None;
+                            }
+                            if key.kind == KeyEventKind::Release && action == Action::HardDrop {
+                                self.hard_drop_held = None;
+                            }
                             continue;
                         }
 
+                        // Without release events, a quick second press of the same key is the OS
+                        // repeating a held key
+                        let os_repeat = !self.key_releases
+                            && remote_action.is_none()
+                            && self.os_press.is_some_and(|(a, at)| {
+                                a == action
+                                    && self.last_input_time.duration_since(at)
+                                        < Duration::from_millis(OS_REPEAT_WINDOW_MS)
+                            });
+                        if !self.key_releases
+                            && self.screen == Screen::Playing
+                            && remote_action.is_none()
+                        {
+                            self.os_press = Some((action, self.last_input_time));
+                        }
+
                         // If we are already repeating this action, ignore subsequent OS Press events
-                        if self.repeat_state.map(|(a, _)| a) == Some(action) {
+                        if self.repeat_state.map(|(a, _)| a) == Some(action)
+                            && remote_action.is_none()
+                        {
                             continue;
                         }
+                        if self.screen == Screen::Playing && remote_action.is_none() {
+                            self.unshown_input
+                                .get_or_insert((action, self.last_input_time));
+                        }
 
                         match self.screen {
                             Screen::Menu => {
@@ -515,16 +1984,9 @@ impl App {
                                                 }
                                             };
                                         }
-                                        MenuTab::Mode => {
-                                            self.menu_state.selected_mode = match self
-                                                .menu_state
-                                                .selected_mode
-                                            {
-                                                crate::GameMode::Endless => crate::GameMode::Clear,
-                                                crate::GameMode::Timed => crate::GameMode::Endless,
-                                                crate::GameMode::Clear => crate::GameMode::Timed,
-                                            };
-                                        }
+                                        MenuTab::Mode => self.menu_state.cycle_mode(false),
+                                        MenuTab::ModeGoal => self.menu_state.adjust_goal(false),
+                                        MenuTab::BoardWidth => self.menu_state.adjust_width(false),
                                         MenuTab::Autoplay => {
                                             // Move to AutoRestart (wrap or side?)
                                             // Side-by-side means Left from Autoplay might wrap to AutoRestart or do nothing?
@@ -534,7 +1996,7 @@ impl App {
                                         MenuTab::AutoRestart => {
                                             self.menu_state.current_tab = MenuTab::Autoplay;
                                         }
-                                        MenuTab::Start => {}
+                                        MenuTab::Handling | MenuTab::Seed | MenuTab::Start => {}
                                     },
                                     Action::MoveRight => match self.menu_state.current_tab {
                                         MenuTab::Difficulty => {
@@ -551,30 +2013,29 @@ impl App {
                                                 crate::Difficulty::Hard => crate::Difficulty::Easy,
                                             };
                                         }
-                                        MenuTab::Mode => {
-                                            self.menu_state.selected_mode = match self
-                                                .menu_state
-                                                .selected_mode
-                                            {
-                                                crate::GameMode::Endless => crate::GameMode::Timed,
-                                                crate::GameMode::Timed => crate::GameMode::Clear,
-                                                crate::GameMode::Clear => crate::GameMode::Endless,
-                                            };
-                                        }
+                                        MenuTab::Mode => self.menu_state.cycle_mode(true),
+                                        MenuTab::ModeGoal => self.menu_state.adjust_goal(true),
+                                        MenuTab::BoardWidth => self.menu_state.adjust_width(true),
                                         MenuTab::Autoplay => {
                                             self.menu_state.current_tab = MenuTab::AutoRestart;
                                         }
                                         MenuTab::AutoRestart => {
                                             self.menu_state.current_tab = MenuTab::Autoplay;
                                         }
-                                        MenuTab::Start => {}
+                                        MenuTab::Handling | MenuTab::Seed | MenuTab::Start => {}
                                     },
                                     Action::SoftDrop => {
                                         self.menu_state.current_tab =
                                             match self.menu_state.current_tab {
                                                 MenuTab::Difficulty => MenuTab::Mode,
-                                                MenuTab::Mode => MenuTab::Autoplay,
-                                                MenuTab::Autoplay | MenuTab::AutoRestart => MenuTab::Start,
+                                                MenuTab::Mode if self.menu_state.has_mode_options() => {
+                                                    MenuTab::ModeGoal
+                                                }
+                                                MenuTab::Mode | MenuTab::BoardWidth => MenuTab::Autoplay,
+                                                MenuTab::ModeGoal => MenuTab::BoardWidth,
+                                                MenuTab::Autoplay | MenuTab::AutoRestart => MenuTab::Handling,
+                                                MenuTab::Handling => MenuTab::Seed,
+                                                MenuTab::Seed => MenuTab::Start,
                                                 MenuTab::Start => MenuTab::Difficulty,
                                             };
                                     }
@@ -583,32 +2044,25 @@ impl App {
                                             match self.menu_state.current_tab {
                                                 MenuTab::Difficulty => MenuTab::Start,
                                                 MenuTab::Mode => MenuTab::Difficulty,
+                                                MenuTab::ModeGoal => MenuTab::Mode,
+                                                MenuTab::BoardWidth => MenuTab::ModeGoal,
+                                                MenuTab::Autoplay | MenuTab::AutoRestart
+                                                    if self.menu_state.has_mode_options() =>
+                                                {
+                                                    MenuTab::BoardWidth
+                                                }
                                                 MenuTab::Autoplay | MenuTab::AutoRestart => MenuTab::Mode,
-                                                MenuTab::Start => MenuTab::Autoplay,
+                                                MenuTab::Handling => MenuTab::Autoplay,
+                                                MenuTab::Seed => MenuTab::Handling,
+                                                MenuTab::Start => MenuTab::Seed,
                                             };
                                     }
-                                    Action::HardDrop => {
-                                        if self.menu_state.current_tab == MenuTab::Start {
-                                            self.args.difficulty =
-                                                self.menu_state.selected_difficulty;
-                                            self.args.mode = self.menu_state.selected_mode;
-                                            self.config.difficulty = self.args.difficulty;
-                                            self.effective_playfield_width =
-                                                self.menu_playfield_width;
-                                            self.effective_playfield_height =
-                                                self.menu_playfield_height;
-                                            // Apply autoplay setting from menu
-                                            self.autoplay = self.menu_state.autoplay_enabled;
-                                            self.auto_restart = self.menu_state.auto_restart_enabled;
-                                            self.reset_game(true);
-                                        } else if self.menu_state.current_tab == MenuTab::Autoplay {
-                                            // Toggle autoplay with Enter/HardDrop
-                                             self.menu_state.autoplay_enabled = !self.menu_state.autoplay_enabled;
-                                        } else if self.menu_state.current_tab == MenuTab::AutoRestart {
-                                             self.menu_state.auto_restart_enabled = !self.menu_state.auto_restart_enabled;
-                                        } else {
-                                            self.menu_state.current_tab = MenuTab::Start;
-                                        }
+                                    Action::HardDrop => self.menu_activate(),
+                                    _ if self.menu_state.current_tab == MenuTab::Seed => {
+                                        self.edit_seed(key);
+                                    }
+                                    _ if matches!(key.code, KeyCode::Char('+' | '=' | '-')) => {
+                                        self.menu_state.adjust_zoom(key.code != KeyCode::Char('-'));
                                     }
                                     _ => {
                                         if let KeyCode::Char(c) = key.code {
@@ -633,6 +2087,7 @@ impl App {
                                                     self.menu_state.selected_difficulty;
                                                 self.args.mode = self.menu_state.selected_mode;
                                                 self.config.difficulty = self.args.difficulty;
+                                                self.config.mode = self.args.mode;
                                                 self.effective_playfield_width =
                                                     self.menu_playfield_width;
                                                 self.effective_playfield_height =
@@ -646,22 +2101,47 @@ impl App {
                                 }
                             }
                             Screen::Playing => {
-                                if self.paused {
+                                if self.paused.is_none() {
+                                    self.record_input(action, now);
+                                }
+                                if self.paused.is_some() {
                                     if action == Action::Pause {
-                                        self.paused = false;
+                                        self.paused = None;
                                     } else if action == Action::Quit {
                                         self.screen = Screen::QuitMenu;
                                         self.quit_selected = QuitOption::Resume;
                                     }
+                                } else if let Some(chord) = self
+                                    .chords
+                                    .as_mut()
+                                    .filter(|_| remote_action.is_none())
+                                    .and_then(|c| c.press(action, now, self.held_direction))
+                                {
+                                    self.apply_chord(chord, now);
                                 } else {
                                     match action {
-                                        Action::Pause => self.paused = true,
+                                        Action::Pause => self.paused = Some(PauseReason::User),
                                         Action::Quit => {
                                             self.screen = Screen::QuitMenu;
                                             self.quit_selected = QuitOption::Resume;
                                         }
+                                        Action::Restart => self.restart(false),
+                                        Action::Retry => self.restart(true),
+                                        Action::MoveLeft | Action::MoveRight | Action::RotateCw
+                                        | Action::RotateCcw
+                                            if !self.state.is_controllable(now) =>
+                                        {
+                                            // Not controllable (spawn delay / clear): keep for the next piece
+                                            if matches!(action, Action::RotateCw | Action::RotateCcw) {
+                                                self.buffered_rotation = Some(action);
+                                            }
+                                        }
+                                        Action::HardDrop
+                                            if remote_action.is_none()
+                                                && !self.hard_drop_confirmed(now) => {}
                                         Action::MoveLeft | Action::MoveRight | Action::RotateCw 
-                                        | Action::RotateCcw | Action::SoftDrop | Action::HardDrop => {
+                                        | Action::RotateCcw | Action::SoftDrop | Action::HardDrop
+                                        | Action::Reroll | Action::Focus | Action::Zone => {
                                              self.apply_action(action, now);
                                              if matches!(action, Action::MoveLeft | Action::MoveRight 
                                                  | Action::RotateCw | Action::RotateCcw) {
@@ -671,21 +2151,25 @@ impl App {
                                         _ => {}
                                     }
                                     
-                                    let repeatable = matches!(
-                                        action,
-                                        Action::MoveLeft | Action::MoveRight | Action::SoftDrop
-                                    );
-                                    if repeatable {
+                                    // Remote actions are taps: nothing stays held
+                                    let held = remote_action.is_none();
+                                    if held && matches!(action, Action::MoveLeft | Action::MoveRight) {
+                                        self.held_direction = Some(action);
+                                    }
+                                    let repeatable =
+                                        self.config.handling.repeat.interval_ms(action).is_some();
+                                    // Without release events a key only counts as held once the OS repeats it
+                                    if held && repeatable && (self.key_releases || os_repeat) {
                                         self.repeat_state = Some((action, now));
                                         self.last_repeat_fire = None;
                                     }
                                 }
 
-                                // If the action caused a lock, clear repeat state to prevent "input memory"
+                                // If the action caused a lock, stop soft drop so it doesn't carry over
                                 if self.state.line_clear_in_progress
                                     || self.state.piece.is_none()
                                 {
-                                    self.repeat_state = None;
+                                    self.drop_soft_drop_repeat();
                                 }
                             }
                             Screen::QuitMenu => {
@@ -708,31 +2192,102 @@ impl App {
                                     }
                                     Action::HardDrop => match self.quit_selected {
                                         QuitOption::Resume => self.screen = Screen::Playing,
-                                        QuitOption::MainMenu => {
-                                            self.autoplay = false;
-                                            self.auto_restart = false;
-                                            self.screen = Screen::Menu;
-                                        }
+                                        QuitOption::MainMenu => self.open_menu(),
                                         QuitOption::Exit => return Ok(()),
                                     },
                                     Action::Pause | Action::Quit => {
                                         self.screen = Screen::Playing;
                                     }
-                                    Action::None => {
+                                    Action::Reroll | Action::Focus | Action::Zone | Action::Restart
+                                    | Action::Retry | Action::None => {
                                         // If user hits Enter/Space directly via Action::HardDrop it confirm.
                                         // The SoftDrop (Down) and RotateCw (Up) are now mapped to cycling.
                                     }
                                 }
                             }
+                            Screen::Settings => {
+                                // Handling rows, then key profile and music
+                                let keys_row = crate::HandlingConfig::FIELDS.len();
+                                let music_row = keys_row + 1;
+                                let fields = music_row + 1;
+                                match action {
+                                    Action::SoftDrop => {
+                                        self.settings_selected = (self.settings_selected + 1) % fields;
+                                    }
+                                    Action::RotateCw | Action::RotateCcw => {
+                                        self.settings_selected =
+                                            (self.settings_selected + fields - 1) % fields;
+                                    }
+                                    Action::MoveLeft | Action::MoveRight
+                                        if self.settings_selected == keys_row =>
+                                    {
+                                        self.config.keys.profile = cycle(
+                                            self.config.keys.profile,
+                                            action == Action::MoveRight,
+                                        );
+                                    }
+                                    Action::MoveLeft | Action::MoveRight
+                                        if self.settings_selected == music_row =>
+                                    {
+                                        self.config.music =
+                                            cycle(self.config.music, action == Action::MoveRight);
+                                        self.audio.set_track(self.config.music);
+                                    }
+                                    Action::MoveLeft | Action::MoveRight => {
+                                        self.config.handling.adjust(
+                                            self.settings_selected,
+                                            action == Action::MoveRight,
+                                        );
+                                    }
+                                    Action::HardDrop | Action::Quit | Action::Pause => {
+                                        self.screen = Screen::Menu;
+                                    }
+                                    Action::Reroll | Action::Focus | Action::Zone | Action::Restart
+                                    | Action::Retry | Action::None => {}
+                                }
+                            }
                             Screen::GameOver => {
                                 if action == Action::Quit {
                                     return Ok(());
                                 }
-                                if key.code == KeyCode::Char('r') || key.code == KeyCode::Char('R')
+                                let restart = action == Action::Retry
+                                    || matches!(key.code, KeyCode::Char('r' | 'R'));
+                                if restart && self.hot_seat.is_some() {
+                                    self.screen = Screen::Standings;
+                                } else if action == Action::Retry {
+                                    self.restart(true);
+                                } else if matches!(key.code, KeyCode::Char('y' | 'Y')) {
+                                    let seed = format!("{:x}", self.state.seed);
+                                    self.notify(match crate::clipboard::copy(&seed) {
+                                        Ok(()) => format!(" Seed {seed} copied "),
+                                        Err(e) => format!(" Copying the seed failed: {e:#} "),
+                                    });
+                                } else if matches!(key.code, KeyCode::Char('u' | 'U')) {
+                                    self.rewind_game();
+                                } else if key.code == KeyCode::Char('r')
+                                    || key.code == KeyCode::Char('R')
                                 {
                                     self.reset_game(true);
                                 }
                             }
+                            Screen::Standings => {
+                                if action == Action::Quit {
+                                    return Ok(());
+                                }
+                                let next = action == Action::Retry
+                                    || key.code == KeyCode::Enter
+                                    || matches!(key.code, KeyCode::Char('r' | 'R'));
+                                match &self.hot_seat {
+                                    Some(hot_seat) if next && hot_seat.round_start() => {
+                                        self.reset_game(true);
+                                    }
+                                    // Everyone in a round plays the same seed
+                                    Some(_) if next => self.restart(true),
+                                    _ => {}
+                                }
+                            }
+                            // Keys leave the title screen before getting here
+                            Screen::Title | Screen::CustomMode => {}
                         }
                     }
                 }
@@ -740,9 +2295,11 @@ impl App {
             
             // Should we tick game logic?
             // Yes if playing, OR if in Menu and autoplay is enabled (background preview)
-            let should_tick = (self.screen == Screen::Playing && !self.paused) 
+            let should_tick = (self.screen == Screen::Playing && self.paused.is_none()) 
                 || (self.screen == Screen::Menu && self.autoplay);
 
+            self.frame_times
+                .ticking(should_tick && self.screen == Screen::Playing);
             if should_tick {
                 self.tick_game_logic(tick_interval);
             }
@@ -750,6 +2307,8 @@ impl App {
     }
 
     fn tick_game_logic(&mut self, tick_interval: Duration) {
+        self.flush_buffered_input(Instant::now());
+
         // --- AUTOPLAY LOGIC ---
         // Bot actions are throttled to the game's tick rate and must wait
         // for frozen grains to fully settle between placements.
@@ -800,17 +2359,44 @@ impl App {
 
         self.tick_repeat();
         if self.last_tick.elapsed() >= tick_interval {
+            let late = self.last_tick.elapsed().saturating_sub(tick_interval);
             self.last_tick = Instant::now();
+            let tick_start = self.last_tick;
             self.state.tick_gravity(Instant::now());
 
-            let steps = if self.menu_state.ratman_unlocked {
+            let steps: u32 = if self.menu_state.ratman_unlocked {
                 2
             } else {
                 1
             };
+            let steps = if self.state.breather_active() {
+                steps * crate::game::BREATHER_SAND_STEPS
+            } else {
+                steps
+            };
+            // Reduced quality: half the sand steps, a step every other tick when there is one
+            let steps = if self.low_quality {
+                self.sand_step_skipped = !self.sand_step_skipped;
+                if self.sand_step_skipped {
+                    steps / 2
+                } else {
+                    steps.div_ceil(2)
+                }
+            } else {
+                steps
+            };
+            let physics_start = Instant::now();
             for _ in 0..steps {
                 self.state.tick_sand();
             }
+            if let Some(diagnostics) = &mut self.diagnostics {
+                diagnostics.tick(physics_start.elapsed());
+            }
+            crate::logging::tick(late, physics_start.elapsed(), steps);
+            if self.screen == Screen::Playing {
+                self.frame_times
+                    .tick(tick_start.elapsed(), late, tick_interval);
+            }
         }
 
         // Check for locking EVERY frame for maximum "snappiness"
@@ -819,15 +2405,44 @@ impl App {
         // --- DYNAMIC CLEAR CHECK ---
         if self.args.mode == crate::GameMode::Clear
             && self.time_to_40_secs.is_none()
-            && self.state.lines_cleared >= self.args.clear_lines
+            && self
+                .splits
+                .update(self.state.lines_cleared, self.game_start.elapsed())
         {
             self.time_to_40_secs = Some(self.game_start.elapsed().as_secs());
+            if self.autoplay {
+                if self.auto_restart || self.screen == Screen::Menu {
+                    self.reset_game(false);
+                    return;
+                }
+            } else if self.config.custom.is_none()
+                && !self.rewound()
+                && matches!(self.splits.save_if_best(), Ok(true))
+            {
+                self.new_high_score_this_game = true;
+            }
+            if self.config.custom.is_some() {
+                self.update_custom_high_score();
+            } else if self.state.lines_cleared > self.high_score_clear && !self.rewound() {
+                self.high_score_clear = self.state.lines_cleared;
+                self.new_high_score_this_game = true;
+                if !self.autoplay {
+                    let _ = crate::highscores::save_high_scores(
+                        self.high_score_endless,
+                        self.high_score_timed,
+                        self.high_score_clear,
+                    );
+                }
+            }
+            self.game_over_reason = Some(GameOverReason::Cleared);
+            self.screen = Screen::GameOver;
+            return;
         }
         
         // Game Over Logic
         if self.state.game_over {
-            // AUTO RESTART LOGIC
-            if self.autoplay && self.auto_restart {
+            // AUTO RESTART LOGIC (and the menu demo, which never shows game over)
+            if self.autoplay && (self.auto_restart || self.screen == Screen::Menu) {
                 self.reset_game(false);
                 return;
             }
@@ -835,6 +2450,8 @@ impl App {
             self.game_over_reason = Some(GameOverReason::StackOverflow);
 
             match self.args.mode {
+                _ if self.rewound() => {}
+                _ if self.config.custom.is_some() => self.update_custom_high_score(),
                 crate::GameMode::Endless => {
                     if self.state.score > self.high_score_endless {
                         self.high_score_endless = self.state.score;
@@ -886,10 +2503,15 @@ impl App {
                  self.screen = Screen::GameOver;
             }
         } else if self.args.mode == crate::GameMode::Timed
-            && self.game_start.elapsed() >= Duration::from_secs(u64::from(self.args.time_limit))
+            && self.timed_out(Instant::now())
+            && self.screen == Screen::Menu
         {
+            self.reset_game(false);
+        } else if self.args.mode == crate::GameMode::Timed && self.timed_out(Instant::now()) {
             self.game_over_reason = Some(GameOverReason::TimeUp);
-            if self.state.score > self.high_score_timed {
+            if self.config.custom.is_some() {
+                self.update_custom_high_score();
+            } else if self.state.score > self.high_score_timed && !self.rewound() {
                 self.high_score_timed = self.state.score;
                 self.new_high_score_this_game = true;
                 if !self.autoplay {
@@ -900,16 +2522,12 @@ impl App {
                     );
                 }
             }
-            if self.screen == Screen::Menu {
-                 self.reset_game(false);
-            } else {
-                 self.screen = Screen::GameOver;
-            }
+            self.screen = Screen::GameOver;
         }
         
         // Handle clear animation finish
         if self.state.line_clear_in_progress
-             && !self.args.no_animation
+             && !self.no_animation()
              && self.line_clear_effect.as_ref().is_some_and(Effect::done)
         {
              self.state.finish_line_clear();
@@ -920,7 +2538,7 @@ impl App {
         // Handle instant clear (no animation)
         if self.state.line_clear_in_progress
             && !self.state.line_clear_cells.is_empty()
-            && self.args.no_animation
+            && self.no_animation()
         {
             self.state.finish_line_clear();
             self.line_clear_started = None;
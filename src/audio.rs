@@ -0,0 +1,308 @@
+//! Sound (`audio` feature): short synthesized blips for game events and a square-wave
+//! background loop whose tempo follows the level.
+//!
+//! Built without the feature, `Audio` is silent so the rest of the game needs no cfgs.
+//! `BellCues` (terminal bell accessibility cues) is always available.
+
+use crate::MusicTrack;
+use crate::game::GameEvent;
+use std::collections::VecDeque;
+use std::io::Write;
+use std::time::{Duration, Instant};
+
+/// Gap between the beeps of one bell cue pattern.
+const BELL_GAP_MS: u64 = 180;
+
+/// What the music should do this frame (see `Audio::update_music`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MusicCue {
+    /// In play: tempo from the level.
+    Play { level: u32 },
+    /// Game over screen: keep going, quieter.
+    Duck,
+    Pause,
+    /// Menus: stop and start the loop over next time.
+    Stop,
+}
+
+/// Output sample rate of the generated music.
+#[cfg(feature = "audio")]
+const SAMPLE_RATE: u32 = 44_100;
+/// Music loudness relative to the effects.
+#[cfg(feature = "audio")]
+const MUSIC_GAIN: f32 = 0.25;
+/// Extra music attenuation on the game over screen.
+#[cfg(feature = "audio")]
+const DUCK_GAIN: f32 = 0.35;
+/// Notes kept queued ahead in the sink (tempo changes apply after these).
+#[cfg(feature = "audio")]
+const MUSIC_LOOKAHEAD: usize = 3;
+
+/// Sound output. Silent when the volume is 0, no audio device is available, or the
+/// crate is built without `audio`.
+pub struct Audio {
+    /// Keeps the device open; dropping the stream stops all sound.
+    #[cfg(feature = "audio")]
+    output: Option<(rodio::OutputStream, rodio::OutputStreamHandle)>,
+    /// 0.0..=1.0
+    #[cfg(feature = "audio")]
+    volume: f32,
+    /// Music queue; None while stopped.
+    #[cfg(feature = "audio")]
+    music: Option<rodio::Sink>,
+    #[cfg(feature = "audio")]
+    track: MusicTrack,
+    /// Next note of the track.
+    #[cfg(feature = "audio")]
+    step: usize,
+}
+
+impl Audio {
+    /// `volume` in percent (0 = muted, no device is opened).
+    pub fn new(volume: u8, track: MusicTrack) -> Self {
+        #[cfg(feature = "audio")]
+        {
+            let volume = f32::from(volume.min(100)) / 100.0;
+            let output = if volume > 0.0 {
+                rodio::OutputStream::try_default().ok()
+            } else {
+                None
+            };
+            Self {
+                output,
+                volume,
+                music: None,
+                track,
+                step: 0,
+            }
+        }
+        #[cfg(not(feature = "audio"))]
+        {
+            let _ = (volume, track);
+            Self {}
+        }
+    }
+
+    /// Switch the background track (restarts the loop; `Off` silences it).
+    #[cfg_attr(not(feature = "audio"), allow(clippy::unused_self))]
+    pub fn set_track(&mut self, track: MusicTrack) {
+        #[cfg(feature = "audio")]
+        {
+            self.track = track;
+            self.music = None;
+            self.step = 0;
+        }
+        #[cfg(not(feature = "audio"))]
+        let _ = track;
+    }
+
+    /// Keep the music going: called every frame with what the current screen wants.
+    #[cfg_attr(not(feature = "audio"), allow(clippy::unused_self))]
+    pub fn update_music(&mut self, cue: MusicCue) {
+        #[cfg(feature = "audio")]
+        {
+            let Some((_, handle)) = &self.output else {
+                return;
+            };
+            let level = match cue {
+                MusicCue::Stop => {
+                    // Dropping the sink stops it
+                    self.music = None;
+                    self.step = 0;
+                    return;
+                }
+                MusicCue::Pause => {
+                    if let Some(sink) = &self.music {
+                        sink.pause();
+                    }
+                    return;
+                }
+                MusicCue::Duck => None,
+                MusicCue::Play { level } => Some(level),
+            };
+            let notes = track_notes(self.track);
+            if notes.is_empty() {
+                return;
+            }
+            if self.music.is_none() {
+                self.music = rodio::Sink::try_new(handle).ok();
+            }
+            let Some(sink) = &self.music else {
+                return;
+            };
+            let duck = if level.is_some() { 1.0 } else { DUCK_GAIN };
+            sink.set_volume(self.volume * MUSIC_GAIN * duck);
+            sink.play();
+            // Tempo rises 6 BPM per level from 120, capped at 216
+            let bpm = 120 + 6 * level.unwrap_or(1).saturating_sub(1).min(16);
+            let eighth_secs = 30.0 / bpm as f32;
+            while sink.len() < MUSIC_LOOKAHEAD {
+                let (note, eighths) = notes[self.step % notes.len()];
+                self.step = (self.step + 1) % notes.len();
+                let samples = square_note(note, eighth_secs * f32::from(eighths));
+                sink.append(rodio::buffer::SamplesBuffer::new(1, SAMPLE_RATE, samples));
+            }
+        }
+        #[cfg(not(feature = "audio"))]
+        let _ = cue;
+    }
+
+    /// Play the sound for `event` (returns immediately; notes are mixed in the background).
+    #[cfg_attr(not(feature = "audio"), allow(clippy::unused_self))]
+    pub fn play(&self, event: GameEvent) {
+        #[cfg(feature = "audio")]
+        {
+            use rodio::Source;
+            use std::time::Duration;
+
+            let Some((_, handle)) = &self.output else {
+                return;
+            };
+            let (notes, gain) = notes(event);
+            let mut offset_ms = 0;
+            for (freq, ms) in notes {
+                let note = rodio::source::SineWave::new(freq)
+                    .take_duration(Duration::from_millis(ms))
+                    .amplify(self.volume * gain)
+                    .delay(Duration::from_millis(offset_ms));
+                let _ = handle.play_raw(note);
+                offset_ms += ms;
+            }
+        }
+        #[cfg(not(feature = "audio"))]
+        let _ = event;
+    }
+}
+
+/// Accessibility cues on the terminal bell (`--bell-cues`, works without the `audio` feature):
+/// one beep when a piece lands, two for a clear, three when the stack enters the danger zone.
+#[derive(Debug, Default)]
+pub struct BellCues {
+    /// When each pending beep is due.
+    due: VecDeque<Instant>,
+    in_danger: bool,
+}
+
+impl BellCues {
+    /// Queue the pattern for `event` (after any pattern still sounding).
+    pub fn on_event(&mut self, event: GameEvent, now: Instant) {
+        match event {
+            GameEvent::Lock => self.queue(1, now),
+            GameEvent::Clear => self.queue(2, now),
+            _ => {}
+        }
+    }
+
+    /// Update the danger state; entering the danger zone beeps three times.
+    pub fn set_danger(&mut self, danger: bool, now: Instant) {
+        if danger && !self.in_danger {
+            self.queue(3, now);
+        }
+        self.in_danger = danger;
+    }
+
+    fn queue(&mut self, beeps: usize, now: Instant) {
+        let gap = Duration::from_millis(BELL_GAP_MS);
+        // A new pattern starts a double gap after the last one so patterns stay countable
+        let mut at = self.due.back().map_or(now, |&last| (last + 2 * gap).max(now));
+        for _ in 0..beeps {
+            self.due.push_back(at);
+            at += gap;
+        }
+    }
+
+    /// Ring the bell for every beep that is due.
+    pub fn tick(&mut self, now: Instant) -> std::io::Result<()> {
+        let mut rang = false;
+        while self.due.front().is_some_and(|&at| at <= now) {
+            self.due.pop_front();
+            std::io::stdout().write_all(b"\x07")?;
+            rang = true;
+        }
+        if rang {
+            std::io::stdout().flush()?;
+        }
+        Ok(())
+    }
+}
+
+/// Track as (MIDI note, length in eighths); note 0 is a rest.
+#[cfg(feature = "audio")]
+const fn track_notes(track: MusicTrack) -> &'static [(u8, u8)] {
+    match track {
+        MusicTrack::Off => &[],
+        // A minor arpeggios
+        MusicTrack::Pulse => &[
+            (57, 1), (60, 1), (64, 1), (69, 1), (64, 1), (60, 1), (57, 1), (64, 1),
+            (53, 1), (57, 1), (60, 1), (65, 1), (60, 1), (57, 1), (53, 1), (60, 1),
+            (55, 1), (59, 1), (62, 1), (67, 1), (62, 1), (59, 1), (55, 1), (62, 1),
+            (52, 1), (56, 1), (59, 1), (64, 1), (68, 1), (64, 1), (59, 1), (56, 1),
+        ],
+        // Dotted rhythm in D minor
+        MusicTrack::March => &[
+            (62, 3), (62, 1), (65, 2), (69, 2), (67, 3), (65, 1), (64, 4),
+            (60, 3), (60, 1), (64, 2), (67, 2), (65, 3), (64, 1), (62, 4),
+            (69, 2), (70, 2), (72, 2), (70, 2), (69, 3), (67, 1), (65, 4),
+            (64, 2), (65, 2), (67, 2), (64, 2), (62, 6), (0, 2),
+        ],
+        // Slow, sparse pentatonic line
+        MusicTrack::Drift => &[
+            (64, 2), (67, 2), (69, 4), (0, 2), (72, 2), (69, 4),
+            (67, 2), (64, 2), (62, 4), (0, 4),
+            (60, 2), (62, 2), (64, 4), (0, 2), (67, 2), (64, 4),
+            (62, 2), (60, 2), (57, 6), (0, 2),
+        ],
+    }
+}
+
+/// One square-wave note (`midi` 0 = silence) with a short release so notes don't click.
+#[cfg(feature = "audio")]
+fn square_note(midi: u8, secs: f32) -> Vec<f32> {
+    let rate = SAMPLE_RATE as f32;
+    let len = (rate * secs) as usize;
+    if midi == 0 {
+        return vec![0.0; len];
+    }
+    let freq = 440.0 * ((f32::from(midi) - 69.0) / 12.0).exp2();
+    let period = rate / freq;
+    let release = rate * 0.02;
+    (0..len)
+        .map(|i| {
+            let env = ((len - i) as f32 / release).min(1.0);
+            let high = (i as f32 % period) < period / 2.0;
+            if high { 0.5 * env } else { -0.5 * env }
+        })
+        .collect()
+}
+
+/// Notes for an event as (frequency Hz, duration ms), played back to back, and their loudness.
+#[cfg(feature = "audio")]
+fn notes(event: GameEvent) -> (Vec<(f32, u64)>, f32) {
+    match event {
+        GameEvent::Spawn(_) => (Vec::new(), 0.0),
+        GameEvent::Move => (vec![(440.0, 12)], 0.15),
+        GameEvent::Rotate => (vec![(660.0, 18)], 0.2),
+        GameEvent::Lock => (vec![(196.0, 40)], 0.35),
+        GameEvent::Clear => (vec![(523.0, 50), (659.0, 50), (784.0, 70)], 0.4),
+        // Higher pitch the longer the chain
+        GameEvent::Combo(n) => {
+            let base = 523.0 * (n.min(12) as f32 / 12.0).exp2();
+            (vec![(base, 60), (base * 1.5, 90)], 0.4)
+        }
+        // Falling back down from the chain's pitch
+        GameEvent::ComboEnd { peak, .. } => {
+            let base = 523.0 * (peak.min(12) as f32 / 12.0).exp2();
+            (vec![(base * 1.5, 60), (base, 60), (base / 1.5, 90)], 0.35)
+        }
+        GameEvent::LevelUp(_) => (
+            vec![(523.0, 70), (659.0, 70), (784.0, 70), (1047.0, 140)],
+            0.45,
+        ),
+        GameEvent::Breather => (
+            vec![(784.0, 50), (1047.0, 50), (784.0, 50), (1047.0, 90)],
+            0.4,
+        ),
+        GameEvent::Mission { .. } => (vec![(659.0, 60), (880.0, 60), (1319.0, 120)], 0.4),
+        GameEvent::GameOver => (vec![(392.0, 160), (330.0, 160), (262.0, 320)], 0.5),
+    }
+}
@@ -3,14 +3,21 @@
 //! Strategy: place same-color pieces near existing same-color sand to build
 //! horizontal color bands that can span left-to-right for clears.
 //! Uses a lightweight grid snapshot — never clones GameState.
+//! Grid values: 0 = empty, color+1 = sand, `RAINBOW_VAL` = rainbow (matches any colour), `STONE_VAL` = stone.
 
-use crate::game::{Cell, GameState, GRAIN_SCALE};
+use crate::game::{Cell, GameState, GRAIN_SCALE, PieceKind, in_blast};
 use crate::input::Action;
 use std::collections::{HashSet, VecDeque};
 
 
 pub struct Bot;
 
+/// Grid value for rainbow grains (one past the last colour).
+const RAINBOW_VAL: u8 = 7;
+
+/// Grid value for stone grains (never matches a colour, falls straight down only).
+const STONE_VAL: u8 = 8;
+
 #[derive(Debug, Clone)]
 struct MoveCandidate {
     score: f32,
@@ -32,7 +39,7 @@ impl Bot {
 
         let (gw, gh) = state.playfield.grain_dims();
         let initial_gx = piece.gx;
-        let piece_color = piece.kind.color_index(state.high_color);
+        let piece_colors = distinct_colors(&piece.cell_colors);
 
         // Snapshot the grid: 0=empty, color+1=occupied.
         let base_grid = snapshot_grid(&state.playfield, &state.frozen_grains, gw, gh);
@@ -74,8 +81,8 @@ impl Bot {
                     continue;
                 }
 
-                // Hard-drop: find landing Y via grid collision.
-                let mut land_y = test_piece.gy;
+                // Hard-drop: straight to the top of the stack, then find landing Y via grid collision.
+                let mut land_y = test_piece.gy + state.fall_distance(&test_piece);
                 loop {
                     test_piece.gy = land_y + 1;
                     if !can_place_on_grid(&base_grid, gw, gh, &test_piece) {
@@ -87,12 +94,15 @@ impl Bot {
 
                 // Clone grid, stamp piece, run simplified settle.
                 let mut grid = base_grid.clone();
-                let stamp_val = piece_color + 1; // grid uses color+1
-                stamp_piece(&mut grid, gw, gh, &test_piece, stamp_val);
+                if test_piece.kind == PieceKind::Bomb {
+                    blast_grid(&mut grid, gw, gh, test_piece.blast_center());
+                } else {
+                    stamp_piece(&mut grid, gw, gh, &test_piece);
+                }
                 settle_sand(&mut grid, gw, gh);
 
                 // Evaluate the resulting board.
-                let score = evaluate(&grid, gw, gh, piece_color);
+                let score = evaluate(&grid, gw, gh, &piece_colors);
 
                 if score > best.score {
                     best = MoveCandidate {
@@ -152,19 +162,29 @@ fn snapshot_grid(
     let mut grid = vec![0u8; gw * gh];
     for y in 0..gh {
         for x in 0..gw {
-            if let Some(Cell::Sand(c, _)) = pf.get(x, y) {
-                grid[y * gw + x] = c + 1;
+            if let Some(cell) = pf.get(x, y) {
+                grid[y * gw + x] = grid_value(cell);
             }
         }
     }
     for fg in frozen {
         if fg.x < gw && fg.y < gh {
-            grid[fg.y * gw + fg.x] = fg.color_index + 1;
+            grid[fg.y * gw + fg.x] = grid_value(fg.cell);
         }
     }
     grid
 }
 
+/// Grid value for a playfield cell.
+const fn grid_value(cell: Cell) -> u8 {
+    match cell {
+        Cell::Empty => 0,
+        Cell::Sand(c, _) | Cell::Converter(c, _) => c + 1,
+        Cell::Rainbow(_) => RAINBOW_VAL,
+        Cell::Stone(_) => STONE_VAL,
+    }
+}
+
 /// Check if a piece fits on the flat grid.
 fn can_place_on_grid(grid: &[u8], gw: usize, gh: usize, piece: &crate::game::Piece) -> bool {
     for (gx_o, gy_o) in piece.cell_grain_origins() {
@@ -187,9 +207,22 @@ fn can_place_on_grid(grid: &[u8], gw: usize, gh: usize, piece: &crate::game::Pie
     true
 }
 
-/// Stamp piece grains onto the grid.
-fn stamp_piece(grid: &mut [u8], gw: usize, gh: usize, piece: &crate::game::Piece, color: u8) {
-    for (gx_o, gy_o) in piece.cell_grain_origins() {
+/// Distinct colours of a piece's cells (one entry unless two-tone).
+fn distinct_colors(cell_colors: &[u8]) -> Vec<u8> {
+    let mut colors = cell_colors.to_vec();
+    colors.sort_unstable();
+    colors.dedup();
+    colors
+}
+
+/// Stamp piece grains onto the grid (each cell in its own colour, grid uses color+1).
+fn stamp_piece(grid: &mut [u8], gw: usize, gh: usize, piece: &crate::game::Piece) {
+    for (i, (gx_o, gy_o)) in piece.cell_grain_origins().into_iter().enumerate() {
+        let color = if piece.rainbow_cell == Some(i) {
+            RAINBOW_VAL
+        } else {
+            piece.cell_colors[i] + 1
+        };
         for dy in 0..GRAIN_SCALE as i32 {
             for dx in 0..GRAIN_SCALE as i32 {
                 let gx = gx_o + dx;
@@ -202,6 +235,17 @@ fn stamp_piece(grid: &mut [u8], gw: usize, gh: usize, piece: &crate::game::Piece
     }
 }
 
+/// Remove every grain inside the bomb's blast circle.
+fn blast_grid(grid: &mut [u8], gw: usize, gh: usize, center: (i32, i32)) {
+    for y in 0..gh {
+        for x in 0..gw {
+            if in_blast(center, x as i32, y as i32) {
+                grid[y * gw + x] = 0;
+            }
+        }
+    }
+}
+
 /// Simplified sand settling: grains fall straight down or cascade diagonally.
 fn settle_sand(grid: &mut [u8], gw: usize, gh: usize) {
     for pass in 0..80 {
@@ -220,7 +264,7 @@ fn settle_sand(grid: &mut [u8], gw: usize, gh: usize) {
                     grid[below] = c;
                     grid[idx] = 0;
                     moved = true;
-                } else {
+                } else if c != STONE_VAL {
                     let can_left = x > 0 && grid[(y + 1) * gw + x - 1] == 0;
                     let can_right = x + 1 < gw && grid[(y + 1) * gw + x + 1] == 0;
                     let go_left = if can_left && can_right { left_first } else { can_left };
@@ -251,10 +295,9 @@ fn evaluate(
     grid: &[u8],
     gw: usize,
     gh: usize,
-    placed_color: u8,
+    placed_colors: &[u8],
 ) -> f32 {
     let mut score: f32 = 0.0;
-    let placed_val = placed_color + 1;
 
     // --- Scoring Constants ---
     const W_SPAN_CLEAR: f32 = 50.0;
@@ -273,22 +316,10 @@ fn evaluate(
     score += clears as f32 * W_SPAN_CLEAR;
 
     // --- 2. Column heights, holes, bumpiness ---
-    let mut col_heights = vec![0usize; gw];
-    let mut holes: u32 = 0;
-
-    for x in 0..gw {
-        let mut found_top = false;
-        for y in 0..gh {
-            if grid[y * gw + x] != 0 {
-                if !found_top {
-                    col_heights[x] = gh - y;
-                    found_top = true;
-                }
-            } else if found_top {
-                holes += 1;
-            }
-        }
-    }
+    let (col_heights, col_holes): (Vec<usize>, Vec<usize>) = (0..gw)
+        .map(|x| crate::view::column_profile(gh, |y| grid[y * gw + x] != 0))
+        .unzip();
+    let holes: usize = col_holes.iter().sum();
 
     let max_height = *col_heights.iter().max().unwrap_or(&0);
     let agg_height: usize = col_heights.iter().sum();
@@ -309,7 +340,7 @@ fn evaluate(
     for y in 0..gh {
         for x in 0..gw {
             let c = grid[y * gw + x];
-            if c != 0 {
+            if c != 0 && c != STONE_VAL {
                 if x + 1 < gw && grid[y * gw + x + 1] == c {
                     h_adj += 1;
                 }
@@ -326,8 +357,11 @@ fn evaluate(
     // --- 4. Placed piece proximity to same-color sand ---
     // Reward placing near existing sand of the same color.
     // This encourages color clustering which leads to spans.
-    let proximity_bonus = same_color_proximity(grid, gw, gh, placed_val);
-    score += proximity_bonus * W_PROXIMITY;
+    // Two-tone pieces count every colour they carry.
+    for &placed_color in placed_colors {
+        let proximity_bonus = same_color_proximity(grid, gw, gh, placed_color + 1);
+        score += proximity_bonus * W_PROXIMITY;
+    }
 
     // --- 5. Horizontal reach per color (how close each color is to spanning) ---
     let reach_bonus = color_reach_bonus(grid, gw, gh, W_REACH);
@@ -341,7 +375,7 @@ fn evaluate(
     score
 }
 
-/// Count same-color 8-connected components that span left-to-right.
+/// Count same-color 8-connected components that span left-to-right (rainbow grains join any colour).
 fn count_spanning_clears(grid: &[u8], gw: usize, gh: usize) -> u32 {
     let mut clears = 0u32;
     let mut visited = HashSet::new();
@@ -349,30 +383,34 @@ fn count_spanning_clears(grid: &[u8], gw: usize, gh: usize) -> u32 {
     for color in 1..=6u8 {
         for start_y in 0..gh {
             let pos = (0usize, start_y);
-            if grid[start_y * gw] != color || visited.contains(&pos) {
+            let matches = |v: u8| v == color || v == RAINBOW_VAL;
+            if !matches(grid[start_y * gw]) || visited.contains(&(pos, color)) {
                 continue;
             }
             let mut stack = vec![pos];
-            visited.insert(pos);
+            visited.insert((pos, color));
             let mut touches_right = false;
+            let mut has_color = false;
 
             while let Some((x, y)) = stack.pop() {
                 if x == gw - 1 {
                     touches_right = true;
                 }
+                if grid[y * gw + x] == color {
+                    has_color = true;
+                }
                 for &(dx, dy) in &NEIGHBOURS_8 {
                     let nx = x as i32 + dx;
                     let ny = y as i32 + dy;
                     if nx >= 0 && nx < gw as i32 && ny >= 0 && ny < gh as i32 {
                         let npos = (nx as usize, ny as usize);
-                        if grid[npos.1 * gw + npos.0] == color && !visited.contains(&npos) {
-                            visited.insert(npos);
+                        if matches(grid[npos.1 * gw + npos.0]) && visited.insert((npos, color)) {
                             stack.push(npos);
                         }
                     }
                 }
             }
-            if touches_right {
+            if touches_right && has_color {
                 clears += 1;
             }
         }
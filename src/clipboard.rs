@@ -0,0 +1,58 @@
+//! System clipboard for sharing seeds.
+//!
+//! Copying uses the OSC 52 escape, which the terminal handles (also over SSH); terminals rarely
+//! allow reading it back, so pasting runs the platform's clipboard tool instead.
+
+use anyhow::{Context, Result, bail};
+use std::io::Write;
+use std::process::Command;
+
+/// Tools tried in order to read the clipboard: program and arguments.
+const PASTE_COMMANDS: &[(&str, &[&str])] = &[
+    ("pbpaste", &[]),
+    ("wl-paste", &["--no-newline"]),
+    ("xclip", &["-selection", "clipboard", "-o"]),
+    ("xsel", &["--clipboard", "--output"]),
+    ("powershell", &["-NoProfile", "-Command", "Get-Clipboard"]),
+];
+
+/// Ask the terminal to put `text` on the clipboard.
+pub fn copy(text: &str) -> Result<()> {
+    let mut stdout = std::io::stdout();
+    write!(stdout, "\x1b]52;c;{}\x07", base64(text.as_bytes()))?;
+    stdout.flush()?;
+    Ok(())
+}
+
+/// The clipboard's text, from the first clipboard tool that works.
+pub fn paste() -> Result<String> {
+    for (program, args) in PASTE_COMMANDS {
+        let Ok(output) = Command::new(program).args(*args).output() else {
+            continue;
+        };
+        if output.status.success() {
+            return String::from_utf8(output.stdout).context("clipboard is not text");
+        }
+    }
+    bail!("no clipboard tool found (tried pbpaste, wl-paste, xclip, xsel and powershell)")
+}
+
+/// Standard base64 with padding.
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, &b)| n | (u32::from(b) << (16 - 8 * i)));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[((n >> (18 - 6 * i)) & 63) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
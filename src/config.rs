@@ -0,0 +1,196 @@
+//! Optional user config file (XDG config or ~/.config/setrixtui/config.json; %APPDATA% on Windows).
+//!
+//! Every field is optional; CLI flags take precedence over values from the file.
+
+use crate::custom::CustomMode;
+use crate::{ComboDecay, KeyProfile, MusicTrack, PieceSet, SandType};
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+const FILENAME: &str = "config.json";
+
+/// Returns the setrixtui config directory (XDG_CONFIG_HOME or ~/.config, plus `setrixtui`).
+/// On Windows it is `%APPDATA%\setrixtui`.
+pub fn config_dir() -> PathBuf {
+    #[cfg(windows)]
+    {
+        if let Some(appdata) = dirs::config_dir() {
+            return appdata.join("setrixtui");
+        }
+    }
+    let base = if let Ok(xdg) = std::env::var("XDG_CONFIG_HOME") {
+        if xdg.is_empty() {
+            std::env::var("HOME")
+                .map(PathBuf::from)
+                .unwrap_or_else(|_| PathBuf::from("."))
+                .join(".config")
+        } else {
+            PathBuf::from(xdg)
+        }
+    } else {
+        std::env::var("HOME")
+            .map(|h| PathBuf::from(h).join(".config"))
+            .unwrap_or_else(|_| PathBuf::from("."))
+    };
+    base.join("setrixtui")
+}
+
+/// Contents of `config.json`. Unknown keys are ignored.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct FileConfig {
+    pub combo: ComboFileConfig,
+    pub scoring: ScoringFileConfig,
+    pub handling: HandlingFileConfig,
+    pub keys: KeysFileConfig,
+    pub audio: AudioFileConfig,
+    pub difficulty: DifficultyFileConfig,
+    /// Named custom modes, saved from the menu's custom mode form (see `crate::custom`).
+    pub custom_modes: Vec<CustomMode>,
+}
+
+/// `"difficulty": { "easy": { "preview": 2 }, "hard": { "tick_rate": 70, "colors": 5 } }`
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct DifficultyFileConfig {
+    pub easy: ProfileFileConfig,
+    pub medium: ProfileFileConfig,
+    pub hard: ProfileFileConfig,
+}
+
+/// Overrides for one difficulty preset (see `crate::difficulty::DifficultyProfile`).
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct ProfileFileConfig {
+    pub tick_rate: Option<f64>,
+    pub level_speedup: Option<f64>,
+    pub preview: Option<usize>,
+    pub colors: Option<u8>,
+    pub repeat_color_chance: Option<f32>,
+    pub narrower: Option<u16>,
+    pub shorter: Option<u16>,
+    pub pieces: Option<PieceSet>,
+    pub sand: Option<SandType>,
+    pub obstacle_levels: Option<u32>,
+}
+
+/// `"audio": { "volume": 70, "music": "march" }` (only used with the `audio` feature)
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct AudioFileConfig {
+    /// Percent.
+    pub volume: Option<u8>,
+    pub music: Option<MusicTrack>,
+}
+
+/// `"keys": { "profile": "guideline", "bind": { "c": "rotate-ccw", "space": "none" } }`
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct KeysFileConfig {
+    pub profile: Option<KeyProfile>,
+    /// Per-key overrides applied on top of the profile (key name → action name).
+    pub bind: HashMap<String, String>,
+}
+
+/// `"combo": { "timer_ticks": 90, "max_multiplier": 10, "decay": "reset" }`
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct ComboFileConfig {
+    pub timer_ticks: Option<u32>,
+    pub max_multiplier: Option<u32>,
+    pub decay: Option<ComboDecay>,
+}
+
+/// `"scoring": { "grain_points": 1, "soft_drop_points": 1, "hard_drop_points": 2,
+/// "combo_step": 1, "lines_per_level": 10 }`
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct ScoringFileConfig {
+    pub grain_points: Option<u32>,
+    pub soft_drop_points: Option<u32>,
+    pub hard_drop_points: Option<u32>,
+    pub combo_step: Option<u32>,
+    pub lines_per_level: Option<u32>,
+}
+
+/// `"handling": { "das_ms": 80, "arr_ms": 38, "soft_drop_ms": 38, "repeat": { ... } }`
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct HandlingFileConfig {
+    pub das_ms: Option<u64>,
+    pub arr_ms: Option<u64>,
+    pub soft_drop_ms: Option<u64>,
+    pub repeat: RepeatFileConfig,
+}
+
+/// `"repeat": { "move": 38, "soft_drop": 20, "rotate": false }`
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct RepeatFileConfig {
+    #[serde(rename = "move")]
+    pub movement: Option<RepeatSetting>,
+    pub soft_drop: Option<RepeatSetting>,
+    #[serde(rename = "rotate")]
+    pub rotation: Option<RepeatSetting>,
+}
+
+/// Repeat entry: `false` (off), `true` (on at the usual rate) or a number of ms between repeats.
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(untagged)]
+pub enum RepeatSetting {
+    Enabled(bool),
+    IntervalMs(u64),
+}
+
+impl RepeatSetting {
+    /// Interval for one action: the repeat entry if present, else `rate_ms`, else the default.
+    /// `true` uses `rate_ms` or the default rate (ARR for actions that don't repeat by default).
+    pub fn resolve(
+        setting: Option<Self>,
+        rate_ms: Option<u64>,
+        default: Option<u64>,
+    ) -> Option<u64> {
+        let rate = rate_ms.or(default);
+        match setting {
+            Some(Self::IntervalMs(ms)) => Some(ms),
+            Some(Self::Enabled(false)) => None,
+            Some(Self::Enabled(true)) => {
+                rate.or_else(|| crate::input::RepeatPolicy::default().movement)
+            }
+            None => rate,
+        }
+    }
+}
+
+/// Load the config file. A missing file is not an error (returns defaults).
+pub fn load() -> Result<FileConfig> {
+    let path = config_dir().join(FILENAME);
+    let content = match fs::read_to_string(&path) {
+        Ok(c) => c,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(FileConfig::default()),
+        Err(e) => return Err(e).with_context(|| format!("reading {}", path.display())),
+    };
+    serde_json::from_str(&content).with_context(|| format!("parsing {}", path.display()))
+}
+
+/// Write `modes` as the config file's `custom_modes`, keeping everything else in the file.
+pub fn save_custom_modes(modes: &[CustomMode]) -> Result<()> {
+    let path = config_dir().join(FILENAME);
+    let mut file: serde_json::Value = match fs::read_to_string(&path) {
+        Ok(c) => serde_json::from_str(&c).with_context(|| format!("parsing {}", path.display()))?,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => serde_json::json!({}),
+        Err(e) => return Err(e).with_context(|| format!("reading {}", path.display())),
+    };
+    let Some(object) = file.as_object_mut() else {
+        anyhow::bail!("{} is not a JSON object", path.display());
+    };
+    object.insert("custom_modes".to_string(), serde_json::to_value(modes)?);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&path, serde_json::to_string_pretty(&file)?)
+        .with_context(|| format!("writing {}", path.display()))
+}
@@ -0,0 +1,212 @@
+//! Custom modes: a goal (endless, time limit or target lines) with garbage rising from the
+//! bottom, a colour count, a sand type and a starting board, built on the menu's form and kept
+//! by name in the config file. Each has its own high score (see `crate::highscores`).
+
+use crate::app::{MENU_LINES_RANGE, MENU_LINES_STEP, MENU_TIME_RANGE, MENU_TIME_STEP};
+use crate::{GameMode, SandType};
+use serde::{Deserialize, Serialize};
+
+/// Longest custom mode name.
+const NAME_LEN: usize = 16;
+
+/// Garbage interval steps and range on the form, in seconds (0 = off sits below the range).
+const GARBAGE_STEP: u32 = 5;
+const GARBAGE_RANGE: (u32, u32) = (5, 60);
+
+/// Most block rows of sand a custom mode can start with.
+const START_GARBAGE_MAX: u16 = 8;
+
+/// How a custom game ends.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Goal {
+    Endless,
+    /// Time limit in seconds.
+    Timed(u32),
+    /// Lines to clear.
+    Lines(u32),
+}
+
+/// One saved custom mode: `{ "name": "rush", "goal": { "timed": 120 }, "garbage_secs": 10,
+/// "colors": 5, "sand": "wet", "start_garbage": 3, "start_clean_streak": true }`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CustomMode {
+    pub name: String,
+    pub goal: Goal,
+    /// A row of garbage rises from the bottom every this many seconds; 0 = never.
+    pub garbage_secs: u32,
+    /// Sand colours in play (2–6).
+    pub colors: u8,
+    pub sand: SandType,
+    /// Block rows of random sand on the board at the start.
+    pub start_garbage: u16,
+    /// The first bag deals no S or Z.
+    pub start_clean_streak: bool,
+}
+
+impl Default for CustomMode {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            goal: Goal::Endless,
+            garbage_secs: 0,
+            colors: 4,
+            sand: SandType::Dry,
+            start_garbage: 0,
+            start_clean_streak: false,
+        }
+    }
+}
+
+impl CustomMode {
+    /// The built-in mode that runs the goal.
+    pub const fn mode(&self) -> GameMode {
+        match self.goal {
+            Goal::Endless => GameMode::Endless,
+            Goal::Timed(_) => GameMode::Timed,
+            Goal::Lines(_) => GameMode::Clear,
+        }
+    }
+}
+
+/// The custom mode form: a draft and its selected row (see `CustomForm::ROWS`).
+#[derive(Debug, Clone, Default)]
+pub struct CustomForm {
+    pub draft: CustomMode,
+    pub selected: usize,
+}
+
+impl CustomForm {
+    /// Row labels, top to bottom; the last row saves.
+    pub const ROWS: [&'static str; 9] = [
+        "Name", "Goal", "Target", "Garbage", "Colours", "Sand", "Pre-fill", "Opening", "Save",
+    ];
+    const NAME_ROW: usize = 0;
+    pub const SAVE_ROW: usize = Self::ROWS.len() - 1;
+
+    pub fn new(draft: CustomMode) -> Self {
+        Self { draft, selected: 0 }
+    }
+
+    pub const fn on_name(&self) -> bool {
+        self.selected == Self::NAME_ROW
+    }
+
+    /// Back to the name row (saving without a name).
+    pub const fn select_name(&mut self) {
+        self.selected = Self::NAME_ROW;
+    }
+
+    /// Move the selection down (or up), wrapping.
+    pub const fn select(&mut self, down: bool) {
+        let n = Self::ROWS.len();
+        self.selected = (self.selected + if down { 1 } else { n - 1 }) % n;
+    }
+
+    /// Type into the name.
+    pub fn type_char(&mut self, c: char) {
+        if !c.is_control() && self.draft.name.chars().count() < NAME_LEN {
+            self.draft.name.push(c);
+        }
+    }
+
+    pub fn backspace(&mut self) {
+        self.draft.name.pop();
+    }
+
+    /// Left / Right on the selected row. The goal cycles endless, timed and lines (at the
+    /// menu's default limit and target); the target row steps its time or lines.
+    pub fn adjust(&mut self, forward: bool) {
+        let step = |value: u32, by: u32, (min, max): (u32, u32)| {
+            let value = if forward {
+                value + by
+            } else {
+                value.saturating_sub(by)
+            };
+            value.clamp(min, max)
+        };
+        let draft = &mut self.draft;
+        match (Self::ROWS[self.selected], draft.goal) {
+            ("Goal", goal) => {
+                let goals = [Goal::Endless, Goal::Timed(180), Goal::Lines(40)];
+                let i = goals
+                    .iter()
+                    .position(|g| std::mem::discriminant(g) == std::mem::discriminant(&goal))
+                    .unwrap_or(0);
+                draft.goal = goals[(i + if forward { 1 } else { goals.len() - 1 }) % goals.len()];
+            }
+            ("Target", Goal::Timed(secs)) => {
+                draft.goal = Goal::Timed(step(secs, MENU_TIME_STEP, MENU_TIME_RANGE));
+            }
+            ("Target", Goal::Lines(lines)) => {
+                draft.goal = Goal::Lines(step(lines, MENU_LINES_STEP, MENU_LINES_RANGE));
+            }
+            ("Garbage", _) => {
+                draft.garbage_secs = match (draft.garbage_secs, forward) {
+                    (0, true) => GARBAGE_RANGE.0,
+                    (secs, false) if secs <= GARBAGE_RANGE.0 => 0,
+                    (secs, _) => step(secs, GARBAGE_STEP, GARBAGE_RANGE),
+                };
+            }
+            ("Colours", _) => {
+                draft.colors = match (draft.colors, forward) {
+                    (n, true) => (n + 1).min(6),
+                    (n, false) => n.saturating_sub(1).max(2),
+                };
+            }
+            ("Sand", _) => draft.sand = crate::app::cycle(draft.sand, forward),
+            ("Pre-fill", _) => {
+                draft.start_garbage = match (draft.start_garbage, forward) {
+                    (n, true) => (n + 1).min(START_GARBAGE_MAX),
+                    (n, false) => n.saturating_sub(1),
+                };
+            }
+            ("Opening", _) => draft.start_clean_streak = !draft.start_clean_streak,
+            _ => {}
+        }
+    }
+
+    /// Value shown on each row, in `ROWS` order.
+    pub fn values(&self) -> [String; 9] {
+        let draft = &self.draft;
+        let (goal, target) = match draft.goal {
+            Goal::Endless => ("endless", "—".to_string()),
+            Goal::Timed(secs) => ("timed", format!("{}:{:02}", secs / 60, secs % 60)),
+            Goal::Lines(n) => ("lines", format!("{n} lines")),
+        };
+        let garbage = match draft.garbage_secs {
+            0 => "off".to_string(),
+            secs => format!("every {secs} s"),
+        };
+        let sand = clap::ValueEnum::to_possible_value(&draft.sand)
+            .map(|v| v.get_name().to_string())
+            .unwrap_or_default();
+        let start_garbage = match draft.start_garbage {
+            0 => "off".to_string(),
+            1 => "1 row".to_string(),
+            rows => format!("{rows} rows"),
+        };
+        let opening = if draft.start_clean_streak {
+            "no S/Z"
+        } else {
+            "any"
+        };
+        let name = if draft.name.is_empty() {
+            "type a name".to_string()
+        } else {
+            draft.name.clone()
+        };
+        [
+            name,
+            goal.to_string(),
+            target,
+            garbage,
+            draft.colors.to_string(),
+            sand,
+            start_garbage,
+            opening.to_string(),
+            String::new(),
+        ]
+    }
+}
@@ -0,0 +1,185 @@
+//! Diagnostics overlay (F3): render FPS, logic ticks per second, physics time per tick and
+//! event-loop latency, averaged over one-second windows, for performance reports. Also the
+//! render and logic frame times of a whole game, shown as percentiles after it.
+
+use std::time::{Duration, Instant};
+
+/// Figures are averaged over windows this long.
+const WINDOW: Duration = Duration::from_secs(1);
+
+/// Frame time histogram bucket width.
+const BUCKET: Duration = Duration::from_micros(100);
+/// Buckets up to 50 ms; longer times all go in one more.
+const BUCKETS: usize = 500;
+
+/// The last complete window's averages.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Stats {
+    /// Frames drawn per second.
+    pub fps: f64,
+    /// Logic ticks (gravity + sand) per second.
+    pub tps: f64,
+    /// Mean time spent in sand physics per logic tick.
+    pub physics: Duration,
+    /// Mean and worst time from the start of a loop pass until input is read.
+    pub latency: Duration,
+    pub latency_max: Duration,
+}
+
+/// Counters for the window in progress.
+#[derive(Debug)]
+pub struct Diagnostics {
+    window_start: Instant,
+    frames: u32,
+    ticks: u32,
+    physics: Duration,
+    passes: u32,
+    latency: Duration,
+    latency_max: Duration,
+    stats: Stats,
+}
+
+impl Diagnostics {
+    pub fn new(now: Instant) -> Self {
+        Self {
+            window_start: now,
+            frames: 0,
+            ticks: 0,
+            physics: Duration::ZERO,
+            passes: 0,
+            latency: Duration::ZERO,
+            latency_max: Duration::ZERO,
+            stats: Stats::default(),
+        }
+    }
+
+    /// Figures to show: zero until the first window completes.
+    pub const fn stats(&self) -> Stats {
+        self.stats
+    }
+
+    pub fn frame(&mut self) {
+        self.frames += 1;
+    }
+
+    /// One logic tick, which spent `physics` in sand physics.
+    pub fn tick(&mut self, physics: Duration) {
+        self.ticks += 1;
+        self.physics += physics;
+    }
+
+    /// A loop pass reached the input poll `latency` after it started. Closes the window once it
+    /// is `WINDOW` long.
+    pub fn pass(&mut self, latency: Duration, now: Instant) {
+        self.passes += 1;
+        self.latency += latency;
+        self.latency_max = self.latency_max.max(latency);
+
+        let elapsed = now.duration_since(self.window_start);
+        if elapsed < WINDOW {
+            return;
+        }
+        let secs = elapsed.as_secs_f64();
+        self.stats = Stats {
+            fps: f64::from(self.frames) / secs,
+            tps: f64::from(self.ticks) / secs,
+            physics: self.physics / self.ticks.max(1),
+            latency: self.latency / self.passes,
+            latency_max: self.latency_max,
+        };
+        *self = Self {
+            stats: self.stats,
+            ..Self::new(now)
+        };
+    }
+}
+
+/// Frame times in `BUCKET` wide buckets.
+#[derive(Debug, Clone)]
+pub struct Histogram {
+    counts: Vec<u32>,
+    total: u32,
+}
+
+impl Default for Histogram {
+    fn default() -> Self {
+        Self {
+            counts: vec![0; BUCKETS + 1],
+            total: 0,
+        }
+    }
+}
+
+impl Histogram {
+    pub fn record(&mut self, time: Duration) {
+        let bucket = (time.as_micros() / BUCKET.as_micros()) as usize;
+        self.counts[bucket.min(BUCKETS)] += 1;
+        self.total += 1;
+    }
+
+    /// Time that `percent` of the frames took at most, to a bucket's width: `Duration::MAX`
+    /// past the last bucket, None before the first frame.
+    pub fn percentile(&self, percent: u32) -> Option<Duration> {
+        let rank = (u64::from(self.total) * u64::from(percent))
+            .div_ceil(100)
+            .max(1);
+        let mut seen = 0;
+        let bucket = self.counts.iter().position(|&count| {
+            seen += u64::from(count);
+            seen >= rank
+        })?;
+        Some(if bucket == BUCKETS {
+            Duration::MAX
+        } else {
+            BUCKET * (bucket as u32 + 1)
+        })
+    }
+}
+
+/// Render and logic frame times over one game, and the logic ticks that came too late to run.
+#[derive(Debug, Clone, Default)]
+pub struct FrameTimes {
+    /// Time to draw a frame and write it to the terminal.
+    pub render: Histogram,
+    /// Time for one logic tick (gravity and sand physics).
+    pub logic: Histogram,
+    /// Ticks skipped because the loop came round more than a tick interval late.
+    pub dropped_ticks: u32,
+    ticking: bool,
+    /// Ticking again after a pause: the first tick's lateness is the pause, not lag.
+    resumed: bool,
+}
+
+impl FrameTimes {
+    /// Whether the game is ticking this loop pass (in play and not paused).
+    pub fn ticking(&mut self, ticking: bool) {
+        self.resumed |= ticking && !self.ticking;
+        self.ticking = ticking;
+    }
+
+    /// One logic tick that took `logic`, run `late` after it was due.
+    pub fn tick(&mut self, logic: Duration, late: Duration, interval: Duration) {
+        self.logic.record(logic);
+        if !std::mem::take(&mut self.resumed) {
+            self.dropped_ticks += (late.as_secs_f64() / interval.as_secs_f64()) as u32;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn percentiles_round_up_to_the_bucket() {
+        let mut histogram = Histogram::default();
+        assert_eq!(histogram.percentile(50), None);
+        for micros in [50, 150, 250, 350] {
+            histogram.record(Duration::from_micros(micros));
+        }
+        histogram.record(Duration::from_secs(1));
+        assert_eq!(histogram.percentile(50), Some(Duration::from_micros(300)));
+        assert_eq!(histogram.percentile(80), Some(Duration::from_micros(400)));
+        assert_eq!(histogram.percentile(99), Some(Duration::MAX));
+    }
+}
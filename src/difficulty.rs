@@ -0,0 +1,126 @@
+//! Difficulty presets: everything easy, medium and hard change, in one place. The config file's
+//! `difficulty` section can override any value of any preset.
+
+use crate::config::{DifficultyFileConfig, ProfileFileConfig};
+use crate::{Difficulty, PieceSet, SandType};
+
+/// What one difficulty sets. `--colors`, `--pieces` and `--sand-type` win over their values here.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DifficultyProfile {
+    /// Logic ticks per second at level 1 (gravity).
+    pub tick_rate: f64,
+    /// Gravity gained per level, as a fraction of `tick_rate`.
+    pub level_speedup: f64,
+    /// Next pieces shown.
+    pub preview: usize,
+    /// Sand colours in play.
+    pub colors: u8,
+    /// Chance (0..1) that a dealt piece repeats the previous piece's colour.
+    pub repeat_color_chance: f32,
+    /// Columns taken off the playfield.
+    pub narrower: u16,
+    /// Rows taken off the playfield.
+    pub shorter: u16,
+    pub pieces: PieceSet,
+    pub sand: SandType,
+    /// An obstacle is dropped in every this many levels (see `GameState::drop_obstacle`);
+    /// 0 = never.
+    pub obstacle_levels: u32,
+}
+
+impl DifficultyProfile {
+    const EASY: Self = Self {
+        tick_rate: 30.0,
+        level_speedup: 0.1,
+        preview: 3,
+        colors: 4,
+        repeat_color_chance: 0.40,
+        narrower: 0,
+        shorter: 0,
+        pieces: PieceSet::Small,
+        sand: SandType::Dry,
+        obstacle_levels: 0,
+    };
+
+    const MEDIUM: Self = Self {
+        tick_rate: 50.0,
+        preview: 2,
+        repeat_color_chance: 0.20,
+        narrower: 1,
+        pieces: PieceSet::Tetromino,
+        ..Self::EASY
+    };
+
+    const HARD: Self = Self {
+        tick_rate: 90.0,
+        preview: 1,
+        repeat_color_chance: 0.08,
+        narrower: 2,
+        shorter: 2,
+        obstacle_levels: 3,
+        ..Self::MEDIUM
+    };
+
+    /// Playfield size after taking off `narrower` / `shorter`. Never shrinks below 4×8 (or the
+    /// given size if already smaller).
+    pub fn playfield_size(&self, width: u16, height: u16) -> (u16, u16) {
+        (
+            width.saturating_sub(self.narrower).max(width.min(4)),
+            height.saturating_sub(self.shorter).max(height.min(8)),
+        )
+    }
+
+    /// This profile with the config file's values on top.
+    fn with(mut self, file: &ProfileFileConfig) -> Self {
+        self.tick_rate = file.tick_rate.unwrap_or(self.tick_rate).max(1.0);
+        self.level_speedup = file.level_speedup.unwrap_or(self.level_speedup).max(0.0);
+        self.preview = file.preview.unwrap_or(self.preview).clamp(1, 3);
+        self.colors = file.colors.unwrap_or(self.colors).clamp(2, 6);
+        self.repeat_color_chance = file
+            .repeat_color_chance
+            .unwrap_or(self.repeat_color_chance)
+            .clamp(0.0, 1.0);
+        self.narrower = file.narrower.unwrap_or(self.narrower);
+        self.shorter = file.shorter.unwrap_or(self.shorter);
+        self.pieces = file.pieces.unwrap_or(self.pieces);
+        self.sand = file.sand.unwrap_or(self.sand);
+        self.obstacle_levels = file.obstacle_levels.unwrap_or(self.obstacle_levels);
+        self
+    }
+}
+
+/// The three presets, as built in or as the config file changed them.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DifficultyProfiles {
+    easy: DifficultyProfile,
+    medium: DifficultyProfile,
+    hard: DifficultyProfile,
+}
+
+impl Default for DifficultyProfiles {
+    fn default() -> Self {
+        Self {
+            easy: DifficultyProfile::EASY,
+            medium: DifficultyProfile::MEDIUM,
+            hard: DifficultyProfile::HARD,
+        }
+    }
+}
+
+impl DifficultyProfiles {
+    pub fn from_file(file: &DifficultyFileConfig) -> Self {
+        Self {
+            easy: DifficultyProfile::EASY.with(&file.easy),
+            medium: DifficultyProfile::MEDIUM.with(&file.medium),
+            hard: DifficultyProfile::HARD.with(&file.hard),
+        }
+    }
+
+    pub const fn get(&self, difficulty: Difficulty) -> &DifficultyProfile {
+        match difficulty {
+            Difficulty::Easy => &self.easy,
+            Difficulty::Medium => &self.medium,
+            Difficulty::Hard => &self.hard,
+        }
+    }
+}
@@ -0,0 +1,104 @@
+//! `setrixtui doctor`: a terminal capability report for bug reports. Measures how fast the
+//! terminal takes full-screen colour frames, and checks colour depth and keyboard protocol
+//! support.
+
+use anyhow::Result;
+use crossterm::execute;
+use crossterm::terminal::{EnterAlternateScreen, LeaveAlternateScreen, size};
+use std::fmt::Write as _;
+use std::io::Write;
+use std::time::{Duration, Instant};
+
+/// Frames drawn for the throughput test.
+const BENCH_FRAMES: u32 = 60;
+
+/// Print the report to stdout. The throughput test briefly takes over the screen.
+pub fn run(frame_rate: f64) -> Result<()> {
+    let (cols, rows) = size().unwrap_or((80, 24));
+    let key_releases =
+        cfg!(windows) || crossterm::terminal::supports_keyboard_enhancement().unwrap_or(false);
+    let (bytes, elapsed) = bench(cols, rows)?;
+
+    let env = |name: &str| std::env::var(name).unwrap_or_else(|_| "(unset)".to_string());
+    let (fit_w, fit_h) = crate::ui::playfield_size_for_terminal_clamped(cols, rows);
+    let secs = elapsed.as_secs_f64().max(f64::EPSILON);
+    let fps = f64::from(BENCH_FRAMES) / secs;
+
+    println!("setrixtui {} doctor", env!("CARGO_PKG_VERSION"));
+    println!(
+        "  platform    {} {}",
+        std::env::consts::OS,
+        std::env::consts::ARCH
+    );
+    println!(
+        "  terminal    TERM={} COLORTERM={} TERM_PROGRAM={}",
+        env("TERM"),
+        env("COLORTERM"),
+        env("TERM_PROGRAM")
+    );
+    println!("  size        {cols}x{rows} (board fits up to {fit_w}x{fit_h})");
+    println!("  colour      {}", color_depth());
+    println!(
+        "  keyboard    key release events: {}",
+        if key_releases {
+            "yes"
+        } else {
+            "no (held keys are detected from OS auto-repeat)"
+        }
+    );
+    println!(
+        "  throughput  {BENCH_FRAMES} full-screen frames in {:.0} ms: {fps:.0} fps, {:.1} MB/s",
+        secs * 1000.0,
+        bytes as f64 / secs / 1_000_000.0
+    );
+    if fps < frame_rate {
+        println!(
+            "  note        below the {frame_rate:.0} fps target: try --no-animation, a smaller \
+             window or a faster terminal"
+        );
+    }
+    Ok(())
+}
+
+/// Colour depth as advertised by the environment (terminals can't be asked reliably).
+fn color_depth() -> &'static str {
+    let colorterm = std::env::var("COLORTERM").unwrap_or_default();
+    let term = std::env::var("TERM").unwrap_or_default();
+    if std::env::var_os("NO_COLOR").is_some() {
+        "NO_COLOR is set (the game draws in colour regardless)"
+    } else if colorterm == "truecolor" || colorterm == "24bit" || cfg!(windows) {
+        "24-bit"
+    } else if term.contains("256color") {
+        "256 colours (24-bit not advertised; themes are approximated)"
+    } else {
+        "16 colours or unknown (set COLORTERM=truecolor if the terminal supports it)"
+    }
+}
+
+/// Draw `BENCH_FRAMES` screens of 24-bit half blocks, every cell a new colour like busy sand.
+/// Returns the bytes written and how long it took.
+fn bench(cols: u16, rows: u16) -> Result<(usize, Duration)> {
+    let mut stdout = std::io::stdout();
+    execute!(stdout, EnterAlternateScreen)?;
+    let mut bytes = 0;
+    let start = Instant::now();
+    for frame in 0..BENCH_FRAMES {
+        let mut out = String::from("\x1b[H");
+        for y in 0..rows {
+            for x in 0..cols {
+                let v = (u32::from(x) * 7 + u32::from(y) * 13 + frame * 5) % 256;
+                let _ = write!(out, "\x1b[38;2;{v};80;160;48;2;160;{v};80m▀");
+            }
+            if y + 1 < rows {
+                out.push_str("\r\n");
+            }
+        }
+        out.push_str("\x1b[0m");
+        stdout.write_all(out.as_bytes())?;
+        stdout.flush()?;
+        bytes += out.len();
+    }
+    let elapsed = start.elapsed();
+    execute!(stdout, LeaveAlternateScreen)?;
+    Ok((bytes, elapsed))
+}
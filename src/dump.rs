@@ -0,0 +1,458 @@
+//! State dumps (F10) for bug reports.
+//!
+//! A dump is the whole game (board, frozen grains, pieces, bag, timers, seed and options) as a
+//! JSON file under `dumps/` in the config directory. `--load-state FILE` plays on from one, with
+//! the options the dumped game was started with.
+//!
+//! The board is one string per grain row: `.` empty, `0`–`5` sand, `a`–`f` colour converters by
+//! colour, `*` rainbow and `#` stone. Shadow edges are left out; they only change how grains are
+//! drawn.
+
+use crate::custom::CustomMode;
+use crate::game::{Cell, FrozenGrain, GameState, NextPiece, Piece, PieceKind, Playfield};
+use crate::theme::Theme;
+use crate::{Args, GameConfig};
+use anyhow::{Context, Result};
+use clap::{Parser, ValueEnum};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StateDump {
+    version: String,
+    /// Seed the game started from, in hex (as `--seed` takes it).
+    seed: String,
+    /// Game RNG (colours, obstacles), sand RNG and bag RNG states now.
+    rng: u64,
+    #[serde(default)]
+    physics_rng: u64,
+    bag_rng: u64,
+    /// `GameState::state_hash` in hex, as in `--state-socket` lines.
+    hash: String,
+    score: u32,
+    level: u32,
+    lines: u32,
+    game_over: bool,
+    /// Time played.
+    elapsed_ms: u64,
+    board: Board,
+    /// `--dual`: the board without the piece, and whether it is drawn on the left.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    idle_board: Option<Board>,
+    #[serde(default)]
+    idle_left: bool,
+    piece: Option<PieceDump>,
+    /// Co-op: the other player's piece.
+    partner: Option<PieceDump>,
+    next: Vec<PieceDump>,
+    /// Kinds queued in the bag, next first.
+    bag: Vec<PieceKind>,
+    /// How many of those are left from each bag, oldest first, and how many of the oldest
+    /// have been dealt.
+    #[serde(default)]
+    bag_left: Vec<usize>,
+    #[serde(default)]
+    bag_dealt: usize,
+    timers: Timers,
+    /// Flags the game was started with, then what was picked on the menu.
+    command_line: Vec<String>,
+    mode: String,
+    difficulty: String,
+    time_limit: u32,
+    clear_lines: u32,
+    custom: Option<CustomMode>,
+    /// Game options with the config file applied, as Rust debug output (not read back).
+    config: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Board {
+    /// In block cells.
+    width: usize,
+    height: usize,
+    /// Sand ticks run.
+    tick: u32,
+    /// Zone: banked stone rows at the bottom, in grains.
+    banked_rows: usize,
+    rows: Vec<String>,
+    /// Grains of locked pieces not yet crumbled into sand: `[x, y, cell]`.
+    frozen: Vec<(usize, usize, char)>,
+    /// A clear in progress: its cells, and whether it is a bomb blast.
+    clearing: Vec<(usize, usize)>,
+    blast: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct PieceDump {
+    kind: PieceKind,
+    /// Grain position (None for queued pieces).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    position: Option<(i32, i32)>,
+    /// Main colour of a queued piece (the next piece's colour repeats it by chance).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    color: Option<u8>,
+    rotation: u8,
+    colors: Vec<u8>,
+    rainbow_cell: Option<usize>,
+    converter_cell: Option<usize>,
+}
+
+impl Board {
+    /// The dump of one board, with no clear in progress.
+    fn new(playfield: &Playfield, frozen: &[FrozenGrain]) -> Self {
+        let (gw, gh) = playfield.grain_dims();
+        Self {
+            width: playfield.width,
+            height: playfield.height,
+            tick: playfield.tick_count,
+            banked_rows: playfield.banked_rows,
+            rows: (0..gh)
+                .map(|y| {
+                    (0..gw)
+                        .map(|x| cell_char(playfield.get(x, y).unwrap_or(Cell::Empty)))
+                        .collect()
+                })
+                .collect(),
+            frozen: frozen
+                .iter()
+                .map(|fg| (fg.x, fg.y, cell_char(fg.cell)))
+                .collect(),
+            clearing: Vec::new(),
+            blast: false,
+        }
+    }
+
+    /// Lay the dumped grains onto `playfield`, a board of the same size. Returns the frozen
+    /// grains.
+    fn restore(&self, playfield: &mut Playfield) -> Result<Vec<FrozenGrain>> {
+        let (gw, gh) = playfield.grain_dims();
+        anyhow::ensure!(
+            self.rows.len() == gh && self.rows.iter().all(|row| row.chars().count() == gw),
+            "the board isn't {}×{} blocks",
+            self.width,
+            self.height
+        );
+        for (y, row) in self.rows.iter().enumerate() {
+            for (x, c) in row.chars().enumerate() {
+                playfield.set(x, y, char_cell(c)?);
+            }
+        }
+        playfield.banked_rows = self.banked_rows;
+        playfield.tick_count = self.tick;
+        self.frozen
+            .iter()
+            .map(|&(x, y, c)| {
+                Ok(FrozenGrain {
+                    x,
+                    y,
+                    cell: char_cell(c)?,
+                })
+            })
+            .collect()
+    }
+}
+
+impl From<&Piece> for PieceDump {
+    fn from(piece: &Piece) -> Self {
+        Self {
+            kind: piece.kind,
+            position: Some((piece.gx, piece.gy)),
+            color: None,
+            rotation: piece.rotation,
+            colors: piece.cell_colors.clone(),
+            rainbow_cell: piece.rainbow_cell,
+            converter_cell: piece.converter_cell,
+        }
+    }
+}
+
+impl From<&NextPiece> for PieceDump {
+    fn from(next: &NextPiece) -> Self {
+        Self {
+            kind: next.kind,
+            position: None,
+            color: Some(next.color_index),
+            rotation: 0,
+            colors: next.cell_colors.clone(),
+            rainbow_cell: next.rainbow_cell,
+            converter_cell: next.converter_cell,
+        }
+    }
+}
+
+impl PieceDump {
+    fn piece(&self, mirrored: bool) -> Piece {
+        let (gx, gy) = self.position.unwrap_or_default();
+        Piece {
+            kind: self.kind,
+            gx,
+            gy,
+            rotation: self.rotation,
+            cell_colors: self.colors.clone(),
+            rainbow_cell: self.rainbow_cell,
+            converter_cell: self.converter_cell,
+            mirrored,
+        }
+    }
+
+    fn next_piece(&self) -> NextPiece {
+        NextPiece {
+            kind: self.kind,
+            color_index: self
+                .color
+                .or_else(|| self.colors.first().copied())
+                .unwrap_or(0),
+            cell_colors: self.colors.clone(),
+            rainbow_cell: self.rainbow_cell,
+            converter_cell: self.converter_cell,
+        }
+    }
+}
+
+/// Lock, spawn, combo, zone, focus and tide timers (see `GameState::timers`).
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Timers {
+    /// The piece has rested on the stack this long.
+    pub lock_delay_ms: Option<u64>,
+    pub lock_delay_resets: u32,
+    /// Spawn delay left.
+    pub spawn_delay_ms: Option<u64>,
+    pub crumble_delay_ticks: u32,
+    pub combo_multiplier: u32,
+    pub combo_timer_ticks: u32,
+    pub combo_peak: u32,
+    pub placement_clears: u32,
+    pub focus: u32,
+    pub focus_ticks: u32,
+    #[serde(default)]
+    pub breather_ticks: u32,
+    pub zone: u32,
+    pub zone_ticks: u32,
+    pub tide_ticks: u32,
+    pub tide_rows: u32,
+    pub converter_budget: u32,
+    pub obstacle_due: bool,
+    pub settle_left_first: bool,
+}
+
+/// The character a cell is dumped as.
+const fn cell_char(cell: Cell) -> char {
+    match cell {
+        Cell::Empty => '.',
+        Cell::Sand(color, _) => (b'0' + color) as char,
+        Cell::Converter(color, _) => (b'a' + color) as char,
+        Cell::Rainbow(_) => '*',
+        Cell::Stone(_) => '#',
+    }
+}
+
+fn char_cell(c: char) -> Result<Cell> {
+    Ok(match c {
+        '.' => Cell::Empty,
+        '0'..='5' => Cell::Sand(c as u8 - b'0', false),
+        'a'..='f' => Cell::Converter(c as u8 - b'a', false),
+        '*' => Cell::Rainbow(false),
+        '#' => Cell::Stone(false),
+        _ => anyhow::bail!("unknown board character {c:?}"),
+    })
+}
+
+/// Write the dump of `state`, `elapsed` into a game started with `command_line`. Returns the
+/// file's path.
+pub fn save(
+    state: &GameState,
+    command_line: &[String],
+    args: &Args,
+    config: &GameConfig,
+    elapsed: Duration,
+    now: Instant,
+) -> Result<PathBuf> {
+    let dump = StateDump::new(state, command_line, args, config, elapsed, now);
+    let dir = crate::config::config_dir().join("dumps");
+    fs::create_dir_all(&dir).with_context(|| format!("creating {}", dir.display()))?;
+    let stamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_millis());
+    let path = dir.join(format!("setrixtui-state-{stamp}.json"));
+    fs::write(&path, serde_json::to_string_pretty(&dump)?)
+        .with_context(|| format!("writing {}", path.display()))?;
+    Ok(path)
+}
+
+/// Read a dump written by `save`.
+pub fn load(path: &Path) -> Result<StateDump> {
+    let content =
+        fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+    serde_json::from_str(&content).with_context(|| format!("parsing {}", path.display()))
+}
+
+impl StateDump {
+    /// The dump of `state`, `elapsed` into a game started with `command_line`.
+    fn new(
+        state: &GameState,
+        command_line: &[String],
+        args: &Args,
+        config: &GameConfig,
+        elapsed: Duration,
+        now: Instant,
+    ) -> Self {
+        let name = |value: Option<clap::builder::PossibleValue>| {
+            value.map(|v| v.get_name().to_string()).unwrap_or_default()
+        };
+        Self {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            seed: format!("{:x}", state.seed),
+            rng: state.rng.get_seed(),
+            physics_rng: state.physics_rng.get_seed(),
+            bag_rng: state.bag.rng_seed(),
+            hash: format!("{:016x}", state.state_hash()),
+            score: state.score,
+            level: state.level,
+            lines: state.lines_cleared,
+            game_over: state.game_over,
+            elapsed_ms: elapsed.as_millis() as u64,
+            board: Board {
+                clearing: state.line_clear_cells.clone(),
+                blast: state.line_clear_blast,
+                ..Board::new(&state.playfield, &state.frozen_grains)
+            },
+            idle_board: state
+                .idle_board
+                .as_ref()
+                .map(|board| Board::new(&board.playfield, &board.frozen_grains)),
+            idle_left: state.idle_board.as_ref().is_some_and(|board| board.left),
+            piece: state.piece.as_ref().map(PieceDump::from),
+            partner: state
+                .partner
+                .as_ref()
+                .and_then(|seat| seat.piece.as_ref())
+                .map(PieceDump::from),
+            next: state.next_pieces.iter().map(PieceDump::from).collect(),
+            bag: state.bag.queue().to_vec(),
+            bag_left: state.bag.progress().0.to_vec(),
+            bag_dealt: state.bag.progress().1,
+            timers: state.timers(now),
+            command_line: command_line.to_vec(),
+            mode: name(args.mode.to_possible_value()),
+            difficulty: name(args.difficulty.to_possible_value()),
+            time_limit: args.time_limit,
+            clear_lines: args.clear_lines,
+            custom: config.custom.clone(),
+            config: format!("{config:?}"),
+        }
+    }
+
+    /// The dumped game's flags, with its menu picks on top; it starts without the menu.
+    pub fn args(&self) -> Result<Args> {
+        let mut args = Args::try_parse_from(
+            std::iter::once("setrixtui".to_string()).chain(self.command_line.iter().cloned()),
+        )
+        .context("reading the dumped command line")?;
+        args.mode = ValueEnum::from_str(&self.mode, true).map_err(anyhow::Error::msg)?;
+        args.difficulty =
+            ValueEnum::from_str(&self.difficulty, true).map_err(anyhow::Error::msg)?;
+        args.time_limit = self.time_limit;
+        args.clear_lines = self.clear_lines;
+        args.seed = Some(u64::from_str_radix(&self.seed, 16).context("reading the seed")?);
+        args.seed_from_clipboard = false;
+        args.load_state = None;
+        args.no_menu = true;
+        Ok(args)
+    }
+
+    /// Flags the dumped game was started with.
+    pub fn command_line(&self) -> &[String] {
+        &self.command_line
+    }
+
+    /// Time the dumped game had been played.
+    pub const fn elapsed(&self) -> Duration {
+        Duration::from_millis(self.elapsed_ms)
+    }
+
+    /// The custom mode the dumped game was playing, if any.
+    pub fn custom(&self) -> Option<CustomMode> {
+        self.custom.clone()
+    }
+
+    /// The game as it was dumped. `config` should be built from `args`.
+    pub fn restore(&self, theme: Theme, config: &GameConfig, now: Instant) -> Result<GameState> {
+        let board = &self.board;
+        // --dual: the game is both boards wide
+        let boards = if config.dual { 2 } else { 1 };
+        let width = board.width as u16 * boards;
+        let mut state = GameState::new(theme, width, board.height as u16, config);
+        state.frozen_grains = board.restore(&mut state.playfield)?;
+        if let (Some(idle), Some(dumped)) = (state.idle_board.as_mut(), &self.idle_board) {
+            idle.frozen_grains = dumped.restore(&mut idle.playfield)?;
+            idle.left = self.idle_left;
+        }
+        state.line_clear_cells.clone_from(&board.clearing);
+        state.line_clear_in_progress = !board.clearing.is_empty();
+        state.line_clear_blast = board.blast;
+
+        let mirror = state.mirror;
+        state.piece = self.piece.as_ref().map(|piece| piece.piece(mirror));
+        if let Some(seat) = state.partner.as_mut() {
+            seat.piece = self.partner.as_ref().map(|piece| piece.piece(mirror));
+        }
+        state.next_pieces = self.next.iter().map(PieceDump::next_piece).collect();
+        state
+            .bag
+            .restore(&self.bag, &self.bag_left, self.bag_dealt, self.bag_rng);
+        state.rng = fastrand::Rng::with_seed(self.rng);
+        state.physics_rng = fastrand::Rng::with_seed(self.physics_rng);
+        state.seed = u64::from_str_radix(&self.seed, 16).context("reading the seed")?;
+        state.score = self.score;
+        state.level = self.level;
+        state.lines_cleared = self.lines;
+        state.game_over = self.game_over;
+        state.restore_timers(&self.timers, now);
+        Ok(state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::FileConfig;
+
+    /// Play on `states` in step for `ticks` sand ticks, a hard drop every `drop_every`.
+    fn play(states: &mut [&mut GameState], ticks: u32, drop_every: u32, now: &mut Instant) {
+        for tick in 0..ticks {
+            *now += Duration::from_millis(20);
+            for state in states.iter_mut() {
+                if tick % drop_every == 0 {
+                    state.hard_drop(*now);
+                }
+                state.tick_gravity(*now);
+                state.tick_sand();
+                state.check_lock(*now);
+                if state.line_clear_in_progress {
+                    state.finish_line_clear();
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn restored_game_plays_on_the_same() {
+        let args = Args::parse_from(["setrixtui", "--seed", "5eed"]);
+        let config = GameConfig::new(&args, FileConfig::default());
+        let mut now = Instant::now();
+        let mut state = GameState::new(Theme::default(), args.width, args.height, &config);
+        play(&mut [&mut state], 500, 40, &mut now);
+
+        let dump = StateDump::new(&state, &[], &args, &config, Duration::ZERO, now);
+        let json = serde_json::to_string(&dump).unwrap();
+        let dump: StateDump = serde_json::from_str(&json).unwrap();
+        let mut restored = dump.restore(Theme::default(), &config, now).unwrap();
+        assert_eq!(restored.state_hash(), state.state_hash());
+
+        play(&mut [&mut state, &mut restored], 500, 25, &mut now);
+        assert_eq!(restored.state_hash(), state.state_hash());
+        assert_eq!(restored.score, state.score);
+    }
+}
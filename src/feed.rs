@@ -0,0 +1,53 @@
+//! Event feed (`--event-log`): the last few scoring events as short lines in the sidebar, so
+//! it's clear where points came from.
+
+use crate::game::{GameEvent, GameState};
+
+/// Lines kept (and shown).
+pub const FEED_LEN: usize = 5;
+
+/// Recent events, oldest first.
+#[derive(Debug, Default)]
+pub struct EventFeed {
+    lines: Vec<String>,
+}
+
+impl EventFeed {
+    pub fn lines(&self) -> &[String] {
+        &self.lines
+    }
+
+    /// Add one frame's events (clear points read from `state`). Piece movement isn't listed.
+    pub fn on_events(&mut self, state: &GameState, events: &[GameEvent]) {
+        for &event in events {
+            let line = match event {
+                GameEvent::Clear => {
+                    let (points, spans) = state.last_clear;
+                    let kind = match spans {
+                        1 => String::new(),
+                        2 => "double ".to_string(),
+                        3 => "triple ".to_string(),
+                        n => format!("{n}× "),
+                    };
+                    if points > 0 {
+                        format!("+{points} {kind}clear")
+                    } else {
+                        format!("{kind}clear banked")
+                    }
+                }
+                // x2 comes with every first clear
+                GameEvent::Combo(n) if n > 2 => format!("Combo ×{n}"),
+                GameEvent::ComboEnd { peak, bonus } => format!("+{bonus} chain ×{peak}"),
+                GameEvent::LevelUp(level) => format!("Level {level}"),
+                GameEvent::Breather => "Breather ×2".to_string(),
+                GameEvent::Mission { bonus } => format!("+{bonus} mission"),
+                GameEvent::GameOver => "Game over".to_string(),
+                _ => continue,
+            };
+            if self.lines.len() == FEED_LEN {
+                self.lines.remove(0);
+            }
+            self.lines.push(line);
+        }
+    }
+}
@@ -1,23 +1,107 @@
 //! Game state: playfield, piece, sand, line clear, gravity.
 
+use crate::invariants::GrainCheck;
 use crate::theme::Theme;
 use ratatui::style::Color;
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::Instant;
 use fastrand::Rng;
 
 /// Scale factor: each tetromino block is `GRAIN_SCALE` x `GRAIN_SCALE` grains.
 pub const GRAIN_SCALE: usize = 6;
 
+/// Source of `Playfield` column revisions. Unique across boards, so a revision seen before means
+/// the same column contents, even on a board cloned from another.
+static NEXT_REVISION: AtomicU64 = AtomicU64::new(1);
+
+fn next_revision() -> u64 {
+    NEXT_REVISION.fetch_add(1, Ordering::Relaxed)
+}
+
 /// Spawn zone: top N physical rows.
 const SPAWN_ZONE_ROWS: usize = 2 * GRAIN_SCALE;
 
-/// After this many move/rotate resets, piece locks on next land immediately.
+/// After this many move/rotate resets the lock timer stops restarting, so the piece locks once it runs out.
 const LOCK_DELAY_RESET_LIMIT: u32 = 15;
 
-/// Tetromino kinds (I, O, T, S, Z, J, L).
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum TetrominoKind {
+/// Blast radius of a bomb in grains (two block cells).
+pub const BOMB_RADIUS: i32 = 2 * GRAIN_SCALE as i32;
+
+/// Chance that a newly queued piece is a bomb (with `--bombs`).
+const BOMB_PIECE_CHANCE: f32 = 0.03;
+
+/// Reaching this combo multiplier turns the last queued piece into a bomb.
+const BOMB_COMBO_AWARD: u32 = 5;
+
+/// Focus meter capacity; it must be full to activate.
+pub const FOCUS_MAX: u32 = 100;
+
+/// Focus gained per cleared line (a full meter every four).
+const FOCUS_PER_CLEAR: u32 = 25;
+
+/// Focus lasts this many (slowed) sand ticks, about 8 s at level 1.
+pub const FOCUS_TICKS: u32 = 36;
+
+/// Gravity and sand run this many times slower while focused.
+pub const FOCUS_SLOWDOWN: f64 = 4.0;
+
+/// Zone meter capacity; it must be full to activate.
+pub const ZONE_MAX: u32 = 100;
+
+/// Zone meter gained per cleared line (a full meter every ten).
+const ZONE_PER_CLEAR: u32 = 10;
+
+/// The zone lasts this many sand ticks, 10 s at level 1.
+pub const ZONE_TICKS: u32 = 180;
+
+/// Endless breather: a bonus phase of this many sand ticks, about 10 s at level 1 (sand runs
+/// `BREATHER_SAND_STEPS` steps a tick during it).
+pub const BREATHER_TICKS: u32 = 360;
+
+/// Sand steps per logic tick during a breather.
+pub const BREATHER_SAND_STEPS: u32 = 2;
+
+/// Clears score this many times their points during a breather.
+const BREATHER_POINTS: u32 = 2;
+
+/// Endless: a breather every this many levels, and after every all-clear.
+const BREATHER_LEVELS: u32 = 5;
+
+/// Co-op: the garbage tide rises one block row every this many sand ticks at level 1 (12 s)...
+const TIDE_TICKS: u32 = 216;
+
+/// ...this many sooner per level after the first...
+const TIDE_TICKS_PER_LEVEL: u32 = 12;
+
+/// ...but never more often than this.
+const TIDE_MIN_TICKS: u32 = 72;
+
+/// Sand ticks in a second at level 1 (custom mode garbage is set in seconds).
+const SAND_TICKS_PER_SEC: u32 = 18;
+
+/// `--start-garbage`: tries at each row's colours before giving up on the rows above.
+const START_GARBAGE_ROLLS: u32 = 20;
+
+/// A chain of clears (peak multiplier x3 and up, so two clears or more) earns this many points
+/// per multiplier step of its peak when it ends.
+const COMBO_END_BONUS: u32 = 20;
+
+/// Cascade clears of one placement score x2, x3, ... their points, up to x(1 + this).
+const PLACEMENT_CHAIN_MAX: u32 = 4;
+
+/// How long the "combo ended" banner shows.
+const COMBO_BANNER_MS: u32 = 1500;
+
+/// A clear this soon after the last clear's popup appeared adds to it (chains, co-op pairs).
+const POPUP_MERGE_MS: u32 = 400;
+
+/// Piece kinds: the seven tetrominoes, the twelve pentominoes, the beginner set (1–3 cells),
+/// and the bomb power-up (not in any bag).
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, serde::Serialize, serde::Deserialize,
+)]
+pub enum PieceKind {
     I,
     O,
     T,
@@ -25,10 +109,28 @@ pub enum TetrominoKind {
     Z,
     J,
     L,
+    F,
+    I5,
+    L5,
+    N,
+    P,
+    T5,
+    U,
+    V,
+    W,
+    X,
+    Y,
+    Z5,
+    Mono,
+    Domino,
+    I3,
+    L3,
+    /// 2x2 power-up: on lock it blasts every grain within `BOMB_RADIUS` instead of turning into sand.
+    Bomb,
 }
 
-impl TetrominoKind {
-    pub const ALL: [Self; 7] = [
+impl PieceKind {
+    pub const TETROMINOES: [Self; 7] = [
         Self::I,
         Self::O,
         Self::T,
@@ -38,8 +140,25 @@ impl TetrominoKind {
         Self::L,
     ];
 
-    /// 4 cells relative to origin (0,0); each (dx, dy).
-    pub const fn cells(&self) -> &[(i8, i8); 4] {
+    pub const PENTOMINOES: [Self; 12] = [
+        Self::F,
+        Self::I5,
+        Self::L5,
+        Self::N,
+        Self::P,
+        Self::T5,
+        Self::U,
+        Self::V,
+        Self::W,
+        Self::X,
+        Self::Y,
+        Self::Z5,
+    ];
+
+    pub const SMALL: [Self; 4] = [Self::Mono, Self::Domino, Self::I3, Self::L3];
+
+    /// Cells relative to origin (0,0); each (dx, dy). Four for tetrominoes, five for pentominoes, 1–3 for the small set.
+    pub const fn cells(&self) -> &'static [(i8, i8)] {
         match self {
             Self::I => &[(0, 0), (1, 0), (2, 0), (3, 0)],
             Self::O => &[(0, 0), (1, 0), (0, 1), (1, 1)],
@@ -48,14 +167,71 @@ impl TetrominoKind {
             Self::Z => &[(0, 0), (1, 0), (1, 1), (2, 1)],
             Self::J => &[(0, 0), (0, 1), (1, 1), (2, 1)],
             Self::L => &[(2, 0), (0, 1), (1, 1), (2, 1)],
+            Self::F => &[(1, 0), (2, 0), (0, 1), (1, 1), (1, 2)],
+            Self::I5 => &[(0, 0), (1, 0), (2, 0), (3, 0), (4, 0)],
+            Self::L5 => &[(3, 0), (0, 1), (1, 1), (2, 1), (3, 1)],
+            Self::N => &[(2, 0), (3, 0), (0, 1), (1, 1), (2, 1)],
+            Self::P => &[(0, 0), (1, 0), (0, 1), (1, 1), (0, 2)],
+            Self::T5 => &[(0, 0), (1, 0), (2, 0), (1, 1), (1, 2)],
+            Self::U => &[(0, 0), (2, 0), (0, 1), (1, 1), (2, 1)],
+            Self::V => &[(0, 0), (0, 1), (0, 2), (1, 2), (2, 2)],
+            Self::W => &[(0, 0), (0, 1), (1, 1), (1, 2), (2, 2)],
+            Self::X => &[(1, 0), (0, 1), (1, 1), (2, 1), (1, 2)],
+            Self::Y => &[(1, 0), (0, 1), (1, 1), (2, 1), (3, 1)],
+            Self::Z5 => &[(0, 0), (1, 0), (1, 1), (1, 2), (2, 2)],
+            Self::Mono => &[(0, 0)],
+            Self::Domino => &[(0, 0), (1, 0)],
+            Self::I3 => &[(0, 0), (1, 0), (2, 0)],
+            Self::L3 => &[(0, 0), (0, 1), (1, 1)],
+            Self::Bomb => &[(0, 0), (1, 0), (0, 1), (1, 1)],
+        }
+    }
+
+    /// `cells()` in the same order, flipped left to right within their bounding box when
+    /// `mirrored` (`--mirror`).
+    pub fn cells_facing(&self, mirrored: bool) -> Vec<(i8, i8)> {
+        let right = self.width() - 1;
+        self.cells()
+            .iter()
+            .map(|&(dx, dy)| if mirrored { (right - dx, dy) } else { (dx, dy) })
+            .collect()
+    }
+
+    /// Block columns the unrotated piece spans.
+    fn width(&self) -> i8 {
+        self.cells()
+            .iter()
+            .map(|&(dx, _)| dx + 1)
+            .max()
+            .unwrap_or(1)
+    }
+
+    /// Cell the piece rotates around (in `cells_facing()` coordinates).
+    fn rotation_center_facing(&self, mirrored: bool) -> (i8, i8) {
+        let (cx, cy) = self.rotation_center();
+        if mirrored {
+            (self.width() - 1 - cx, cy)
+        } else {
+            (cx, cy)
         }
     }
 
-    /// Colour index 0..6 for `theme.sand_color()`.
-    /// If `high_color` is false, maps to 0..3 (Green, Yellow, Red, Blue).
+    /// Cell the piece rotates around (in `cells()` coordinates).
+    const fn rotation_center(&self) -> (i8, i8) {
+        match self {
+            Self::I | Self::I3 => (1, 0),
+            Self::I5 => (2, 0),
+            Self::Mono | Self::Domino => (0, 0),
+            Self::P => (0, 1),
+            _ => (1, 1),
+        }
+    }
+
+    /// Base colour from the 6-colour table, or the 4-colour table (Green, Yellow, Red, Blue)
+    /// when `six` is false. `ColorScheme` folds these down further.
     #[allow(clippy::needless_pass_by_value)] // self is small but used by callers with &kind
-    pub const fn color_index(&self, high_color: bool) -> u8 {
-        if high_color {
+    const fn base_color(&self, six: bool) -> u8 {
+        if six {
             match self {
                 Self::S => 0, // Green
                 Self::O => 1, // Yellow
@@ -64,6 +240,17 @@ impl TetrominoKind {
                 Self::T => 4, // Magenta
                 Self::I => 5, // Cyan
                 Self::L => 2, // Orange -> Red
+                Self::F | Self::T5 => 4,
+                Self::I5 | Self::V => 5,
+                Self::L5 | Self::Z5 => 2,
+                Self::N | Self::W => 0,
+                Self::P | Self::X => 1,
+                Self::U | Self::Y => 3,
+                Self::Mono => 4,
+                Self::Domino => 0,
+                Self::I3 => 5,
+                Self::L3 => 3,
+                Self::Bomb => 2, // Red
             }
         } else {
             match self {
@@ -74,59 +261,158 @@ impl TetrominoKind {
                 Self::T => 2, // Red
                 Self::I => 3, // Blue
                 Self::L => 1, // Yellow
+                Self::N | Self::V | Self::W => 0,
+                Self::L5 | Self::P | Self::X => 1,
+                Self::F | Self::T5 | Self::Z5 => 2,
+                Self::I5 | Self::U | Self::Y => 3,
+                Self::Mono => 2,
+                Self::Domino => 0,
+                Self::I3 => 3,
+                Self::L3 => 1,
+                Self::Bomb => 2, // Red
             }
         }
     }
 }
 
+/// Colour assignment: how many sand colours are in play (2..=6) and which one each kind gets.
+/// Fewer colours means more same-colour neighbours, so bridges are easier to build.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ColorScheme {
+    count: u8,
+}
+
+impl ColorScheme {
+    pub const fn new(count: u8) -> Self {
+        let count = if count < 2 {
+            2
+        } else if count > 6 {
+            6
+        } else {
+            count
+        };
+        Self { count }
+    }
+
+    /// Number of colours in play.
+    pub const fn count(self) -> u8 {
+        self.count
+    }
+
+    /// Colour index 0..count for `theme.sand_color()`. Six and four colours use the hand-picked
+    /// tables; five folds the 6-colour table and three/two fold the 4-colour one.
+    pub const fn color_index(self, kind: PieceKind) -> u8 {
+        kind.base_color(self.count >= 5) % self.count
+    }
+}
+
+impl crate::PieceSet {
+    /// Kinds dealt by the bag for this set.
+    pub fn kinds(self) -> Vec<PieceKind> {
+        match self {
+            Self::Tetromino => PieceKind::TETROMINOES.to_vec(),
+            Self::Pentomino => PieceKind::PENTOMINOES.to_vec(),
+            Self::Mixed => [
+                PieceKind::TETROMINOES.as_slice(),
+                PieceKind::PENTOMINOES.as_slice(),
+            ]
+            .concat(),
+            Self::Small => PieceKind::SMALL.to_vec(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct FrozenGrain {
     pub x: usize,
     pub y: usize,
-    pub color_index: u8,
-    pub is_shadow: bool,
+    /// What the grain becomes once it crumbles into the playfield.
+    pub cell: Cell,
 }
 
 /// Current piece with position and rotation (0..4).
 #[derive(Debug, Clone)]
 pub struct Piece {
-    pub kind: TetrominoKind,
+    pub kind: PieceKind,
     pub gx: i32,
     pub gy: i32,
     pub rotation: u8, // 0..4
+    /// Colour of each cell, same order as `kind.cells()` (all equal unless two-tone).
+    pub cell_colors: Vec<u8>,
+    /// Index of the cell made of rainbow grains, if any.
+    pub rainbow_cell: Option<usize>,
+    /// Index of the cell made of colour-converter grains, if any.
+    pub converter_cell: Option<usize>,
+    /// `--mirror`: the shape is flipped left to right (S plays as Z, J as L, and so on).
+    pub mirrored: bool,
+}
+
+/// Queued piece shown in the next preview. `color_index` is the primary colour (repeat bias).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NextPiece {
+    pub kind: PieceKind,
     pub color_index: u8,
+    pub cell_colors: Vec<u8>,
+    pub rainbow_cell: Option<usize>,
+    pub converter_cell: Option<usize>,
 }
 
 impl Piece {
-    /// Returns the top-left grain coordinate for each of the 4 tetromino cells (uses logic position).
-    pub fn cell_grain_origins(&self) -> [(i32, i32); 4] {
+    /// Returns the top-left grain coordinate for each cell of the piece (uses logic position).
+    pub fn cell_grain_origins(&self) -> Vec<(i32, i32)> {
         self.cell_grain_origins_at(self.gx, self.gy)
     }
 
     /// Same as cell_grain_origins but with explicit offset (for smooth visual drawing).
-    pub fn cell_grain_origins_at(&self, ox: i32, oy: i32) -> [(i32, i32); 4] {
+    pub fn cell_grain_origins_at(&self, ox: i32, oy: i32) -> Vec<(i32, i32)> {
         let s = GRAIN_SCALE as i32;
-        if self.kind == TetrominoKind::O {
-            return [(ox, oy), (ox + s, oy), (ox, oy + s), (ox + s, oy + s)];
+        if matches!(self.kind, PieceKind::O | PieceKind::Bomb) {
+            return vec![(ox, oy), (ox + s, oy), (ox, oy + s), (ox + s, oy + s)];
         }
-        let cells = self.kind.cells();
         let r = self.rotation % 4;
-        let (cx, cy) = match self.kind {
-            TetrominoKind::I => (1, 0),
-            _ => (1, 1),
+        let (cx, cy) = self.kind.rotation_center_facing(self.mirrored);
+        self.kind
+            .cells_facing(self.mirrored)
+            .into_iter()
+            .map(|(dx, dy)| {
+                let (rdx, rdy) = rotate_cell(dx, dy, r, cx, cy);
+                (ox + (rdx as i32 * s), oy + (rdy as i32 * s))
+            })
+            .collect()
+    }
+
+    /// Grain x of a piece spawning over block `column` of a board `width` blocks wide; mirrored,
+    /// over the mirror image of the columns it would span.
+    pub fn spawn_gx(&self, column: i32, width: i32) -> i32 {
+        let column = if self.mirrored {
+            (width - column - i32::from(self.kind.width())).max(0)
+        } else {
+            column
         };
-        let mut out = [(0i32, 0i32); 4];
-        for (i, (dx, dy)) in cells.iter().enumerate() {
-            let (rdx, rdy) = rotate_cell(*dx, *dy, r, cx, cy);
-            out[i] = (
-                ox + (rdx as i32 * GRAIN_SCALE as i32),
-                oy + (rdy as i32 * GRAIN_SCALE as i32),
-            );
-        }
-        out
+        column * GRAIN_SCALE as i32
+    }
+
+    /// Grain at the centre of the piece's bounding box (blast origin for bombs).
+    pub fn blast_center(&self) -> (i32, i32) {
+        let origins = self.cell_grain_origins();
+        let s = GRAIN_SCALE as i32;
+        let (min_x, max_x) = origins
+            .iter()
+            .fold((i32::MAX, i32::MIN), |(lo, hi), &(x, _)| (lo.min(x), hi.max(x + s)));
+        let (min_y, max_y) = origins
+            .iter()
+            .fold((i32::MAX, i32::MIN), |(lo, hi), &(_, y)| (lo.min(y), hi.max(y + s)));
+        ((min_x + max_x) / 2, (min_y + max_y) / 2)
     }
 }
 
+/// True if grain (x, y) is inside the blast circle around `center`.
+#[inline]
+pub fn in_blast(center: (i32, i32), x: i32, y: i32) -> bool {
+    let (dx, dy) = (x - center.0, y - center.1);
+    dx * dx + dy * dy <= BOMB_RADIUS * BOMB_RADIUS
+}
+
 fn rotate_cell(dx: i8, dy: i8, r: u8, cx: i8, cy: i8) -> (i16, i16) {
     let dx = dx - cx;
     let dy = dy - cy;
@@ -140,11 +426,227 @@ fn rotate_cell(dx: i8, dy: i8, r: u8, cx: i8, cy: i8) -> (i16, i16) {
     (i16::from(dx + cx), i16::from(dy + cy))
 }
 
-/// Single cell: either empty or sand of a given colour index.
+/// Main colour for a newly queued piece (`--color-assign`). `kind`: the kind's own colour, or
+/// `last` (the colour queued before it) by the difficulty's repeat chance. `random`: any colour.
+/// `balanced`: the colour with the least sand on `playfield`, ties broken at random.
+fn assign_color(
+    rng: &mut Rng,
+    assign: crate::ColorAssign,
+    kind: PieceKind,
+    last: Option<u8>,
+    repeat_chance: f32,
+    colors: ColorScheme,
+    playfield: &Playfield,
+) -> u8 {
+    match assign {
+        crate::ColorAssign::Kind => match last {
+            Some(last) if rng.f32() < repeat_chance => last,
+            _ => colors.color_index(kind),
+        },
+        crate::ColorAssign::Random => rng.u8(..colors.count()),
+        crate::ColorAssign::Balanced => {
+            let grains = playfield.color_counts(colors.count());
+            let fewest = grains.iter().copied().min().unwrap_or(0);
+            let rarest: Vec<u8> = (0..colors.count())
+                .filter(|&c| grains[c as usize] == fewest)
+                .collect();
+            rarest[rng.usize(..rarest.len())]
+        }
+    }
+}
+
+/// A fresh board for a game with `config`: its stone and start garbage rows, its edges joined
+/// for `--cylinder`.
+fn new_board(rng: &mut Rng, width: u16, height: u16, config: &crate::GameConfig) -> Playfield {
+    let mut playfield = Playfield::new(width, height);
+    playfield.wrap = config.cylinder;
+    playfield.fill_stone_rows(config.dig_rows as usize);
+    fill_start_garbage(
+        rng,
+        &mut playfield,
+        config.dig_rows as usize,
+        config.start_garbage() as usize,
+        config.colors(),
+    );
+    if config.invisible {
+        playfield.track_moves();
+    }
+    playfield
+}
+
+/// `--start-garbage`: lay `rows` block rows of random colours on top of `below` rows of stone.
+/// A row that would complete a clear is rolled again; after `START_GARBAGE_ROLLS` tries the
+/// rows stop there.
+fn fill_start_garbage(
+    rng: &mut Rng,
+    playfield: &mut Playfield,
+    below: usize,
+    rows: usize,
+    colors: ColorScheme,
+) {
+    for row in below..below + rows {
+        let Some(filled) = (0..START_GARBAGE_ROLLS).find_map(|_| {
+            let row_colors: Vec<u8> = (0..playfield.width)
+                .map(|_| rng.u8(..colors.count()))
+                .collect();
+            let mut filled = playfield.clone();
+            filled.fill_sand_row(row, &row_colors);
+            (filled.find_spanning_components().0 == 0).then_some(filled)
+        }) else {
+            return;
+        };
+        *playfield = filled;
+    }
+}
+
+/// Per-cell colours for a new piece. Without two-tone every cell is `primary`.
+/// Two-tone mixes in one secondary colour (Setris-style) and, rarely, a third.
+fn roll_cell_colors(
+    rng: &mut Rng,
+    kind: PieceKind,
+    primary: u8,
+    two_tone: bool,
+    colors: ColorScheme,
+) -> Vec<u8> {
+    let mut cells = vec![primary; kind.cells().len()];
+    if !two_tone {
+        return cells;
+    }
+    let num_colors = colors.count();
+    let other = |rng: &mut Rng, not: u8| (not + rng.u8(1..num_colors)) % num_colors;
+    let secondary = other(rng, primary);
+    for c in &mut cells {
+        let r = rng.f32();
+        if r < 0.45 {
+            *c = secondary;
+        } else if r > 0.95 {
+            *c = other(rng, primary);
+        }
+    }
+    if cells.iter().all(|&c| c == primary) {
+        let i = rng.usize(..cells.len());
+        cells[i] = secondary;
+    }
+    cells
+}
+
+/// `--balance-colors`: each colour in play is dealt this many times per colour round.
+const COLOR_BAG_COPIES: usize = 2;
+
+/// `--balance-colors`: most queued pieces in a row with the same main colour.
+const COLOR_STREAK_LIMIT: usize = 3;
+
+/// Chance that a newly queued piece carries one rainbow cell (with `--rainbow`).
+const RAINBOW_PIECE_CHANCE: f32 = 0.04;
+
+/// Reaching this combo multiplier awards a rainbow cell to the last queued piece.
+const RAINBOW_COMBO_AWARD: u32 = 3;
+
+/// Kind of the next queued piece: from the bag, or occasionally a bomb in bomb mode.
+fn next_kind(bag: &mut Bag, rng: &mut Rng, bombs: bool) -> PieceKind {
+    if bombs && rng.f32() < BOMB_PIECE_CHANCE {
+        PieceKind::Bomb
+    } else {
+        bag.next()
+    }
+}
+
+/// Which cell (if any) of a new piece is rainbow. Always None unless rainbow mode is on.
+fn roll_rainbow_cell(rng: &mut Rng, kind: PieceKind, rainbow: bool) -> Option<usize> {
+    (rainbow && kind != PieceKind::Bomb && rng.f32() < RAINBOW_PIECE_CHANCE)
+        .then(|| rng.usize(..kind.cells().len()))
+}
+
+/// Stone grains within this many grains of a clear are removed with it (one block cell).
+const STONE_BREAK_RADIUS: i32 = GRAIN_SCALE as i32;
+
+/// Reaching this combo multiplier gives the last queued piece a colour-converter cell.
+const CONVERTER_COMBO_AWARD: u32 = 7;
+
+/// Most grains one converter cell may recolour in total (four block cells).
+const CONVERTER_FLOOD_LIMIT: u32 = 4 * (GRAIN_SCALE * GRAIN_SCALE) as u32;
+
+/// Single cell: empty, sand of a given colour index, a rainbow (wildcard) grain, stone, or a colour converter.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Cell {
     Empty,
     Sand(u8, bool), // colour index 0..6, is_shadow
+    /// Counts as every colour when looking for spanning clears.
+    Rainbow(bool), // is_shadow
+    /// Uncoloured garbage: never matches, falls straight down only, breaks when a clear happens next to it.
+    Stone(bool), // is_shadow
+    /// Behaves like sand of its colour; once at rest it recolours the neighbouring component, then becomes sand.
+    Converter(u8, bool), // colour index 0..6, is_shadow
+}
+
+impl Cell {
+    /// True for anything that occupies the grain (sand or rainbow).
+    #[inline]
+    pub const fn is_solid(self) -> bool {
+        !matches!(self, Self::Empty)
+    }
+
+    /// True if this grain can be part of a `color` span.
+    #[inline]
+    pub const fn matches_color(self, color: u8) -> bool {
+        match self {
+            Self::Sand(c, _) | Self::Converter(c, _) => c == color,
+            Self::Rainbow(_) => true,
+            Self::Empty | Self::Stone(_) => false,
+        }
+    }
+}
+
+/// Sand flow parameters consumed by `Playfield::tick_physics` (see `--sand-type`).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PhysicsParams {
+    /// Chance (0..1) that a grain skips its move this tick: higher = slower, clumpier sand.
+    pub lag: f32,
+    /// Chance (0..1) that a grain blocked below and diagonally slides sideways instead (liquids level out).
+    pub drift: f32,
+    /// Chance (0..1) that a grain blocked below tries to slide diagonally: lower = steeper piles.
+    pub diagonal: f32,
+    /// No randomness (`--reduced-motion`): grains scan in a fixed order and each chance above
+    /// is taken as always (>= 0.5) or never, so sand settles without jitter.
+    pub steady: bool,
+}
+
+impl PhysicsParams {
+    /// Preset for a sand type. `Dry` is the classic behaviour.
+    pub const fn preset(sand_type: crate::SandType) -> Self {
+        match sand_type {
+            crate::SandType::Dry => Self {
+                lag: 0.0,
+                drift: 0.0,
+                diagonal: 1.0,
+                steady: false,
+            },
+            crate::SandType::Wet => Self {
+                lag: 0.15,
+                drift: 0.0,
+                diagonal: 0.5,
+                steady: false,
+            },
+            crate::SandType::Sticky => Self {
+                lag: 0.3,
+                drift: 0.0,
+                diagonal: 0.15,
+                steady: false,
+            },
+            crate::SandType::Liquid => Self {
+                lag: 0.0,
+                drift: 0.6,
+                diagonal: 1.0,
+                steady: false,
+            },
+        }
+    }
+}
+
+impl Default for PhysicsParams {
+    fn default() -> Self {
+        Self::preset(crate::SandType::Dry)
+    }
 }
 
 /// Playfield: grid of cells. y=0 is top; rows are stored [0..height].
@@ -154,6 +656,19 @@ pub struct Playfield {
     pub height: usize,
     /// rows[y][x] = cell. rows[0] is top.
     rows: VecDeque<Vec<Cell>>,
+    /// Zone: grain rows at the bottom holding banked clears. They are stone that clears can't
+    /// break, cleared all at once when the zone ends.
+    pub banked_rows: usize,
+    /// Co-op: origins of the other player's piece cells, solid to `can_place`.
+    pub blocked: Vec<(i32, i32)>,
+    /// `--cylinder`: the left and right edges join, for pieces, sand and clears.
+    pub wrap: bool,
+    /// When each grain last moved or landed (same layout as `rows`); only kept for invisible sand.
+    moved_at: Option<VecDeque<Vec<Instant>>>,
+    /// Revision of each grain column (see `column_revision`).
+    revisions: Vec<u64>,
+    /// Row of each grain column's topmost solid grain; the board height when it is empty.
+    tops: Vec<usize>,
     pub tick_count: u32,
 }
 
@@ -166,16 +681,88 @@ impl Playfield {
             width: w,
             height: h,
             rows,
+            banked_rows: 0,
+            blocked: Vec::new(),
+            wrap: false,
+            moved_at: None,
+            revisions: vec![next_revision(); gw],
+            tops: vec![gh; gw],
             tick_count: 0,
         }
     }
 
+    /// Start recording per-grain "last moved" timestamps (invisible sand).
+    pub fn track_moves(&mut self) {
+        let (gw, gh) = self.grain_dims();
+        let now = Instant::now();
+        self.moved_at = Some((0..gh).map(|_| vec![now; gw]).collect());
+    }
+
+    /// When the grain at (x, y) last moved; None unless tracking is on.
+    #[inline]
+    pub fn moved_at(&self, x: usize, y: usize) -> Option<Instant> {
+        self.moved_at
+            .as_ref()
+            .and_then(|rows| rows.get(y))
+            .and_then(|row| row.get(x))
+            .copied()
+    }
+
     /// Get actual grain dimensions.
     #[inline]
     pub fn grain_dims(&self) -> (usize, usize) {
         (self.width * GRAIN_SCALE, self.height * GRAIN_SCALE)
     }
 
+    /// Grain column `x` brought round onto the board on a cylinder; unchanged otherwise.
+    #[inline]
+    pub fn wrap_x(&self, x: i32) -> i32 {
+        if self.wrap {
+            x.rem_euclid((self.width * GRAIN_SCALE) as i32)
+        } else {
+            x
+        }
+    }
+
+    /// Grain column next to `x` (`dir` -1 for left, 1 for right); None past a wall.
+    #[inline]
+    fn beside(&self, x: usize, dir: i32) -> Option<usize> {
+        let (gw, _) = self.grain_dims();
+        let nx = self.wrap_x(x as i32 + dir);
+        (0..gw as i32).contains(&nx).then_some(nx as usize)
+    }
+
+    /// Changes whenever grain column `x` does: two boards with the same revision of a column
+    /// hold the same grains in it (`view::BoardView` only rescans columns that changed).
+    #[inline]
+    pub fn column_revision(&self, x: usize) -> u64 {
+        self.revisions.get(x).copied().unwrap_or(0)
+    }
+
+    /// Row of the topmost solid grain in grain column `x`; None when the column is empty. Kept
+    /// up to date as grains move, so it costs no scan.
+    #[inline]
+    pub fn column_top(&self, x: usize) -> Option<usize> {
+        let (_, gh) = self.grain_dims();
+        self.tops.get(x).copied().filter(|&y| y < gh)
+    }
+
+    /// Stack height of grain column `x` in grains: from the floor to its topmost solid grain.
+    #[inline]
+    pub fn column_height(&self, x: usize) -> usize {
+        let (_, gh) = self.grain_dims();
+        self.column_top(x).map_or(0, |y| gh - y)
+    }
+
+    /// Rows were pushed in at the bottom: every column changed.
+    fn rows_shifted(&mut self) {
+        self.revisions.fill(next_revision());
+        let (gw, gh) = self.grain_dims();
+        for x in 0..gw {
+            self.tops[x] = (0..gh).find(|&y| self.rows[y][x].is_solid()).unwrap_or(gh);
+        }
+    }
+
     #[inline]
     pub fn get(&self, x: usize, y: usize) -> Option<Cell> {
         let (gw, gh) = self.grain_dims();
@@ -192,6 +779,20 @@ impl Playfield {
             if let Some(row) = self.rows.get_mut(y) {
                 row[x] = cell;
             }
+            self.revisions[x] = next_revision();
+            if cell.is_solid() {
+                self.tops[x] = self.tops[x].min(y);
+            } else if self.tops[x] == y {
+                // The top grain left: the next one down is the new top
+                self.tops[x] = (y + 1..gh)
+                    .find(|&below| self.rows[below][x].is_solid())
+                    .unwrap_or(gh);
+            }
+            if cell.is_solid() {
+                if let Some(row) = self.moved_at.as_mut().and_then(|rows| rows.get_mut(y)) {
+                    row[x] = Instant::now();
+                }
+            }
         }
     }
 
@@ -201,10 +802,20 @@ impl Playfield {
         let origins = piece.cell_grain_origins();
         let (gw, gh) = self.grain_dims();
 
+        // Co-op: the other falling piece is solid too
+        let s = GRAIN_SCALE as i32;
+        if origins.iter().any(|&(x, y)| {
+            self.blocked
+                .iter()
+                .any(|&(bx, by)| (x - bx).abs() < s && (y - by).abs() < s)
+        }) {
+            return false;
+        }
+
         for (gx_origin, gy_origin) in origins {
             for dy in 0..GRAIN_SCALE as i32 {
                 for dx in 0..GRAIN_SCALE as i32 {
-                    let gx = gx_origin + dx;
+                    let gx = self.wrap_x(gx_origin + dx);
                     let gy = gy_origin + dy;
 
                     // Boundary check
@@ -216,7 +827,7 @@ impl Playfield {
                     }
 
                     // Collision check with SAND
-                    if let Some(Cell::Sand(..)) = self.get(gx as usize, gy as usize) {
+                    if self.get(gx as usize, gy as usize).is_some_and(Cell::is_solid) {
                         return false;
                     }
                 }
@@ -235,7 +846,7 @@ impl Playfield {
         for (gx_origin, gy_origin) in origins {
             for dy in 0..GRAIN_SCALE as i32 {
                 for dx in 0..GRAIN_SCALE as i32 {
-                    let gx = gx_origin + dx;
+                    let gx = self.wrap_x(gx_origin + dx);
                     let gy = gy_origin + dy;
                     if gy >= 0 {
                          for fg in frozen_grains {
@@ -251,6 +862,8 @@ impl Playfield {
     }
 
     /// Edge-to-edge clear: one colour connects left (x=0) to right (x=width-1); path can be slanted (8-neighbour).
+    /// On a cylinder there are no edges: the path must go all the way round, through every column.
+    /// Rainbow grains join any colour's path, but a path needs at least one real grain of that colour.
     /// Returns (number of such clears, list of (x,y) cells to clear).
     pub fn find_spanning_components(&self) -> (u32, Vec<(usize, usize)>) {
         const NEIGHBOURS_8: [(i16, i16); 8] = [
@@ -270,26 +883,28 @@ impl Playfield {
         for color in 0..6u8 {
             let mut visited = HashSet::new();
             for start_y in 0..gh {
-                if let Some(Cell::Sand(c, _)) = self.get(0, start_y) {
-                    if c == color && !visited.contains(&(0, start_y)) {
+                if let Some(start) = self.get(0, start_y) {
+                    if start.matches_color(color) && !visited.contains(&(0, start_y)) {
                         let mut component = Vec::new();
                         let mut stack = vec![(0, start_y)];
                         visited.insert((0, start_y));
-                        let mut touches_right = false;
+                        let mut columns = vec![false; gw];
+                        let mut has_color = false;
 
                         while let Some((x, y)) = stack.pop() {
                             component.push((x, y));
-                            if x == gw - 1 {
-                                touches_right = true;
+                            columns[x] = true;
+                            if matches!(self.get(x, y), Some(Cell::Sand(..))) {
+                                has_color = true;
                             }
 
                             for (dx, dy) in NEIGHBOURS_8 {
-                                let nx = x as i16 + dx;
+                                let nx = self.wrap_x(i32::from(x as i16 + dx));
                                 let ny = y as i16 + dy;
-                                if nx >= 0 && nx < gw as i16 && ny >= 0 && ny < gh as i16 {
+                                if nx >= 0 && nx < gw as i32 && ny >= 0 && ny < gh as i16 {
                                     let (nx, ny) = (nx as usize, ny as usize);
-                                    if let Some(Cell::Sand(c2, _)) = self.get(nx, ny) {
-                                        if c2 == color && !visited.contains(&(nx, ny)) {
+                                    if let Some(c2) = self.get(nx, ny) {
+                                        if c2.matches_color(color) && !visited.contains(&(nx, ny)) {
                                             visited.insert((nx, ny));
                                             stack.push((nx, ny));
                                         }
@@ -298,7 +913,13 @@ impl Playfield {
                             }
                         }
 
-                        if touches_right {
+                        // Flat: touching the right edge; a cylinder: every column
+                        let spans = if self.wrap {
+                            columns.iter().all(|&c| c)
+                        } else {
+                            columns[gw - 1]
+                        };
+                        if spans && has_color {
                             num_clears += 1;
                             all_to_clear.extend(component);
                         }
@@ -310,57 +931,293 @@ impl Playfield {
     }
 
     /// Unified physics step: gravity + cascading.
-    /// Grains fall down, or down-left/down-right if blocked.
-    pub fn tick_physics(&mut self, left_first: bool) -> bool {
+    /// Grains fall down, or down-left/down-right if blocked; `params` adds lag, sideways drift
+    /// and diagonal reluctance (stone ignores all three and only falls straight).
+    pub fn tick_physics(&mut self, left_first: bool, params: &PhysicsParams, rng: &mut Rng) -> bool {
         self.tick_count = self.tick_count.wrapping_add(1);
         let mut moved = false;
         let (gw, gh) = self.grain_dims();
         // Scan Entropy: Randomize x_order every frame to eliminate clumping bias.
         let mut x_order: Vec<usize> = (0..gw).collect();
-        // Uses tick_count for dynamic shuffle
-        let seed = self.tick_count.wrapping_mul(31).wrapping_add(gw as u32);
-        // Simple swap-based shuffle
-        for i in 0..gw / 4 {
-            let j = (seed as usize + i) % gw;
-            let k = (seed as usize * 17 + i) % gw;
-            x_order.swap(j, k);
+        if !params.steady {
+            // Uses tick_count for dynamic shuffle
+            let seed = self.tick_count.wrapping_mul(31).wrapping_add(gw as u32);
+            // Simple swap-based shuffle
+            for i in 0..gw / 4 {
+                let j = (seed as usize + i) % gw;
+                let k = (seed as usize * 17 + i) % gw;
+                x_order.swap(j, k);
+            }
         }
+        let mut chance = |p: f32| {
+            if params.steady {
+                p >= 0.5
+            } else {
+                p >= 1.0 || (p > 0.0 && rng.f32() < p)
+            }
+        };
 
         let limit_y = gh.saturating_sub(1);
         for y in (0..limit_y).rev() {
             for &x in &x_order {
-                if let Some(Cell::Sand(c, is_shadow)) = self.get(x, y) {
+                if let Some(grain) = self.get(x, y).filter(|c| c.is_solid()) {
+                    let is_stone = matches!(grain, Cell::Stone(_));
+                    if !is_stone && chance(params.lag) {
+                        continue;
+                    }
                     // 1. Try straight down
                     if self.get(x, y + 1) == Some(Cell::Empty) {
                         self.set(x, y, Cell::Empty);
-                        self.set(x, y + 1, Cell::Sand(c, is_shadow));
+                        self.set(x, y + 1, grain);
                         moved = true;
                     }
-                    // 2. Cascading: try down-left or down-right only when blocked below
-                    else {
-                        let try_left = x > 0 && self.get(x - 1, y + 1) == Some(Cell::Empty);
-                        let try_right = x + 1 < gw && self.get(x + 1, y + 1) == Some(Cell::Empty);
+                    // 2. Cascading: try down-left or down-right only when blocked below (not stone)
+                    // 3. Drift: fully blocked grains flow sideways toward a nearby drop
+                    else if !is_stone
+                        && ((chance(params.diagonal)
+                            && self.slide_diagonal(x, y, grain, left_first))
+                            || (chance(params.drift)
+                                && self.drift_sideways(x, y, grain, left_first)))
+                    {
+                        moved = true;
+                    }
+                }
+            }
+        }
+        moved
+    }
 
-                        let go_left = if try_left && try_right {
-                            left_first
-                        } else {
-                            try_left
-                        };
+    /// Move the grain at (x, y) down-left or down-right if either is empty. Returns true if it moved.
+    fn slide_diagonal(&mut self, x: usize, y: usize, grain: Cell, left_first: bool) -> bool {
+        let open = |dir| {
+            self.beside(x, dir)
+                .filter(|&nx| self.get(nx, y + 1) == Some(Cell::Empty))
+        };
+        let (left, right) = (open(-1), open(1));
 
-                        if go_left {
-                            self.set(x, y, Cell::Empty);
-                            self.set(x - 1, y + 1, Cell::Sand(c, is_shadow));
-                            moved = true;
-                        } else if try_right {
-                            self.set(x, y, Cell::Empty);
-                            self.set(x + 1, y + 1, Cell::Sand(c, is_shadow));
-                            moved = true;
-                        }
+        let target = if left.is_some() && right.is_some() {
+            if left_first { left } else { right }
+        } else {
+            left.or(right)
+        };
+
+        if let Some(nx) = target {
+            self.set(x, y, Cell::Empty);
+            self.set(nx, y + 1, grain);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Move the grain one step sideways if, within one block cell along an empty run of its row,
+    /// there is a gap it could fall into. Flat surfaces stay still (no endless jitter).
+    fn drift_sideways(&mut self, x: usize, y: usize, grain: Cell, left_first: bool) -> bool {
+        let dirs: [i32; 2] = if left_first { [-1, 1] } else { [1, -1] };
+        for dir in dirs {
+            let Some(tx) = self.beside(x, dir) else {
+                continue;
+            };
+            let mut nx = tx;
+            for _ in 0..GRAIN_SCALE {
+                if self.get(nx, y) != Some(Cell::Empty) {
+                    break;
+                }
+                if self.get(nx, y + 1) == Some(Cell::Empty) {
+                    self.set(x, y, Cell::Empty);
+                    self.set(tx, y, grain);
+                    return true;
+                }
+                match self.beside(nx, dir) {
+                    Some(next) => nx = next,
+                    None => break,
+                }
+            }
+        }
+        false
+    }
+
+    /// True if the grain at (x, y) cannot fall straight or diagonally.
+    fn is_resting(&self, x: usize, y: usize) -> bool {
+        let blocked = |x: usize, y: usize| self.get(x, y).is_none_or(Cell::is_solid);
+        blocked(x, y + 1)
+            && self.beside(x, -1).is_none_or(|nx| blocked(nx, y + 1))
+            && self.beside(x, 1).is_none_or(|nx| blocked(nx, y + 1))
+    }
+
+    /// Settle colour converters: each resting converter recolours the 8-connected component of a
+    /// different colour next to it (up to `budget` grains in total) and then turns into plain sand.
+    /// Returns the number of grains recoloured.
+    pub fn settle_converters(&mut self, budget: &mut u32) -> u32 {
+        let (gw, gh) = self.grain_dims();
+        let mut converted = 0;
+        for y in (0..gh).rev() {
+            for x in 0..gw {
+                let Some(Cell::Converter(color, is_shadow)) = self.get(x, y) else {
+                    continue;
+                };
+                if !self.is_resting(x, y) {
+                    continue;
+                }
+                self.set(x, y, Cell::Sand(color, is_shadow));
+                converted += self.flood_recolor(x, y, color, budget);
+            }
+        }
+        converted
+    }
+
+    /// Recolour the first differently coloured sand component touching (x, y) to `color`.
+    fn flood_recolor(&mut self, x: usize, y: usize, color: u8, budget: &mut u32) -> u32 {
+        const NEIGHBOURS_8: [(i16, i16); 8] = [
+            (-1, -1),
+            (-1, 0),
+            (-1, 1),
+            (0, -1),
+            (0, 1),
+            (1, -1),
+            (1, 0),
+            (1, 1),
+        ];
+        let (gw, gh) = self.grain_dims();
+        let neighbours = |x: usize, y: usize| {
+            NEIGHBOURS_8.iter().filter_map(move |&(dx, dy)| {
+                let nx = x as i16 + dx;
+                let ny = y as i16 + dy;
+                (nx >= 0 && nx < gw as i16 && ny >= 0 && ny < gh as i16)
+                    .then_some((nx as usize, ny as usize))
+            })
+        };
+        let target = neighbours(x, y).find_map(|(nx, ny)| match self.get(nx, ny) {
+            Some(Cell::Sand(c, _)) if c != color => Some(c),
+            _ => None,
+        });
+        let Some(target) = target else {
+            return 0;
+        };
+        let mut converted = 0;
+        let mut frontier: VecDeque<(usize, usize)> = neighbours(x, y).collect();
+        while *budget > 0 {
+            let Some((cx, cy)) = frontier.pop_front() else {
+                break;
+            };
+            if let Some(Cell::Sand(c, is_shadow)) = self.get(cx, cy) {
+                if c == target {
+                    self.set(cx, cy, Cell::Sand(color, is_shadow));
+                    *budget -= 1;
+                    converted += 1;
+                    frontier.extend(neighbours(cx, cy));
+                }
+            }
+        }
+        converted
+    }
+
+    /// Fill the bottom `rows` block rows with stone (dig mode). Capped so the spawn zone stays clear.
+    pub fn fill_stone_rows(&mut self, rows: usize) {
+        let (gw, gh) = self.grain_dims();
+        let start_y = gh - rows.min(self.max_fill_rows()) * GRAIN_SCALE;
+        for y in start_y..gh {
+            for x in 0..gw {
+                let is_shadow = (y + 1) % GRAIN_SCALE == 0 || (x + 1) % GRAIN_SCALE == 0;
+                self.set(x, y, Cell::Stone(is_shadow));
+            }
+        }
+    }
+
+    /// Block rows that can be filled at game start, leaving the spawn zone clear.
+    fn max_fill_rows(&self) -> usize {
+        self.height
+            .saturating_sub(SPAWN_ZONE_ROWS / GRAIN_SCALE + 2)
+    }
+
+    /// Lay one block row of sand on top of the bottom `below` block rows, block column `x` in
+    /// colour `colors[x]` (start garbage). Does nothing past `max_fill_rows`.
+    pub fn fill_sand_row(&mut self, below: usize, colors: &[u8]) {
+        let (gw, gh) = self.grain_dims();
+        if below >= self.max_fill_rows() {
+            return;
+        }
+        let top = gh - (below + 1) * GRAIN_SCALE;
+        for y in top..top + GRAIN_SCALE {
+            for x in 0..gw {
+                let is_shadow = (y + 1) % GRAIN_SCALE == 0 || (x + 1) % GRAIN_SCALE == 0;
+                let color = colors.get(x / GRAIN_SCALE).copied().unwrap_or(0);
+                self.set(x, y, Cell::Sand(color, is_shadow));
+            }
+        }
+    }
+
+    /// Zone: push `rows` banked grain rows in at the bottom, lifting everything above (the top
+    /// rows drop off). Capped so the banked rows stay out of the spawn zone. Returns how many
+    /// were added.
+    pub fn bank_rows(&mut self, rows: usize) -> usize {
+        let (gw, gh) = self.grain_dims();
+        let rows = rows.min(gh.saturating_sub(SPAWN_ZONE_ROWS + self.banked_rows));
+        let now = Instant::now();
+        for _ in 0..rows {
+            self.rows.pop_front();
+            self.rows.push_back(vec![Cell::Stone(false); gw]);
+            if let Some(moved_at) = self.moved_at.as_mut() {
+                moved_at.pop_front();
+                moved_at.push_back(vec![now; gw]);
+            }
+        }
+        self.banked_rows += rows;
+        self.rows_shifted();
+        rows
+    }
+
+    /// Zone over: the banked rows become ordinary stone, ready to clear. Returns how many there
+    /// were.
+    pub const fn unbank_rows(&mut self) -> usize {
+        let rows = self.banked_rows;
+        self.banked_rows = 0;
+        rows
+    }
+
+    /// Co-op tide: push one block row of sand in at the bottom, block column `x` in colour
+    /// `colors[x]`, lifting everything above (the top row drops off).
+    pub fn raise_garbage(&mut self, colors: &[u8]) {
+        let (gw, _) = self.grain_dims();
+        let now = Instant::now();
+        for dy in 0..GRAIN_SCALE {
+            let row = (0..gw)
+                .map(|x| {
+                    let is_shadow = dy == GRAIN_SCALE - 1 || (x + 1) % GRAIN_SCALE == 0;
+                    Cell::Sand(colors.get(x / GRAIN_SCALE).copied().unwrap_or(0), is_shadow)
+                })
+                .collect();
+            self.rows.pop_front();
+            self.rows.push_back(row);
+            if let Some(moved_at) = self.moved_at.as_mut() {
+                moved_at.pop_front();
+                moved_at.push_back(vec![now; gw]);
+            }
+        }
+        self.rows_shifted();
+    }
+
+    /// Stone grains within `STONE_BREAK_RADIUS` of any of `cleared` (banked rows never break).
+    pub fn stones_near(&self, cleared: &HashSet<(usize, usize)>) -> HashSet<(usize, usize)> {
+        let (gw, gh) = self.grain_dims();
+        let gh = gh - self.banked_rows;
+        let mut stones = HashSet::new();
+        for &(x, y) in cleared {
+            for dy in -STONE_BREAK_RADIUS..=STONE_BREAK_RADIUS {
+                for dx in -STONE_BREAK_RADIUS..=STONE_BREAK_RADIUS {
+                    let nx = x as i32 + dx;
+                    let ny = y as i32 + dy;
+                    if nx < 0 || ny < 0 || nx >= gw as i32 || ny >= gh as i32 {
+                        continue;
+                    }
+                    let (nx, ny) = (nx as usize, ny as usize);
+                    if matches!(self.get(nx, ny), Some(Cell::Stone(_))) {
+                        stones.insert((nx, ny));
                     }
                 }
             }
         }
-        moved
+        stones
     }
 
     /// Game over if any sand in spawn zone (top SPAWN_ZONE_ROWS).
@@ -368,54 +1225,199 @@ impl Playfield {
         self.topmost_sand_y().map_or(false, |y| y < SPAWN_ZONE_ROWS)
     }
 
-    /// Minimum (topmost) row index that contains any sand. None if playfield has no sand.
-    pub fn topmost_sand_y(&self) -> Option<usize> {
-        let (gw, gh) = self.grain_dims();
-        for y in 0..gh {
-            for x in 0..gw {
-                if matches!(self.get(x, y), Some(Cell::Sand(..))) {
-                    return Some(y);
+    /// The stack reaches the top quarter of the board below the spawn zone (accessibility cue).
+    pub fn in_danger_zone(&self) -> bool {
+        let (_, gh) = self.grain_dims();
+        self.topmost_sand_y()
+            .is_some_and(|y| y < SPAWN_ZONE_ROWS + gh / 4)
+    }
+
+    /// Number of occupied grains.
+    pub fn grain_count(&self) -> usize {
+        self.rows
+            .iter()
+            .map(|row| row.iter().filter(|c| c.is_solid()).count())
+            .sum()
+    }
+
+    /// Grains of each colour index `0..colors`, converters counted as their colour.
+    pub fn color_counts(&self, colors: u8) -> Vec<usize> {
+        let mut counts = vec![0; colors as usize];
+        for cell in self.rows.iter().flatten() {
+            if let Cell::Sand(c, _) | Cell::Converter(c, _) = *cell {
+                if let Some(count) = counts.get_mut(c as usize) {
+                    *count += 1;
                 }
             }
         }
-        None
+        counts
+    }
+
+    /// Minimum (topmost) row index that contains any sand. None if playfield has no sand.
+    pub fn topmost_sand_y(&self) -> Option<usize> {
+        let (_, gh) = self.grain_dims();
+        self.tops.iter().copied().min().filter(|&y| y < gh)
     }
 }
 
-/// Bag of 7 tetrominoes (random order, then refill).
+/// `--anti-drought`: the most pieces in a row a kind can go without being dealt.
+pub const DROUGHT_LIMIT: u32 = 12;
+
+/// Bag of the piece set's kinds (random order, then refill).
 #[derive(Debug, Clone)]
 pub struct Bag {
-    queue: Vec<TetrominoKind>,
+    queue: Vec<PieceKind>,
+    kinds: Vec<PieceKind>,
     rng: Rng,
+    /// Pieces of each bag still in `queue`, oldest first. Skipped S and Z pieces and
+    /// anti-drought picks from further up leave bags short, so these are counted, not derived.
+    left: Vec<usize>,
+    /// Pieces dealt since the oldest bag in `queue` started.
+    dealt: usize,
+    /// `--anti-drought`: pieces dealt since each kind (same order as `kinds`) last came up.
+    droughts: Option<Vec<u32>>,
 }
 
 impl Bag {
-    pub fn new(seed: u64) -> Self {
+    pub fn new(seed: u64, set: crate::PieceSet) -> Self {
+        let kinds = set.kinds();
         let mut b = Self {
-            queue: Vec::with_capacity(14),
+            queue: Vec::with_capacity(2 * kinds.len()),
+            kinds,
             rng: Rng::with_seed(seed),
+            left: Vec::with_capacity(2),
+            dealt: 0,
+            droughts: None,
         };
         b.refill();
         b
     }
 
     fn refill(&mut self) {
-        let mut all = TetrominoKind::ALL.to_vec();
+        let mut all = self.kinds.clone();
         self.rng.shuffle(&mut all);
+        self.left.push(all.len());
         self.queue.extend(all);
     }
 
-    pub fn next(&mut self) -> TetrominoKind {
+    pub fn next(&mut self) -> PieceKind {
         if self.queue.len() < 2 {
             self.refill();
         }
-        self.queue.remove(0)
+        // Anti-drought: a kind at the limit is dealt now, taken from further up the queue
+        let index = match self.longest_drought() {
+            Some((kind, n)) if n >= DROUGHT_LIMIT => loop {
+                if let Some(i) = self.queue.iter().position(|&k| k == kind) {
+                    break i;
+                }
+                self.refill();
+            },
+            _ => 0,
+        };
+        let kind = self.queue.remove(index);
+        self.count_dealt(index);
+        if let Some(droughts) = &mut self.droughts {
+            for (&k, n) in self.kinds.iter().zip(droughts.iter_mut()) {
+                *n = if k == kind { 0 } else { *n + 1 };
+            }
+        }
+        kind
+    }
+
+    /// Book the piece dealt from `index` of the queue against the bag it came from.
+    fn count_dealt(&mut self, index: usize) {
+        let mut start = 0;
+        for left in &mut self.left {
+            if index < start + *left {
+                *left -= 1;
+                break;
+            }
+            start += *left;
+        }
+        self.dealt += 1;
+        if self.left.first() == Some(&0) {
+            self.left.remove(0);
+            self.dealt = 0;
+        }
+    }
+
+    /// `--start-clean-streak`: take S and Z out of the first bag. Call before dealing.
+    pub fn skip_first_s_z(&mut self) {
+        let first = self.left[0];
+        let kept: Vec<PieceKind> = self
+            .queue
+            .drain(..first)
+            .filter(|&kind| !matches!(kind, PieceKind::S | PieceKind::Z))
+            .collect();
+        self.left[0] = kept.len();
+        self.queue.splice(..0, kept);
+    }
+
+    /// Turn on the anti-drought rule (see `DROUGHT_LIMIT`).
+    pub fn track_droughts(&mut self) {
+        self.droughts = Some(vec![0; self.kinds.len()]);
+    }
+
+    /// Anti-drought: the kind that has gone longest without being dealt, and for how many pieces.
+    pub fn longest_drought(&self) -> Option<(PieceKind, u32)> {
+        let droughts = self.droughts.as_ref()?;
+        self.kinds
+            .iter()
+            .copied()
+            .zip(droughts.iter().copied())
+            .max_by_key(|&(_, n)| n)
+    }
+
+    /// Kinds in one bag.
+    pub fn kinds(&self) -> &[PieceKind] {
+        &self.kinds
+    }
+
+    /// Kinds queued to be dealt, next first (the bag refills when one is left).
+    pub fn queue(&self) -> &[PieceKind] {
+        &self.queue
+    }
+
+    /// The bag's RNG state (for state dumps).
+    pub fn rng_seed(&self) -> u64 {
+        self.rng.get_seed()
+    }
+
+    /// Pieces of each bag still queued, oldest first, and pieces dealt from the oldest (for
+    /// state dumps).
+    pub fn progress(&self) -> (&[usize], usize) {
+        (&self.left, self.dealt)
+    }
+
+    /// Back to a dumped queue, bag progress and RNG state (`--load-state`). Dumps from before
+    /// bag progress was saved have no `left`; their queue is taken as whole bags after a
+    /// part-dealt one.
+    pub fn restore(&mut self, queue: &[PieceKind], left: &[usize], dealt: usize, rng_seed: u64) {
+        self.queue = queue.to_vec();
+        self.rng = Rng::with_seed(rng_seed);
+        let n = self.kinds.len();
+        if left.iter().sum::<usize>() == queue.len() && !left.is_empty() {
+            self.left = left.to_vec();
+            self.dealt = dealt;
+        } else {
+            let part = queue.len() % n;
+            self.left = std::iter::once(part)
+                .filter(|&part| part > 0)
+                .chain(std::iter::repeat_n(n, queue.len() / n))
+                .collect();
+            self.dealt = if part > 0 { n - part } else { 0 };
+        }
+    }
+
+    /// Pieces dealt from the bag being dealt now (0 when the last one dealt finished a bag).
+    pub const fn dealt_from_current(&self) -> usize {
+        self.dealt
     }
 }
 
 impl Default for Bag {
     fn default() -> Self {
-        Self::new(0x1234_5678)
+        Self::new(0x1234_5678, crate::PieceSet::default())
     }
 }
 
@@ -426,7 +1428,82 @@ pub struct ScorePopup {
     pub amount: u32,
     pub multiplier: u32,
     pub age_ms: u32,
+    /// The colour that scored most (yellow without sand; bombs and the zone have their own).
     pub color: Color,
+    /// Clears: points per cleared colour index, largest first. Empty for bombs and the zone.
+    pub breakdown: Vec<(u8, u32)>,
+}
+
+/// A chain that just ended, shown as a banner over the board.
+#[derive(Debug, Clone, Copy)]
+pub struct ComboEnd {
+    /// Highest multiplier the chain reached.
+    pub peak: u32,
+    pub bonus: u32,
+    pub age_ms: u32,
+}
+
+/// Something that happened in the game, for effects outside the simulation (sound).
+/// Collected on `GameState` and drained by the app once per frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameEvent {
+    /// A new piece entered the playfield.
+    Spawn(PieceKind),
+    Move,
+    Rotate,
+    Lock,
+    Clear,
+    /// Combo multiplier reached (x2 and up).
+    Combo(u32),
+    /// A chain's multiplier ran out after peaking at `peak`, for a bonus.
+    ComboEnd { peak: u32, bonus: u32 },
+    /// New level.
+    LevelUp(u32),
+    /// Raised by the app, which decides when a game ends (e.g. timed mode).
+    GameOver,
+    /// Endless: a breather started (all-clear or every `BREATHER_LEVELS` levels).
+    Breather,
+    /// `--missions`: an objective was completed, for a bonus.
+    Mission { bonus: u32 },
+}
+
+/// Co-op: the other player's falling piece and its timing. `swap_seats` trades these with the
+/// state's own fields, so every piece method works on whichever player is acting.
+#[derive(Debug, Clone)]
+pub struct Seat {
+    pub piece: Option<Piece>,
+    lock_delay_started: Option<Instant>,
+    lock_delay_resets: u32,
+    spawn_ready_at: Option<Instant>,
+    visual_gx: f32,
+    visual_gy: f32,
+    spawn_column: Option<i32>,
+}
+
+/// `--dual`: the board the falling piece isn't on. `swap_boards` trades it with the state's own
+/// board as each piece spawns, so every board method works on the piece's board.
+#[derive(Debug, Clone)]
+pub struct IdleBoard {
+    pub playfield: Playfield,
+    pub frozen_grains: Vec<FrozenGrain>,
+    crumble_delay_ticks: u32,
+    pub popups: Vec<ScorePopup>,
+    /// Drawn on the left: the piece is on the right-hand board.
+    pub left: bool,
+}
+
+impl IdleBoard {
+    /// Zone over: the rows banked on this board clear at once. Returns how many there were.
+    fn release_bank(&mut self) -> usize {
+        let rows = self.playfield.unbank_rows();
+        let (gw, gh) = self.playfield.grain_dims();
+        for y in gh - rows..gh {
+            for x in 0..gw {
+                self.playfield.set(x, y, Cell::Empty);
+            }
+        }
+        rows
+    }
 }
 
 /// Game state: playfield, current piece, next piece, score, level, etc.
@@ -435,7 +1512,7 @@ pub struct GameState {
     pub theme: Theme,
     pub playfield: Playfield,
     pub piece: Option<Piece>,
-    pub next_pieces: Vec<(TetrominoKind, u8)>,
+    pub next_pieces: Vec<NextPiece>,
     pub bag: Bag,
     pub score: u32,
     pub level: u32,
@@ -444,6 +1521,8 @@ pub struct GameState {
     /// Cells to clear (animation); when empty and not in_progress, we clear + gravity.
     pub line_clear_cells: Vec<(usize, usize)>,
     pub line_clear_in_progress: bool,
+    /// The cells in `line_clear_cells` come from a bomb blast (different animation, no line credit).
+    pub line_clear_blast: bool,
     /// When piece first landed (can't move down); lock after lock_delay_ms if not reset.
     lock_delay_started: Option<Instant>,
     /// Number of move/rotate resets since last land; cap at LOCK_DELAY_RESET_LIMIT.
@@ -452,36 +1531,157 @@ pub struct GameState {
     spawn_ready_at: Option<Instant>,
     /// Spawn delay in ms (0 = disabled).
     spawn_delay_ms: u64,
-    /// High-color mode: if true, uses 6 colors; otherwise 4.
-    pub high_color: bool,
+    /// Time a landed piece may rest before locking (0 = lock on contact).
+    lock_delay_ms: u64,
+    /// Points a reroll costs.
+    reroll_cost: u32,
+    /// Rerolls used this game (sidebar).
+    pub rerolls: u32,
+    /// 20G: the piece always sits on the stack (spawns resting, falls instantly after every move).
+    pub twenty_g: bool,
+    /// Invisible sand: settled grains fade out shortly after they stop moving.
+    pub invisible: bool,
+    /// `--mirror`: pieces and spawn positions are flipped left to right.
+    pub mirror: bool,
+    /// Colour assignment (number of colours in play).
+    pub colors: ColorScheme,
+    /// Two-tone mode: the cells of a piece can carry different colours.
+    pub two_tone: bool,
+    /// `--balance-colors`: main colours still to deal this round (see `balanced_color`).
+    color_bag: Option<Vec<u8>>,
+    /// How queued pieces get their main colour (`--color-assign`).
+    color_assign: crate::ColorAssign,
+    /// Rainbow mode: pieces occasionally carry a wildcard cell; combos award them too.
+    pub rainbow: bool,
+    /// Bomb mode: bomb pieces occasionally replace a bag piece; combos award them too.
+    pub bombs: bool,
+    /// Converter mode: large combos award a colour-converter cell.
+    pub converters: bool,
+    /// Grains the converters currently in play may still recolour.
+    converter_budget: u32,
+    /// Sand flow parameters (`--sand-type`).
+    pub physics: PhysicsParams,
     /// Settle direction bias toggle.
     settle_left_first: bool,
-    pub difficulty: crate::Difficulty,
+    /// Difficulty preset this game was started with.
+    pub profile: crate::difficulty::DifficultyProfile,
     pub popups: Vec<ScorePopup>,
     pub frozen_grains: Vec<FrozenGrain>,
     pub clears: u32,
     pub crumble_delay_ticks: u32,
     pub combo_multiplier: u32,
     pub combo_timer_ticks: u32,
+    /// Highest multiplier of the running chain.
+    combo_peak: u32,
+    /// Clears caused by the last placed piece so far: the first raises the combo, the ones
+    /// that cascade after it (while it crumbles and settles) score an escalating chain bonus.
+    placement_clears: u32,
+    /// The last chain to end, while its banner shows.
+    pub combo_end: Option<ComboEnd>,
+    /// Points and spans of the latest clear (0 points when banked in the zone).
+    pub last_clear: (u32, u32),
+    /// Sand colours in the latest clear's spanning bridge (none for a zone release).
+    pub last_clear_colors: Vec<u8>,
+    /// Pieces spawned this game, per kind (sidebar).
+    pub spawned: BTreeMap<PieceKind, u32>,
+    /// Piece cells locked this game, per block column (game over heatmap).
+    pub lock_columns: Vec<u32>,
+    /// A level-up asked for an obstacle (`DifficultyProfile::obstacle_levels`); it lands before the next piece spawns.
+    obstacle_due: bool,
+    /// Zone meter, 0..=`ZONE_MAX`, filled by clears.
+    pub zone: u32,
+    /// Sand ticks of zone left; 0 when not in the zone.
+    pub zone_ticks: u32,
+    /// Clears banked this zone (the score multiplier).
+    pub zone_clears: u32,
+    /// Grains banked this zone.
+    zone_grains: u32,
+    /// Focus meter, 0..=`FOCUS_MAX`, filled by clears.
+    pub focus: u32,
+    /// Sand ticks of slow motion left; 0 when not focused.
+    pub focus_ticks: u32,
+    /// Endless: all-clears and every `BREATHER_LEVELS` levels start a breather.
+    pub breathers: bool,
+    /// Sand ticks of breather left (faster sand, double points); 0 when not in one.
+    pub breather_ticks: u32,
+    /// Active combo tuning (window length, cap, decay); the UI reads the window length from here.
+    pub combo: crate::ComboConfig,
+    /// Points per grain and drop, combo step and lines per level.
+    scoring: crate::ScoringConfig,
+    /// Co-op: the second player's piece (the board is shared, the queue too).
+    pub partner: Option<Seat>,
+    /// `--dual`: the other board (the queue, score and goal are shared).
+    pub idle_board: Option<IdleBoard>,
+    /// Block column new pieces spawn at; None = centred.
+    spawn_column: Option<i32>,
+    /// Co-op: sand ticks since the tide last rose.
+    tide_ticks: u32,
+    /// Co-op: block rows the tide has risen this game.
+    pub tide_rows: u32,
+    /// Custom mode garbage: the tide rises every this many sand ticks, co-op or not; 0 = off.
+    garbage_ticks: u32,
+    /// `--debug-invariants`: count grains around every sand tick, lock and clear.
+    debug_invariants: bool,
+    /// `--debug-invariants`: the board as the clear in progress started, and the grains it takes.
+    clear_check: Option<(GrainCheck, usize)>,
     /// Visual position (grain coords) for smooth sliding; interpolates toward piece.gx/gy each frame.
     piece_visual_gx: f32,
     piece_visual_gy: f32,
     pub last_spawn_color: Option<u8>,
+    /// Seed the game started from (replaying it deals the same pieces).
+    pub seed: u64,
     pub rng: Rng,
+    /// Sand's random rolls (`--sand-type` lag, drift and diagonals), kept apart from `rng` so a
+    /// same-seed retry deals the same colours however the sand falls.
+    pub physics_rng: Rng,
+    /// Events since the last `drain_events`.
+    events: Vec<GameEvent>,
 }
 
 impl GameState {
     pub fn new(theme: Theme, width: u16, height: u16, config: &crate::GameConfig) -> Self {
-        let seed = fastrand::u64(..);
+        let width = Self::board_width(width, config.dual);
+        let seed = config.seed.unwrap_or_else(|| fastrand::u64(..));
         let mut rng = Rng::with_seed(seed);
-        let mut bag = Bag::new(rng.u64(..));
+        let profile = *config.profile();
+        let pieces = config.pieces.unwrap_or(profile.pieces);
+        let mut bag = Bag::new(rng.u64(..), pieces);
+        let physics_rng = Rng::with_seed(rng.u64(..));
+        if config.anti_drought {
+            bag.track_droughts();
+        }
+        if config.start_clean_streak() {
+            bag.skip_first_s_z();
+        }
         let p1 = bag.next();
-        let p2 = bag.next();
-        let p3 = bag.next();
-        let p4 = bag.next();
-
-        let c1 = p1.color_index(config.high_color);
-        let piece = Some(Self::spawn_piece(width, height, p1, c1));
+        let p2 = next_kind(&mut bag, &mut rng, config.bombs);
+        let p3 = next_kind(&mut bag, &mut rng, config.bombs);
+        let p4 = next_kind(&mut bag, &mut rng, config.bombs);
+
+        let colors = config.colors();
+        let playfield = new_board(&mut rng, width, height, config);
+        let c1 = assign_color(
+            &mut rng,
+            config.color_assign,
+            p1,
+            None,
+            profile.repeat_color_chance,
+            colors,
+            &playfield,
+        );
+        let cells1 = roll_cell_colors(&mut rng, p1, c1, config.two_tone, colors);
+        let piece = Some(Self::spawn_piece(
+            width,
+            height,
+            NextPiece {
+                kind: p1,
+                color_index: c1,
+                cell_colors: cells1,
+                rainbow_cell: None,
+                converter_cell: None,
+            },
+            config.mirror,
+        ));
 
         let (vx, vy) = piece
             .as_ref()
@@ -494,22 +1694,30 @@ impl GameState {
         let mut next_pieces = Vec::new();
         let mut last_c = c1;
         for kind in [p2, p3, p4] {
-            let repeat_chance = match config.difficulty {
-                crate::Difficulty::Easy => 0.40,
-                crate::Difficulty::Medium => 0.20,
-                crate::Difficulty::Hard => 0.08,
-            };
-            let mut nc = kind.color_index(config.high_color);
-            if rng.f32() < repeat_chance {
-                nc = last_c;
-            }
-            next_pieces.push((kind, nc));
+            let nc = assign_color(
+                &mut rng,
+                config.color_assign,
+                kind,
+                Some(last_c),
+                profile.repeat_color_chance,
+                colors,
+                &playfield,
+            );
+            let cell_colors = roll_cell_colors(&mut rng, kind, nc, config.two_tone, colors);
+            let rainbow_cell = roll_rainbow_cell(&mut rng, kind, config.rainbow);
+            next_pieces.push(NextPiece {
+                kind,
+                color_index: nc,
+                cell_colors,
+                rainbow_cell,
+                converter_cell: None,
+            });
             last_c = nc;
         }
 
-        Self {
+        let mut state = Self {
             theme,
-            playfield: Playfield::new(width, height),
+            playfield,
             piece,
             next_pieces,
             bag,
@@ -520,28 +1728,118 @@ impl GameState {
             game_over: false,
             line_clear_cells: Vec::new(),
             line_clear_in_progress: false,
+            line_clear_blast: false,
             lock_delay_started: None,
             lock_delay_resets: 0,
             spawn_ready_at,
             spawn_delay_ms: config.spawn_delay_ms,
-            high_color: config.high_color,
+            lock_delay_ms: config.lock_delay_ms,
+            reroll_cost: config.reroll_cost,
+            rerolls: 0,
+            twenty_g: config.twenty_g,
+            invisible: config.invisible,
+            mirror: config.mirror,
+            colors,
+            two_tone: config.two_tone,
+            color_bag: config.balance_colors.then(Vec::new),
+            color_assign: config.color_assign,
+            rainbow: config.rainbow,
+            bombs: config.bombs,
+            breathers: config.mode == crate::GameMode::Endless,
+            converters: config.converters,
+            converter_budget: 0,
+            physics: PhysicsParams {
+                steady: config.steady_sand,
+                ..PhysicsParams::preset(config.sand())
+            },
             settle_left_first: true,
-            difficulty: config.difficulty,
+            profile,
             popups: Vec::new(),
             frozen_grains: Vec::new(),
             clears: 0,
+            spawned: BTreeMap::new(),
+            lock_columns: vec![0; width as usize],
+            obstacle_due: false,
+            zone: 0,
+            zone_ticks: 0,
+            zone_clears: 0,
+            zone_grains: 0,
+            focus: 0,
+            focus_ticks: 0,
+            breather_ticks: 0,
             crumble_delay_ticks: 0,
             combo_multiplier: 1,
             combo_timer_ticks: 0,
+            combo_peak: 1,
+            placement_clears: 0,
+            combo_end: None,
+            last_clear: (0, 0),
+            last_clear_colors: Vec::new(),
+            combo: config.combo,
+            scoring: config.scoring,
+            partner: None,
+            idle_board: None,
+            spawn_column: None,
+            tide_ticks: 0,
+            tide_rows: 0,
+            garbage_ticks: config
+                .custom
+                .as_ref()
+                .map_or(0, |custom| custom.garbage_secs * SAND_TICKS_PER_SEC),
+            debug_invariants: config.debug_invariants,
+            clear_check: None,
             piece_visual_gx: vx,
             piece_visual_gy: vy,
             last_spawn_color: Some(c1),
+            seed,
             rng,
+            physics_rng,
+            events: Vec::new(),
+        };
+        if config.coop {
+            // Each player spawns over their own half of the board
+            let w = i32::from(width);
+            let left = (w / 4 - 1).max(0);
+            state.spawn_column = Some(left);
+            if let Some(piece) = state.piece.as_mut() {
+                piece.gx = piece.spawn_gx(left, w);
+            }
+            state.sync_piece_visual();
+            state.partner = Some(Seat {
+                piece: None,
+                lock_delay_started: None,
+                lock_delay_resets: 0,
+                spawn_ready_at: None,
+                visual_gx: 0.0,
+                visual_gy: 0.0,
+                spawn_column: Some((w * 3 / 4 - 1).max(0)),
+            });
+            state.swap_seats();
+            state.spawn_next();
+            state.swap_seats();
         }
+        if config.dual {
+            state.idle_board = Some(IdleBoard {
+                playfield: new_board(&mut state.rng, width, height, config),
+                frozen_grains: Vec::new(),
+                crumble_delay_ticks: 0,
+                popups: Vec::new(),
+                left: false,
+            });
+        }
+        if state.twenty_g {
+            state.sink_piece();
+            state.sync_piece_visual();
+        }
+        state
     }
 
     /// Call every frame to smooth-slide the piece visual toward its logic position.
     pub fn tick_piece_visual(&mut self, dt_secs: f32) {
+        self.both_seats(|state| state.slide_piece_visual(dt_secs));
+    }
+
+    fn slide_piece_visual(&mut self, dt_secs: f32) {
         if let Some(ref piece) = self.piece {
             let speed = 55.0; // fast catch-up: smooth slide, reaches target quickly
             let t = (dt_secs * speed).min(1.0);
@@ -551,7 +1849,7 @@ impl GameState {
     }
 
     /// Piece origins for drawing (uses smoothed visual position so the block slides instead of snapping).
-    pub fn piece_draw_origins(&self) -> Option<[(i32, i32); 4]> {
+    pub fn piece_draw_origins(&self) -> Option<Vec<(i32, i32)>> {
         self.piece.as_ref().map(|p| {
             p.cell_grain_origins_at(
                 self.piece_visual_gx.round() as i32,
@@ -560,35 +1858,236 @@ impl GameState {
         })
     }
 
+    /// Falling pieces with their draw origins: the active one, then (co-op) the partner's.
+    pub fn drawn_pieces(&self) -> Vec<(&Piece, Vec<(i32, i32)>)> {
+        let mut pieces: Vec<_> = self
+            .piece
+            .iter()
+            .zip(self.piece_draw_origins())
+            .collect();
+        if let Some(seat) = &self.partner {
+            if let Some(piece) = &seat.piece {
+                let origins = piece.cell_grain_origins_at(
+                    seat.visual_gx.round() as i32,
+                    seat.visual_gy.round() as i32,
+                );
+                pieces.push((piece, origins));
+            }
+        }
+        pieces
+    }
+
+    /// FNV-1a hash of the grain grid, frozen grains, falling pieces and RNG state. Two copies of
+    /// a game stepped identically hash the same, so comparing hashes from two runs (see the
+    /// state stream) shows the first tick where they diverged.
+    pub fn state_hash(&self) -> u64 {
+        const PRIME: u64 = 0x0100_0000_01b3;
+        let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+        let mut feed = |bytes: &[u8]| {
+            for &b in bytes {
+                hash ^= u64::from(b);
+                hash = hash.wrapping_mul(PRIME);
+            }
+        };
+        let idle = self.idle_board.as_ref().map(|board| &board.playfield);
+        for playfield in std::iter::once(&self.playfield).chain(idle) {
+            let (gw, gh) = playfield.grain_dims();
+            for y in 0..gh {
+                for x in 0..gw {
+                    feed(&match playfield.get(x, y).unwrap_or(Cell::Empty) {
+                        Cell::Empty => [0, 0],
+                        Cell::Sand(color, _) => [1, color],
+                        Cell::Rainbow(_) => [2, 0],
+                        Cell::Stone(_) => [3, 0],
+                        Cell::Converter(color, _) => [4, color],
+                    });
+                }
+            }
+        }
+        for fg in &self.frozen_grains {
+            feed(&(fg.x as u32).to_le_bytes());
+            feed(&(fg.y as u32).to_le_bytes());
+        }
+        let partner = self.partner.as_ref().and_then(|seat| seat.piece.as_ref());
+        for piece in self.piece.iter().chain(partner) {
+            feed(&piece.gx.to_le_bytes());
+            feed(&piece.gy.to_le_bytes());
+            feed(&[piece.kind as u8, piece.rotation]);
+            feed(&piece.cell_colors);
+        }
+        feed(&self.rng.get_seed().to_le_bytes());
+        feed(&self.physics_rng.get_seed().to_le_bytes());
+        hash
+    }
+
+    /// Timers and counters for a state dump (F10), as of `now`.
+    pub fn timers(&self, now: Instant) -> crate::dump::Timers {
+        crate::dump::Timers {
+            lock_delay_ms: self
+                .lock_delay_started
+                .map(|t| now.saturating_duration_since(t).as_millis() as u64),
+            lock_delay_resets: self.lock_delay_resets,
+            spawn_delay_ms: self
+                .spawn_ready_at
+                .map(|t| t.saturating_duration_since(now).as_millis() as u64),
+            crumble_delay_ticks: self.crumble_delay_ticks,
+            combo_multiplier: self.combo_multiplier,
+            combo_timer_ticks: self.combo_timer_ticks,
+            combo_peak: self.combo_peak,
+            placement_clears: self.placement_clears,
+            focus: self.focus,
+            focus_ticks: self.focus_ticks,
+            breather_ticks: self.breather_ticks,
+            zone: self.zone,
+            zone_ticks: self.zone_ticks,
+            tide_ticks: self.tide_ticks,
+            tide_rows: self.tide_rows,
+            converter_budget: self.converter_budget,
+            obstacle_due: self.obstacle_due,
+            settle_left_first: self.settle_left_first,
+        }
+    }
+
+    /// Back to dumped timers and counters (`--load-state`), as of `now`.
+    pub fn restore_timers(&mut self, timers: &crate::dump::Timers, now: Instant) {
+        let ms = std::time::Duration::from_millis;
+        self.lock_delay_started = timers
+            .lock_delay_ms
+            .map(|elapsed| now.checked_sub(ms(elapsed)).unwrap_or(now));
+        self.lock_delay_resets = timers.lock_delay_resets;
+        self.spawn_ready_at = timers.spawn_delay_ms.map(|left| now + ms(left));
+        self.crumble_delay_ticks = timers.crumble_delay_ticks;
+        self.combo_multiplier = timers.combo_multiplier;
+        self.combo_timer_ticks = timers.combo_timer_ticks;
+        self.combo_peak = timers.combo_peak;
+        self.placement_clears = timers.placement_clears;
+        self.focus = timers.focus;
+        self.focus_ticks = timers.focus_ticks;
+        self.breather_ticks = timers.breather_ticks;
+        self.zone = timers.zone;
+        self.zone_ticks = timers.zone_ticks;
+        self.tide_ticks = timers.tide_ticks;
+        self.tide_rows = timers.tide_rows;
+        self.converter_budget = timers.converter_budget;
+        self.obstacle_due = timers.obstacle_due;
+        self.settle_left_first = timers.settle_left_first;
+        self.sync_piece_visual();
+    }
+
+    /// Co-op: make the partner's piece the active one, or back. The other piece is solid to
+    /// `can_place` meanwhile. Does nothing outside co-op.
+    pub fn swap_seats(&mut self) {
+        let Some(seat) = self.partner.as_mut() else {
+            return;
+        };
+        std::mem::swap(&mut self.piece, &mut seat.piece);
+        std::mem::swap(&mut self.lock_delay_started, &mut seat.lock_delay_started);
+        std::mem::swap(&mut self.lock_delay_resets, &mut seat.lock_delay_resets);
+        std::mem::swap(&mut self.spawn_ready_at, &mut seat.spawn_ready_at);
+        std::mem::swap(&mut self.piece_visual_gx, &mut seat.visual_gx);
+        std::mem::swap(&mut self.piece_visual_gy, &mut seat.visual_gy);
+        std::mem::swap(&mut self.spawn_column, &mut seat.spawn_column);
+        self.playfield.blocked = seat
+            .piece
+            .as_ref()
+            .map(Piece::cell_grain_origins)
+            .unwrap_or_default();
+    }
+
+    /// `--dual`: each board's width for a game `width` block columns wide (two boards side by
+    /// side, or one).
+    pub fn board_width(width: u16, dual: bool) -> u16 {
+        if dual { (width / 2).max(1) } else { width }
+    }
+
+    /// `--dual`: make the other board the piece's, or back. Does nothing with one board.
+    pub fn swap_boards(&mut self) {
+        let Some(board) = self.idle_board.as_mut() else {
+            return;
+        };
+        std::mem::swap(&mut self.playfield, &mut board.playfield);
+        std::mem::swap(&mut self.frozen_grains, &mut board.frozen_grains);
+        std::mem::swap(
+            &mut self.crumble_delay_ticks,
+            &mut board.crumble_delay_ticks,
+        );
+        std::mem::swap(&mut self.popups, &mut board.popups);
+        board.left = !board.left;
+    }
+
+    /// `--dual`: the other board as a game of its own for drawing, with no piece or clear.
+    pub fn idle_view(&self) -> Option<Self> {
+        self.idle_board.as_ref()?;
+        let mut view = self.clone();
+        view.swap_boards();
+        view.piece = None;
+        view.line_clear_cells.clear();
+        view.line_clear_in_progress = false;
+        Some(view)
+    }
+
+    /// Run `f` for the active piece, then (co-op) for the partner's.
+    fn both_seats(&mut self, mut f: impl FnMut(&mut Self)) {
+        f(self);
+        if self.partner.is_some() {
+            self.swap_seats();
+            f(self);
+            self.swap_seats();
+        }
+    }
+
+    /// True if there is a piece that accepts input right now (not in spawn delay or a clear).
+    pub fn is_controllable(&self, now: Instant) -> bool {
+        !self.game_over
+            && !self.line_clear_in_progress
+            && self.piece.is_some()
+            && !self.is_spawn_delay(now)
+    }
+
     /// True if the current piece is still in spawn delay (no gravity / no input).
     pub fn is_spawn_delay(&self, now: Instant) -> bool {
         self.spawn_ready_at.map(|t| now < t).unwrap_or(false)
     }
 
-    pub fn spawn_piece(width: u16, _height: u16, kind: TetrominoKind, color_index: u8) -> Piece {
+    pub fn spawn_piece(width: u16, _height: u16, next: NextPiece, mirrored: bool) -> Piece {
         let w = width as i32;
-        let s = GRAIN_SCALE as i32;
-        Piece {
-            kind,
-            gx: (w / 2 - 1).max(0) * s,
+        let mut piece = Piece {
+            kind: next.kind,
+            gx: 0,
             gy: 0,
             rotation: 0,
-            color_index,
-        }
+            cell_colors: next.cell_colors,
+            rainbow_cell: next.rainbow_cell,
+            converter_cell: next.converter_cell,
+            mirrored,
+        };
+        piece.gx = piece.spawn_gx((w / 2 - 1).max(0), w);
+        piece
     }
 
-    /// Move piece down one step if possible.
+    /// Move piece down one step if possible (20G: all the way down). Both pieces in co-op.
     pub fn tick_gravity(&mut self, now: Instant) {
-        if self.game_over || self.line_clear_in_progress || self.is_spawn_delay(now) {
+        self.both_seats(|state| state.fall(now));
+    }
+
+    fn fall(&mut self, now: Instant) {
+        // Time stops in the zone: the piece only moves when the player moves it
+        if self.game_over
+            || self.line_clear_in_progress
+            || self.is_spawn_delay(now)
+            || self.zone_active()
+        {
             return;
         }
+        if self.twenty_g {
+            self.sink_piece();
+        }
         if let Some(ref mut piece) = self.piece {
             piece.gy += 1;
             let can_place = self.playfield.can_place_with_frozen(piece, &self.frozen_grains);
             if !can_place {
                 piece.gy -= 1;
-                // Instant crumble! The moment we hit something, it locks.
-                self.lock_piece();
+                self.land(now);
             } else {
                 // If we moved down successfully, we are NOT landed.
                 self.lock_delay_started = None;
@@ -598,38 +2097,167 @@ impl GameState {
         self.update_game_over_status();
     }
 
-    /// Check if piece should lock due to time spent on ground.
+    /// Piece can't move down: lock now when there is no lock delay (Sandtrix-style instant
+    /// crumble), otherwise start the lock timer.
+    fn land(&mut self, now: Instant) {
+        if self.lock_delay_ms == 0 {
+            self.lock_piece();
+        } else if self.lock_delay_started.is_none() {
+            self.lock_delay_started = Some(now);
+        }
+    }
+
+    /// Check if piece should lock due to time spent on ground (both pieces in co-op).
     /// Call this every frame for snappy snapping.
-    pub fn check_lock(&mut self, _now: Instant) {
-        if self.game_over || self.line_clear_in_progress {
+    pub fn check_lock(&mut self, now: Instant) {
+        self.both_seats(|state| state.lock_if_landed(now));
+    }
+
+    fn lock_if_landed(&mut self, now: Instant) {
+        if self.game_over || self.line_clear_in_progress || self.is_spawn_delay(now) {
             return;
         }
         if let Some(ref piece) = self.piece {
             let mut test_p = piece.clone();
             test_p.gy += 1;
 
-            if !self.playfield.can_place_with_frozen(&test_p, &self.frozen_grains) {
-                // Piece is on the ground - lock instantly in Sandtrix
-                self.lock_piece();
-            } else {
-                // Piece is in the air
-                self.lock_delay_started = None;
-                self.lock_delay_resets = 0;
+            if !self.playfield.can_place_with_frozen(&test_p, &self.frozen_grains) {
+                self.land(now);
+                let expired = self.lock_delay_started.is_some_and(|t| {
+                    now.duration_since(t).as_millis() >= u128::from(self.lock_delay_ms)
+                });
+                if expired {
+                    self.lock_piece();
+                }
+            } else {
+                // Piece is in the air
+                self.lock_delay_started = None;
+                self.lock_delay_resets = 0;
+            }
+        }
+    }
+
+    /// Call when player moves or rotates; restarts the lock timer until the reset limit is hit.
+    pub fn on_move_or_rotate(&mut self, now: Instant) {
+        if self.lock_delay_started.is_some() && self.lock_delay_resets < LOCK_DELAY_RESET_LIMIT {
+            self.lock_delay_started = Some(now);
+            self.lock_delay_resets += 1;
+        }
+    }
+
+    /// Block column under the centre of the current piece (0 = leftmost).
+    pub fn piece_column(&self) -> Option<i32> {
+        let s = GRAIN_SCALE as i32;
+        let origins = self.piece.as_ref()?.cell_grain_origins();
+        let min_x = origins.iter().map(|&(x, _)| x).min().unwrap_or(0);
+        let max_x = origins.iter().map(|&(x, _)| x).max().unwrap_or(0);
+        let center = (min_x + max_x + s) / 2;
+        Some(self.playfield.wrap_x(center) / s)
+    }
+
+    /// Grain (x, y)'s offset into a piece cell drawn at `origin`, or None if it is outside the
+    /// cell. On a cylinder a cell can straddle the seam.
+    pub fn offset_in_cell(&self, origin: (i32, i32), x: usize, y: usize) -> Option<(i32, i32)> {
+        let s = GRAIN_SCALE as i32;
+        let dx = self.playfield.wrap_x(x as i32 - origin.0);
+        let dy = y as i32 - origin.1;
+        ((0..s).contains(&dx) && (0..s).contains(&dy)).then_some((dx, dy))
+    }
+
+    /// Stack height in block rows (sand only, not the falling piece).
+    pub fn stack_height(&self) -> usize {
+        let (_, gh) = self.playfield.grain_dims();
+        self.playfield
+            .topmost_sand_y()
+            .map_or(0, |y| (gh - y).div_ceil(GRAIN_SCALE))
+    }
+
+    /// Move the piece sideways until it is centred on block `column` or blocked (mouse steering).
+    /// Returns true if it moved.
+    pub fn shift_toward(&mut self, column: i32, now: Instant) -> bool {
+        let mut moved = false;
+        loop {
+            let (Some(center), Some(before)) = (self.piece_column(), self.piece.as_ref().map(|p| p.gx))
+            else {
+                return moved;
+            };
+            if center < column {
+                self.move_right(now);
+            } else if center > column {
+                self.move_left(now);
+            }
+            if self.piece.as_ref().is_none_or(|p| p.gx == before) {
+                return moved;
+            }
+            moved = true;
+        }
+    }
+
+    /// Grains `piece` can surely fall straight down: to just above the highest grain, sand or
+    /// frozen, under each of its grain columns (from the column tops, without a scan). 0 when a
+    /// grain is level with or above part of the piece, or in co-op, where the other piece is in
+    /// the way too.
+    pub fn fall_distance(&self, piece: &Piece) -> i32 {
+        if !self.playfield.blocked.is_empty() {
+            return 0;
+        }
+        let (gw, gh) = self.playfield.grain_dims();
+        // Lowest piece grain in each grain column it covers
+        let mut bottoms: HashMap<usize, i32> = HashMap::new();
+        for (ox, oy) in piece.cell_grain_origins() {
+            for dx in 0..GRAIN_SCALE as i32 {
+                let x = self.playfield.wrap_x(ox + dx);
+                if (0..gw as i32).contains(&x) {
+                    let bottom = bottoms.entry(x as usize).or_insert(oy);
+                    *bottom = (*bottom).max(oy + GRAIN_SCALE as i32 - 1);
+                }
+            }
+        }
+        let mut tops: HashMap<usize, usize> = bottoms
+            .keys()
+            .map(|&x| (x, self.playfield.column_top(x).unwrap_or(gh)))
+            .collect();
+        for fg in &self.frozen_grains {
+            if let Some(top) = tops.get_mut(&fg.x) {
+                *top = (*top).min(fg.y);
+            }
+        }
+        bottoms
+            .iter()
+            .map(|(x, &bottom)| tops[x] as i32 - 1 - bottom)
+            .min()
+            .unwrap_or(0)
+            .max(0)
+    }
+
+    /// Drop the piece to its resting position without locking (20G).
+    fn sink_piece(&mut self) {
+        let fall = self
+            .piece
+            .as_ref()
+            .map_or(0, |piece| self.fall_distance(piece));
+        if let Some(ref mut piece) = self.piece {
+            piece.gy += fall + 1;
+            while self.playfield.can_place_with_frozen(piece, &self.frozen_grains) {
+                piece.gy += 1;
             }
+            piece.gy -= 1;
         }
     }
 
-    /// Call when player moves or rotates; resets lock delay and increments reset count.
-    pub fn on_move_or_rotate(&mut self, now: Instant) {
-        if self.lock_delay_started.is_some() {
-            self.lock_delay_started = Some(now);
-            self.lock_delay_resets = self
-                .lock_delay_resets
-                .saturating_add(1)
-                .min(LOCK_DELAY_RESET_LIMIT);
+    /// Snap the smoothed draw position to the piece's logic position.
+    fn sync_piece_visual(&mut self) {
+        if let Some(ref p) = self.piece {
+            self.piece_visual_gx = p.gx as f32;
+            self.piece_visual_gy = p.gy as f32;
         }
     }
 
+    /// Take the events collected since the last call.
+    pub fn drain_events(&mut self) -> Vec<GameEvent> {
+        std::mem::take(&mut self.events)
+    }
+
     pub fn move_left(&mut self, now: Instant) {
         if self.game_over || self.line_clear_in_progress || self.is_spawn_delay(now) {
             return;
@@ -638,8 +2266,24 @@ impl GameState {
             piece.gx -= GRAIN_SCALE as i32;
             if !self.playfield.can_place_with_frozen(piece, &self.frozen_grains) {
                 piece.gx += GRAIN_SCALE as i32;
+            } else {
+                self.events.push(GameEvent::Move);
             }
         }
+        self.wrap_piece();
+        if self.twenty_g {
+            self.sink_piece();
+        }
+    }
+
+    /// Cylinder: bring a piece that crossed the seam back onto the board, moving where it is
+    /// drawn by the same amount so it doesn't slide the long way round.
+    fn wrap_piece(&mut self) {
+        if let Some(piece) = self.piece.as_mut() {
+            let gx = self.playfield.wrap_x(piece.gx);
+            self.piece_visual_gx += (gx - piece.gx) as f32;
+            piece.gx = gx;
+        }
     }
 
     pub fn move_right(&mut self, now: Instant) {
@@ -650,8 +2294,14 @@ impl GameState {
             piece.gx += GRAIN_SCALE as i32;
             if !self.playfield.can_place_with_frozen(piece, &self.frozen_grains) {
                 piece.gx -= GRAIN_SCALE as i32;
+            } else {
+                self.events.push(GameEvent::Move);
             }
         }
+        self.wrap_piece();
+        if self.twenty_g {
+            self.sink_piece();
+        }
     }
 
     /// Wall kick order: try 0, -1, +1, -2, +2 (SRS-style).
@@ -664,8 +2314,13 @@ impl GameState {
             piece.rotation = (piece.rotation + 1) % 4;
             if !self.playfield.can_place_with_frozen(piece, &self.frozen_grains) {
                 piece.rotation = old_rotation;
+            } else {
+                self.events.push(GameEvent::Rotate);
             }
         }
+        if self.twenty_g {
+            self.sink_piece();
+        }
     }
 
     pub fn rotate_ccw(&mut self, now: Instant) {
@@ -677,10 +2332,375 @@ impl GameState {
             piece.rotation = (piece.rotation + 3) % 4;
             if !self.playfield.can_place_with_frozen(piece, &self.frozen_grains) {
                 piece.rotation = old_rotation;
+            } else {
+                self.events.push(GameEvent::Rotate);
+            }
+        }
+        if self.twenty_g {
+            self.sink_piece();
+        }
+    }
+
+    /// Points a reroll costs (`--reroll-cost`).
+    pub const fn reroll_cost(&self) -> u32 {
+        self.reroll_cost
+    }
+
+    /// Swap the falling piece for the first queued one, paying `reroll_cost` points. Only while
+    /// the piece is controllable and the score covers the cost. Returns true if it happened.
+    pub fn reroll(&mut self, now: Instant) -> bool {
+        if self.game_over
+            || self.line_clear_in_progress
+            || self.is_spawn_delay(now)
+            || self.piece.is_none()
+            || self.score < self.reroll_cost
+        {
+            return false;
+        }
+        self.score -= self.reroll_cost;
+        self.rerolls += 1;
+        self.lock_delay_started = None;
+        self.lock_delay_resets = 0;
+        // --dual: the new piece stays on this board
+        self.swap_boards();
+        self.spawn_next();
+        true
+    }
+
+    /// Points are still coming: a clear is animating, a piece is crumbling, the combo window is
+    /// open or the zone has banked clears to pay.
+    pub fn chain_active(&self) -> bool {
+        self.line_clear_in_progress
+            || self.combo_timer_ticks > 0
+            || !self.frozen_grains.is_empty()
+            || self.zone_active()
+    }
+
+    /// The zone is running: gravity and the combo timer are stopped and clears are banked.
+    pub const fn zone_active(&self) -> bool {
+        self.zone_ticks > 0
+    }
+
+    /// Spend a full zone meter on `ZONE_TICKS` of stopped time. Returns true if it started.
+    pub const fn activate_zone(&mut self) -> bool {
+        if self.game_over
+            || self.line_clear_in_progress
+            || self.zone < ZONE_MAX
+            || self.zone_active()
+        {
+            return false;
+        }
+        self.zone = 0;
+        self.zone_ticks = ZONE_TICKS;
+        true
+    }
+
+    /// Zone: take a clear off the board at once and bank it as rows at the bottom (one grain row
+    /// per board width of grains). Points come when the zone ends.
+    fn bank_clear(&mut self, num: u32, cells: &HashSet<(usize, usize)>) {
+        self.frozen_grains
+            .retain(|fg| !cells.contains(&(fg.x, fg.y)));
+        for &(x, y) in cells {
+            self.playfield.set(x, y, Cell::Empty);
+        }
+        let (gw, _) = self.playfield.grain_dims();
+        let rows = self.playfield.bank_rows(cells.len().div_ceil(gw));
+        // Everything resting on the board rises with it
+        for fg in &mut self.frozen_grains {
+            fg.y = fg.y.saturating_sub(rows);
+        }
+        self.both_seats(|state| state.lift_piece(rows));
+
+        self.zone_clears += num;
+        self.zone_grains += cells.len() as u32;
+        self.lines_cleared += num;
+        self.clears += num;
+        let prev_level = self.level;
+        self.level = 1 + self.lines_cleared / self.scoring.lines_per_level;
+        self.last_clear = (0, num);
+        self.events.push(GameEvent::Clear);
+        if self.level > prev_level {
+            self.level_up(prev_level);
+        }
+    }
+
+    /// Raise the piece by `rows` grains if the board rose into it.
+    fn lift_piece(&mut self, rows: usize) {
+        if let Some(piece) = self.piece.as_mut() {
+            if !self.playfield.can_place(piece) {
+                piece.gy -= rows as i32;
+                self.piece_visual_gy = piece.gy as f32;
+            }
+        }
+    }
+
+    /// Sand ticks between tide rises: the custom mode's garbage interval, else co-op's at the
+    /// current level.
+    const fn tide_interval(&self) -> u32 {
+        if self.garbage_ticks > 0 {
+            return self.garbage_ticks;
+        }
+        let sooner = self.level.saturating_sub(1).saturating_mul(TIDE_TICKS_PER_LEVEL);
+        let ticks = TIDE_TICKS.saturating_sub(sooner);
+        if ticks < TIDE_MIN_TICKS {
+            TIDE_MIN_TICKS
+        } else {
+            ticks
+        }
+    }
+
+    /// `--balance-colors`: deal main colours in rounds holding each colour `COLOR_BAG_COPIES`
+    /// times, so one colour can't crowd out the rest, and never queue more than
+    /// `COLOR_STREAK_LIMIT` in a row. `wanted` (the kind's colour, or a repeat) is kept when the
+    /// round still has it. Without the option `wanted` is returned as is.
+    fn balanced_color(&mut self, wanted: u8) -> u8 {
+        let Some(bag) = &mut self.color_bag else {
+            return wanted;
+        };
+        if bag.is_empty() {
+            for _ in 0..COLOR_BAG_COPIES {
+                bag.extend(0..self.colors.count());
+            }
+            self.rng.shuffle(bag);
+        }
+        let last = self.next_pieces.last().map(|n| n.color_index);
+        let run = self
+            .next_pieces
+            .iter()
+            .rev()
+            .take_while(|n| Some(n.color_index) == last)
+            .count();
+        let allowed = |c: u8| Some(c) != last || run < COLOR_STREAK_LIMIT;
+        let index = bag
+            .iter()
+            .position(|&c| c == wanted && allowed(c))
+            .or_else(|| bag.iter().position(|&c| allowed(c)))
+            .unwrap_or(0);
+        bag.remove(index)
+    }
+
+    /// Index into `next_pieces` where a new bag starts: its first piece, or `next_pieces.len()`
+    /// when the queue ends a bag. None when the whole queue is from one bag. Bombs don't come
+    /// from the bag.
+    pub fn bag_boundary(&self) -> Option<usize> {
+        let mut at = self.next_pieces.len();
+        for _ in 0..self.bag.dealt_from_current() {
+            at = at.checked_sub(1)?;
+            while self.next_pieces[at].kind == PieceKind::Bomb {
+                at = at.checked_sub(1)?;
+            }
+        }
+        Some(at)
+    }
+
+    /// How far the tide is towards its next rise, 0.0..1.0; None without co-op or custom
+    /// mode garbage.
+    pub fn tide_progress(&self) -> Option<f64> {
+        self.tide_rising()
+            .then(|| f64::from(self.tide_ticks) / f64::from(self.tide_interval()))
+    }
+
+    /// Co-op and custom mode garbage: the tide is on.
+    const fn tide_rising(&self) -> bool {
+        self.partner.is_some() || self.garbage_ticks > 0
+    }
+
+    /// Co-op: the garbage tide rises one block row of mixed-colour sand, lifting everything on
+    /// the board and both pieces with it.
+    fn raise_tide(&mut self) {
+        let count = self.colors.count();
+        let colors: Vec<u8> = (0..self.playfield.width)
+            .map(|_| self.rng.u8(..count))
+            .collect();
+        self.playfield.raise_garbage(&colors);
+        for fg in &mut self.frozen_grains {
+            fg.y = fg.y.saturating_sub(GRAIN_SCALE);
+        }
+        self.tide_rows += 1;
+        self.both_seats(|state| state.lift_piece(GRAIN_SCALE));
+        self.update_game_over_status();
+    }
+
+    /// Tutorial: empty the board and lay a block row along the bottom in colour 0 with the last
+    /// two columns in colour 1, then make every piece colour 0. A piece dropped at the right end,
+    /// resting partly on the bridge, joins it to the right wall and clears it.
+    pub fn build_bridge(&mut self) {
+        let width = self.playfield.width;
+        let mut playfield = Playfield::new(width as u16, self.playfield.height as u16);
+        playfield.tick_count = self.playfield.tick_count;
+        playfield.blocked = std::mem::take(&mut self.playfield.blocked);
+        playfield.wrap = self.playfield.wrap;
+        if self.playfield.moved_at.is_some() {
+            playfield.track_moves();
+        }
+        let colors: Vec<u8> = (0..width).map(|x| u8::from(x + 2 >= width)).collect();
+        playfield.raise_garbage(&colors);
+        self.playfield = playfield;
+        self.frozen_grains.clear();
+        if let Some(piece) = &mut self.piece {
+            piece.cell_colors.fill(0);
+            piece.rainbow_cell = None;
+            piece.converter_cell = None;
+        }
+        for next in &mut self.next_pieces {
+            next.color_index = 0;
+            next.cell_colors.fill(0);
+            next.rainbow_cell = None;
+            next.converter_cell = None;
+        }
+    }
+
+    /// Game over drain (on a copy of the final state): the falling pieces go and crumbling grains
+    /// join the sand, then the bottom grain row is emptied and everything above falls a step.
+    /// False once the board is empty.
+    pub fn drain_step(&mut self) -> bool {
+        self.piece = None;
+        if let Some(seat) = &mut self.partner {
+            seat.piece = None;
+        }
+        for grain in std::mem::take(&mut self.frozen_grains) {
+            self.playfield.set(grain.x, grain.y, grain.cell);
+        }
+        self.playfield.banked_rows = 0;
+        self.line_clear_in_progress = false;
+        self.line_clear_cells.clear();
+
+        let (gw, gh) = self.playfield.grain_dims();
+        for x in 0..gw {
+            self.playfield.set(x, gh - 1, Cell::Empty);
+        }
+        self.playfield
+            .tick_physics(self.settle_left_first, &self.physics, &mut self.physics_rng);
+        self.settle_left_first = !self.settle_left_first;
+        self.playfield.grain_count() > 0
+    }
+
+    /// Announce the new level; every `obstacle_levels` levels of the difficulty (hard: three)
+    /// also queues an obstacle.
+    fn level_up(&mut self, prev_level: u32) {
+        self.events.push(GameEvent::LevelUp(self.level));
+        let every = self.profile.obstacle_levels;
+        if every > 0 && self.level / every > prev_level / every {
+            self.obstacle_due = true;
+        }
+        if self.breathers && self.level / BREATHER_LEVELS > prev_level / BREATHER_LEVELS {
+            self.start_breather();
+        }
+    }
+
+    /// Obstacle: heap one block cell of stone, or sand in a colour no queued piece has, on the lowest
+    /// block column. Skipped while the stack is in the danger zone, and never built into the
+    /// spawn zone, so it can't end the game by itself.
+    fn drop_obstacle(&mut self) {
+        if self.playfield.in_danger_zone() {
+            return;
+        }
+        let (gw, gh) = self.playfield.grain_dims();
+        let s = GRAIN_SCALE;
+        let cols = gw / s;
+        if cols == 0 {
+            return;
+        }
+        let playfield = &self.playfield;
+        let surface = |x: usize| {
+            (0..gh)
+                .find(|&y| playfield.get(x, y).is_some_and(Cell::is_solid))
+                .unwrap_or(gh)
+        };
+        let column_top = |col: usize| (col * s..(col + 1) * s).map(surface).min().unwrap_or(gh);
+        // Deepest column; ties go to whichever comes last from a random start
+        let start = self.rng.usize(..cols);
+        let col = (0..cols)
+            .map(|i| (start + i) % cols)
+            .max_by_key(|&c| column_top(c))
+            .unwrap_or(0);
+
+        let queued: HashSet<u8> = self
+            .next_pieces
+            .iter()
+            .flat_map(|n| n.cell_colors.iter().copied())
+            .collect();
+        let off_colors: Vec<u8> = (0..self.colors.count())
+            .filter(|c| !queued.contains(c))
+            .collect();
+        let color = if off_colors.is_empty() || self.rng.bool() {
+            None
+        } else {
+            Some(off_colors[self.rng.usize(..off_colors.len())])
+        };
+
+        let tops: [usize; GRAIN_SCALE] = std::array::from_fn(|dx| surface(col * s + dx));
+        for (dx, top) in tops.into_iter().enumerate() {
+            let x = col * s + dx;
+            for dy in 0..s {
+                let Some(y) = top.checked_sub(dy + 1).filter(|&y| y >= SPAWN_ZONE_ROWS) else {
+                    break;
+                };
+                let is_shadow = (dy + 1) % s == 0 || (dx + 1) % s == 0;
+                let cell = color.map_or(Cell::Stone(is_shadow), |c| Cell::Sand(c, is_shadow));
+                self.playfield.set(x, y, cell);
             }
         }
     }
 
+    /// Zone over: every banked row clears at once, scoring the banked grains times the number of
+    /// clears banked.
+    fn end_zone(&mut self) {
+        let rows = self.playfield.unbank_rows();
+        // --dual: the other board's banked rows come off at once
+        let idle_rows = self.idle_board.as_mut().map_or(0, IdleBoard::release_bank);
+        let clears = std::mem::take(&mut self.zone_clears);
+        let grains = std::mem::take(&mut self.zone_grains);
+        if rows + idle_rows == 0 {
+            return;
+        }
+        let (gw, gh) = self.playfield.grain_dims();
+        let amount = grains * self.scoring.grain_points * clears;
+        self.score += amount;
+        self.popups.push(ScorePopup {
+            x: gw / 2,
+            y: gh - rows.max(1),
+            amount,
+            multiplier: clears,
+            age_ms: 0,
+            color: Color::LightMagenta,
+            breakdown: Vec::new(),
+        });
+        self.last_clear = (amount, clears);
+        self.last_clear_colors.clear();
+        self.events.push(GameEvent::Clear);
+        self.line_clear_cells = (gh - rows..gh)
+            .flat_map(|y| (0..gw).map(move |x| (x, y)))
+            .collect();
+        self.line_clear_in_progress = true;
+    }
+
+    /// Slow motion is running (gravity and sand at 1/`FOCUS_SLOWDOWN` speed).
+    pub const fn focus_active(&self) -> bool {
+        self.focus_ticks > 0
+    }
+
+    /// Endless breather running: faster sand and double points.
+    pub const fn breather_active(&self) -> bool {
+        self.breather_ticks > 0
+    }
+
+    /// Start a breather, or give a running one its full length again.
+    fn start_breather(&mut self) {
+        self.breather_ticks = BREATHER_TICKS;
+        self.events.push(GameEvent::Breather);
+    }
+
+    /// Spend a full focus meter on `FOCUS_TICKS` of slow motion. Returns true if it started.
+    pub const fn activate_focus(&mut self) -> bool {
+        if self.game_over || self.focus < FOCUS_MAX || self.focus_active() {
+            return false;
+        }
+        self.focus = 0;
+        self.focus_ticks = FOCUS_TICKS;
+        true
+    }
+
     pub fn soft_drop(&mut self, now: Instant) {
         if self.game_over || self.line_clear_in_progress || self.is_spawn_delay(now) {
             return;
@@ -689,11 +2709,11 @@ impl GameState {
             piece.gy += 1;
             if !self.playfield.can_place_with_frozen(piece, &self.frozen_grains) {
                 piece.gy -= 1;
-                self.lock_piece();
+                self.land(now);
             } else {
                 self.lock_delay_started = None;
                 self.lock_delay_resets = 0;
-                self.score += 1;
+                self.score += self.scoring.soft_drop_points;
             }
         }
     }
@@ -705,9 +2725,8 @@ impl GameState {
         if let Some(piece) = &self.piece { // Avoid clone if possible, but we need mut access later
             let mut p = piece.clone();
             let (_, gh) = self.playfield.grain_dims();
-            let mut pgy = p.gy;
-            
-            // Linear scan down
+            // Straight to the top of the stack, then a linear scan down
+            let mut pgy = p.gy + self.fall_distance(&p);
             while pgy < gh as i32 {
                 p.gy = pgy + 1;
                 if !self.playfield.can_place_with_frozen(&p, &self.frozen_grains) {
@@ -717,7 +2736,7 @@ impl GameState {
             }
             
             let dist_grains = (pgy - piece.gy).max(0) as u32;
-            self.score += (dist_grains / GRAIN_SCALE as u32) * 2;
+            self.score += (dist_grains / GRAIN_SCALE as u32) * self.scoring.hard_drop_points;
             
             if let Some(ref mut p_ref) = self.piece {
                 p_ref.gy = pgy;
@@ -731,15 +2750,32 @@ impl GameState {
             Some(p) => p,
             None => return,
         };
-        let color_index = piece.color_index;
-
+        self.lock_delay_started = None;
+        self.lock_delay_resets = 0;
+        self.placement_clears = 0;
+        self.events.push(GameEvent::Lock);
+        for (gx, _) in piece.cell_grain_origins() {
+            let gx = self.playfield.wrap_x(gx);
+            if let Some(count) = usize::try_from(gx / GRAIN_SCALE as i32)
+                .ok()
+                .and_then(|col| self.lock_columns.get_mut(col))
+            {
+                *count += 1;
+            }
+        }
+        if piece.kind == PieceKind::Bomb {
+            self.detonate(&piece);
+            return;
+        }
         // --- PIECE FREEZING (Freeze & Crumble) ---
         // Instead of writing to the playfield instantly, we move grains to the frozen buffer.
         // This makes the piece "freeze" in place before dissolving.
-        for (gx, gy) in piece.cell_grain_origins() {
+        let check = self.grain_check("lock");
+        for (i, (gx, gy)) in piece.cell_grain_origins().into_iter().enumerate() {
+            let color_index = piece.cell_colors[i];
             for dy in 0..GRAIN_SCALE as i32 {
                 for dx in 0..GRAIN_SCALE as i32 {
-                    let px = gx + dx;
+                    let px = self.playfield.wrap_x(gx + dx);
                     let py = gy + dy;
 
                     // Boundary check to prevent grain loss
@@ -754,18 +2790,35 @@ impl GameState {
                             // This creates persistent edge separation.
                             let is_shadow =
                                 (dy == GRAIN_SCALE as i32 - 1) || (dx == GRAIN_SCALE as i32 - 1);
-
-                            self.frozen_grains.push(FrozenGrain {
-                                x: tx,
-                                y: ty,
-                                color_index,
-                                is_shadow,
-                            });
+                            let cell = if piece.rainbow_cell == Some(i) {
+                                Cell::Rainbow(is_shadow)
+                            } else if piece.converter_cell == Some(i) {
+                                Cell::Converter(color_index, is_shadow)
+                            } else {
+                                Cell::Sand(color_index, is_shadow)
+                            };
+
+                            self.frozen_grains.push(FrozenGrain { x: tx, y: ty, cell });
                         }
                     }
                 }
             }
         }
+        // Only a piece locking out over the top (game over) leaves grains off the board
+        let origins = piece.cell_grain_origins();
+        let above: usize = origins
+            .iter()
+            .map(|&(_, gy)| (gy..gy + GRAIN_SCALE as i32).filter(|&y| y < 0).count())
+            .sum();
+        self.end_grain_check(
+            check,
+            origins.len() * GRAIN_SCALE * GRAIN_SCALE,
+            above * GRAIN_SCALE,
+        );
+
+        if piece.converter_cell.is_some() {
+            self.converter_budget = CONVERTER_FLOOD_LIMIT;
+        }
 
         // --- GRAVITY-FIRST CRUMBLE ---
         // Sort grains by Y ascending so that pop() retrieves the bottom-most grains first.
@@ -786,6 +2839,58 @@ impl GameState {
         }
     }
 
+    /// Bomb lock: every grain (sand, frozen or stone) within `BOMB_RADIUS` of the bomb's centre is blasted.
+    /// Reuses the line-clear pipeline (`line_clear_blast` selects the blast animation); scores one point per grain.
+    fn detonate(&mut self, piece: &Piece) {
+        let center = piece.blast_center();
+        let (gw, gh) = self.playfield.grain_dims();
+        let check = self.grain_check("bomb blast");
+        let mut blasted: Vec<(usize, usize)> = Vec::new();
+        for y in (center.1 - BOMB_RADIUS).max(0)..=(center.1 + BOMB_RADIUS).min(gh as i32 - 1) {
+            for x in (center.0 - BOMB_RADIUS).max(0)..=(center.0 + BOMB_RADIUS).min(gw as i32 - 1) {
+                if in_blast(center, x, y)
+                    && self
+                        .playfield
+                        .get(x as usize, y as usize)
+                        .is_some_and(Cell::is_solid)
+                {
+                    blasted.push((x as usize, y as usize));
+                }
+            }
+        }
+        let before = self.frozen_grains.len();
+        self.frozen_grains
+            .retain(|fg| !in_blast(center, fg.x as i32, fg.y as i32));
+        let frozen_blasted = before - self.frozen_grains.len();
+
+        let amount = (blasted.len() + frozen_blasted) as u32 * self.scoring.grain_points;
+        if amount > 0 {
+            self.score += amount;
+            let (px, py) = (
+                center.0.clamp(0, gw as i32 - 1) as usize,
+                center.1.clamp(0, gh as i32 - 1) as usize,
+            );
+            self.popups.push(ScorePopup {
+                x: px,
+                y: py,
+                amount,
+                multiplier: 1,
+                age_ms: 0,
+                color: Color::LightRed,
+                breakdown: Vec::new(),
+            });
+        }
+        if blasted.is_empty() {
+            self.end_grain_check(check, 0, frozen_blasted);
+            self.spawn_next();
+        } else {
+            self.clear_check = check.map(|check| (check, blasted.len() + frozen_blasted));
+            self.line_clear_cells = blasted;
+            self.line_clear_in_progress = true;
+            self.line_clear_blast = true;
+        }
+    }
+
     /// Updates game_over flag when the effective top of the stack (sand or frozen) enters the spawn zone.
     /// Falling grains count as height even if they haven't landed on the main pile.
     /// We do not include the current piece: it always starts in the spawn zone and would trigger
@@ -803,59 +2908,102 @@ impl GameState {
         }
     }
 
+    /// `--debug-invariants`: the board before a step, to check its grain count after.
+    fn grain_check(&self, step: &'static str) -> Option<GrainCheck> {
+        self.debug_invariants
+            .then(|| GrainCheck::new(step, &self.playfield, &self.frozen_grains))
+    }
+
+    /// `--debug-invariants`: the step since `check` added and removed this many grains, and no
+    /// others (see `GrainCheck::finish`).
+    fn end_grain_check(&self, check: Option<GrainCheck>, added: usize, removed: usize) {
+        if let Some(check) = check {
+            check.finish(&self.playfield, &self.frozen_grains, added, removed);
+        }
+    }
+
     /// Called after line-clear animation: clear cells, apply gravity, spawn next.
     /// Only spawns a new piece if none is currently active (avoids replacing a mid-air piece).
     pub fn finish_line_clear(&mut self) {
+        self.remove_clear_cells();
+        self.spawn_missing();
+    }
+
+    /// Take the clear in progress off the board.
+    fn remove_clear_cells(&mut self) {
+        self.line_clear_blast = false;
         if self.line_clear_cells.is_empty() {
             self.line_clear_in_progress = false;
-            if self.piece.is_none() {
-                self.spawn_next();
-            }
             return;
         }
         for &(x, y) in &self.line_clear_cells {
             self.playfield.set(x, y, Cell::Empty);
         }
+        if let Some((check, removed)) = self.clear_check.take() {
+            self.end_grain_check(Some(check), 0, removed);
+        }
         self.line_clear_cells.clear();
         self.line_clear_in_progress = false;
-        if self.piece.is_none() {
-            self.spawn_next();
+        if self.breathers && self.playfield.grain_count() == 0 && self.frozen_grains.is_empty() {
+            self.start_breather();
         }
     }
 
-    /// Update sand physics (one step). Should be called regularly.
-    pub fn tick_sand(&mut self) {
-        if self.line_clear_in_progress {
-            return;
-        }
+    /// Spawn for every player whose piece locked during the clear.
+    fn spawn_missing(&mut self) {
+        self.both_seats(|state| {
+            if state.piece.is_none() {
+                state.spawn_next();
+            }
+        });
+    }
 
+    /// Locked pieces turn to sand once their crumble delay is up.
+    fn crumble(&mut self) {
         // --- PIECE CRUMBLE PROCESSOR ---
         if self.crumble_delay_ticks > 0 {
             self.crumble_delay_ticks = self.crumble_delay_ticks.saturating_sub(1);
         } else {
             // --- TURBO DRAIN (36 grains per tick) ---
             // Faster conversion (one full 6x6 block cell per logic tick).
+            let check = self.grain_check("crumble");
+            let mut stuck = Vec::new();
             for _ in 0..36 {
-                if let Some(fg) = self.frozen_grains.pop() {
-                    self.playfield
-                        .set(fg.x, fg.y, Cell::Sand(fg.color_index, fg.is_shadow));
+                let Some(fg) = self.frozen_grains.pop() else {
+                    break;
+                };
+                // Sand that slid in under the frozen piece holds the grain up instead of being
+                // overwritten by it. A column full to the top has no room: the grain stays
+                // frozen, and the stack in the spawn zone ends the game.
+                let free = (0..=fg.y)
+                    .rev()
+                    .find(|&y| self.playfield.get(fg.x, y) == Some(Cell::Empty));
+                match free {
+                    Some(y) => self.playfield.set(fg.x, y, fg.cell),
+                    None => stuck.push(fg),
                 }
             }
+            // Last to be popped, so they don't hold up the rest
+            self.frozen_grains.splice(..0, stuck);
+            self.end_grain_check(check, 0, 0);
         }
+    }
 
-        // --- COMBO DECAY ---
-        if self.combo_timer_ticks > 0 {
-            self.combo_timer_ticks = self.combo_timer_ticks.saturating_sub(1);
-            if self.combo_timer_ticks == 0 {
-                self.combo_multiplier = 1;
-            }
-        }
+    /// Sand physics and converters for one tick, then the clears and top-out they cause.
+    fn settle_sand(&mut self) {
+        let check = self.grain_check("sand physics");
+        let moved = self.playfield.tick_physics(
+            self.settle_left_first,
+            &self.physics,
+            &mut self.physics_rng,
+        );
 
-        let moved = self.playfield.tick_physics(self.settle_left_first);
-        self.settle_left_first = !self.settle_left_first;
+        // --- COLOUR CONVERTERS ---
+        let converted = self.playfield.settle_converters(&mut self.converter_budget) > 0;
+        self.end_grain_check(check, 0, 0);
 
         // --- DYNAMIC CLEAR CHECK (During Physics/Crumble) ---
-        if (moved || (self.crumble_delay_ticks == 0 && !self.frozen_grains.is_empty()))
+        if (moved || converted || (self.crumble_delay_ticks == 0 && !self.frozen_grains.is_empty()))
             && !self.line_clear_in_progress
         {
             self.process_clears();
@@ -863,6 +3011,78 @@ impl GameState {
         self.update_game_over_status();
     }
 
+    /// `--dual`: the other board's sand runs too. A clear there comes off at once, unanimated,
+    /// and spawns no piece.
+    fn tick_idle_board(&mut self) {
+        if self.idle_board.is_none() || self.line_clear_in_progress {
+            return;
+        }
+        let piece = self.piece.take();
+        self.swap_boards();
+        self.crumble();
+        self.settle_sand();
+        if self.line_clear_in_progress {
+            self.remove_clear_cells();
+        }
+        self.swap_boards();
+        self.piece = piece;
+    }
+
+    /// Update sand physics (one step). Should be called regularly.
+    pub fn tick_sand(&mut self) {
+        if self.line_clear_in_progress {
+            return;
+        }
+
+        self.crumble();
+
+        self.focus_ticks = self.focus_ticks.saturating_sub(1);
+        self.breather_ticks = self.breather_ticks.saturating_sub(1);
+
+        // --- ZONE ---
+        if self.zone_ticks > 0 {
+            self.zone_ticks -= 1;
+            if self.zone_ticks == 0 {
+                self.end_zone();
+                if self.line_clear_in_progress {
+                    return;
+                }
+            }
+        }
+
+        // --- CO-OP / CUSTOM GARBAGE TIDE (held in the zone) ---
+        if self.tide_rising() && !self.zone_active() {
+            self.tide_ticks += 1;
+            if self.tide_ticks >= self.tide_interval() {
+                self.tide_ticks = 0;
+                self.raise_tide();
+            }
+        }
+
+        // --- COMBO DECAY (held in the zone and while a piece crumbles) ---
+        if self.combo_timer_ticks > 0 && !self.zone_active() && self.frozen_grains.is_empty() {
+            self.combo_timer_ticks = self.combo_timer_ticks.saturating_sub(1);
+            if self.combo_timer_ticks == 0 {
+                match self.combo.decay {
+                    crate::ComboDecay::Reset => self.combo_multiplier = 1,
+                    crate::ComboDecay::Step => {
+                        self.combo_multiplier = self.combo_multiplier.saturating_sub(1).max(1);
+                        if self.combo_multiplier > 1 {
+                            self.combo_timer_ticks = self.combo.timer_ticks;
+                        }
+                    }
+                }
+                if self.combo_multiplier == 1 {
+                    self.end_combo();
+                }
+            }
+        }
+
+        self.settle_sand();
+        self.tick_idle_board();
+        self.settle_left_first = !self.settle_left_first;
+    }
+
     /// Check for clears and update score/popups.
     /// Clears the spanning component plus any 8-connected frozen grains of the same colour
     /// so the entire block clears at once (not just the part that had already turned to sand).
@@ -896,13 +3116,13 @@ impl GameState {
                 .collect();
 
             let mut clear_set: HashSet<(usize, usize)> = cells.iter().copied().collect();
-            let frozen_map: HashMap<(usize, usize), u8> = self
+            let frozen_map: HashMap<(usize, usize), Cell> = self
                 .frozen_grains
                 .iter()
-                .map(|fg| ((fg.x, fg.y), fg.color_index))
+                .map(|fg| ((fg.x, fg.y), fg.cell))
                 .collect();
 
-            // Expand clear set to include 8-connected frozen grains of the same colour(s)
+            // Expand clear set to include 8-connected frozen grains of the same colour(s) (or rainbow)
             let mut frontier: VecDeque<(usize, usize)> = clear_set.iter().copied().collect();
             while let Some((x, y)) = frontier.pop_front() {
                 for (dx, dy) in NEIGHBOURS_8 {
@@ -910,9 +3130,9 @@ impl GameState {
                     let ny = y as i16 + dy;
                     if nx >= 0 && nx < gw as i16 && ny >= 0 && ny < gh as i16 {
                         let (nx, ny) = (nx as usize, ny as usize);
-                        let is_frozen_same_color = frozen_map
-                            .get(&(nx, ny))
-                            .map_or(false, |&c| clear_colors.contains(&c));
+                        let is_frozen_same_color = frozen_map.get(&(nx, ny)).map_or(false, |&c| {
+                            clear_colors.iter().any(|&color| c.matches_color(color))
+                        });
                         if is_frozen_same_color && clear_set.insert((nx, ny)) {
                             frontier.push_back((nx, ny));
                         }
@@ -920,20 +3140,91 @@ impl GameState {
                 }
             }
 
+            // Stone next to the clear breaks with it
+            let stones = self.playfield.stones_near(&clear_set);
+            clear_set.extend(stones);
+            self.last_clear_colors = clear_colors.iter().copied().collect();
+            self.last_clear_colors.sort_unstable();
+
+            if self.zone_active() {
+                self.bank_clear(num, &clear_set);
+                return;
+            }
+
             // --- COMBO SYSTEM ---
-            self.combo_multiplier = (self.combo_multiplier + 1).min(10);
-            self.combo_timer_ticks = 90; // 1.5s at 60Hz
+            // One placement raises the multiplier once; its cascades escalate a chain bonus
+            let chain = self.placement_clears.min(PLACEMENT_CHAIN_MAX);
+            self.placement_clears += 1;
+            let prev_multiplier = self.combo_multiplier;
+            if chain == 0 {
+                self.combo_multiplier = (self.combo_multiplier + self.scoring.combo_step)
+                    .min(self.combo.max_multiplier);
+            }
+            self.combo_peak = self.combo_peak.max(self.combo_multiplier);
+            self.combo_timer_ticks = self.combo.timer_ticks;
+            let reached = |award: u32| prev_multiplier < award && self.combo_multiplier >= award;
+            if self.bombs && reached(BOMB_COMBO_AWARD) {
+                if let Some(last) = self.next_pieces.last_mut() {
+                    last.kind = PieceKind::Bomb;
+                    last.cell_colors = vec![last.color_index; PieceKind::Bomb.cells().len()];
+                    last.rainbow_cell = None;
+                    last.converter_cell = None;
+                }
+            } else if self.converters && reached(CONVERTER_COMBO_AWARD) {
+                if let Some(last) = self.next_pieces.last_mut() {
+                    if last.kind != PieceKind::Bomb {
+                        last.rainbow_cell = None;
+                        last.converter_cell = Some(self.rng.usize(..last.cell_colors.len()));
+                    }
+                }
+            } else if self.rainbow && reached(RAINBOW_COMBO_AWARD) {
+                if let Some(last) = self.next_pieces.last_mut() {
+                    if last.kind != PieceKind::Bomb && last.converter_cell.is_none() {
+                        let len = last.cell_colors.len();
+                        last.rainbow_cell.get_or_insert_with(|| self.rng.usize(..len));
+                    }
+                }
+            }
 
-            let pixel_score = clear_set.len() as u32;
-            let amount = pixel_score * self.combo_multiplier;
+            let mut grain_score = self.scoring.grain_points * self.combo_multiplier * (1 + chain);
+            if self.breather_active() {
+                grain_score *= BREATHER_POINTS;
+            }
+            let amount = clear_set.len() as u32 * grain_score;
+            let mut by_color: BTreeMap<u8, u32> = BTreeMap::new();
+            for pos in &clear_set {
+                let cell = self
+                    .playfield
+                    .get(pos.0, pos.1)
+                    .filter(|c| c.is_solid())
+                    .or_else(|| frozen_map.get(pos).copied());
+                if let Some(Cell::Sand(c, _) | Cell::Converter(c, _)) = cell {
+                    *by_color.entry(c).or_default() += grain_score;
+                }
+            }
 
             self.score += amount;
             self.lines_cleared += num;
             self.clears += num;
-            self.level = 1 + self.lines_cleared / 10;
+            self.focus = (self.focus + FOCUS_PER_CLEAR * num).min(FOCUS_MAX);
+            self.zone = (self.zone + ZONE_PER_CLEAR * num).min(ZONE_MAX);
+            let prev_level = self.level;
+            self.level = 1 + self.lines_cleared / self.scoring.lines_per_level;
+
+            self.last_clear = (amount, num);
+            self.events.push(GameEvent::Clear);
+            if self.combo_multiplier > prev_multiplier && self.combo_multiplier > 1 {
+                self.events.push(GameEvent::Combo(self.combo_multiplier));
+            }
+            if self.level > prev_level {
+                self.level_up(prev_level);
+            }
 
             self.line_clear_cells = clear_set.into_iter().collect();
             self.line_clear_in_progress = true;
+            self.clear_check = self
+                .grain_check("clear")
+                .map(|check| (check, self.line_clear_cells.len()));
 
             let clear_cells: HashSet<(usize, usize)> =
                 self.line_clear_cells.iter().copied().collect();
@@ -949,43 +3240,123 @@ impl GameState {
                     (self.playfield.height * GRAIN_SCALE) / 2,
                 )
             };
+            self.push_clear_popup(px, py, amount, by_color);
+        }
+    }
+
+    /// `--missions`: score a completed objective's bonus.
+    pub fn award_mission(&mut self, bonus: u32) {
+        self.score += bonus;
+        self.events.push(GameEvent::Mission { bonus });
+    }
+
+    /// The multiplier is back to x1: a chain of two clears or more scores its bonus.
+    fn end_combo(&mut self) {
+        let peak = std::mem::replace(&mut self.combo_peak, 1);
+        if peak < 3 {
+            return;
+        }
+        let bonus = COMBO_END_BONUS * peak;
+        self.score += bonus;
+        self.events.push(GameEvent::ComboEnd { peak, bonus });
+        self.combo_end = Some(ComboEnd {
+            peak,
+            bonus,
+            age_ms: 0,
+        });
+    }
 
+    /// Score popup for a clear, or (within `POPUP_MERGE_MS` of the last clear's) added to that
+    /// one, so a chain reads as one stacked total.
+    fn push_clear_popup(&mut self, x: usize, y: usize, amount: u32, by_color: BTreeMap<u8, u32>) {
+        let merge = self
+            .popups
+            .last()
+            .is_some_and(|p| !p.breakdown.is_empty() && p.age_ms < POPUP_MERGE_MS);
+        if !merge {
             self.popups.push(ScorePopup {
-                x: px,
-                y: py,
-                amount,
-                multiplier: self.combo_multiplier,
+                x,
+                y,
+                amount: 0,
+                multiplier: 1,
                 age_ms: 0,
                 color: Color::Yellow,
+                breakdown: Vec::new(),
             });
         }
+        let Some(popup) = self.popups.last_mut() else {
+            return;
+        };
+        popup.amount += amount;
+        popup.multiplier = self.combo_multiplier;
+        popup.age_ms = 0;
+        for (color, points) in by_color {
+            match popup.breakdown.iter_mut().find(|(c, _)| *c == color) {
+                Some((_, total)) => *total += points,
+                None => popup.breakdown.push((color, points)),
+            }
+        }
+        popup.breakdown.sort_by_key(|&(_, points)| std::cmp::Reverse(points));
+        if let Some(&(color, _)) = popup.breakdown.first() {
+            popup.color = self.theme.sand_color(color);
+        }
     }
 
     fn spawn_next(&mut self) {
+        // --dual: pieces take turns on the two boards
+        self.swap_boards();
+        if std::mem::take(&mut self.obstacle_due) {
+            self.drop_obstacle();
+        }
         let width = self.playfield.width as u16;
         let height = self.playfield.height as u16;
 
-        // Pull from queue (kind, color)
-        let (next_kind, next_color) = self.next_pieces.remove(0);
+        // Pull from queue
+        let next = self.next_pieces.remove(0);
+        let next_color = next.color_index;
 
         // Add new piece to queue with bias
-        let new_kind = self.bag.next();
-        let repeat_chance = match self.difficulty {
-            crate::Difficulty::Easy => 0.40,
-            crate::Difficulty::Medium => 0.20,
-            crate::Difficulty::Hard => 0.08,
-        };
+        let new_kind = next_kind(&mut self.bag, &mut self.rng, self.bombs);
 
         // Bias towards the color of the last piece currently in the queue
-        let last_queued_color = self.next_pieces.last().map(|(_, c)| *c).unwrap_or(next_color);
-        let mut new_color = new_kind.color_index(self.high_color);
-        if self.rng.f32() < repeat_chance {
-            new_color = last_queued_color;
-        }
-        self.next_pieces.push((new_kind, new_color));
+        let last_queued_color = self
+            .next_pieces
+            .last()
+            .map(|n| n.color_index)
+            .unwrap_or(next_color);
+        let new_color = assign_color(
+            &mut self.rng,
+            self.color_assign,
+            new_kind,
+            Some(last_queued_color),
+            self.profile.repeat_color_chance,
+            self.colors,
+            &self.playfield,
+        );
+        let new_color = self.balanced_color(new_color);
+        let cell_colors = roll_cell_colors(
+            &mut self.rng,
+            new_kind,
+            new_color,
+            self.two_tone,
+            self.colors,
+        );
+        let rainbow_cell = roll_rainbow_cell(&mut self.rng, new_kind, self.rainbow);
+        self.next_pieces.push(NextPiece {
+            kind: new_kind,
+            color_index: new_color,
+            cell_colors,
+            rainbow_cell,
+            converter_cell: None,
+        });
 
         self.last_spawn_color = Some(next_color);
-        self.piece = Some(Self::spawn_piece(width, height, next_kind, next_color));
+        self.events.push(GameEvent::Spawn(next.kind));
+        *self.spawned.entry(next.kind).or_default() += 1;
+        self.piece = Some(Self::spawn_piece(width, height, next, self.mirror));
+        if let (Some(column), Some(piece)) = (self.spawn_column, self.piece.as_mut()) {
+            piece.gx = piece.spawn_gx(column, i32::from(width));
+        }
         if let Some(ref p) = self.piece {
             self.piece_visual_gx = p.gx as f32;
             self.piece_visual_gy = p.gy as f32;
@@ -1000,17 +3371,51 @@ impl GameState {
         if !self.playfield.can_place(self.piece.as_ref().unwrap()) {
             self.game_over = true;
         }
+        if self.twenty_g && !self.game_over {
+            self.sink_piece();
+            self.sync_piece_visual();
+        }
     }
 
-    pub fn tick_popups(&mut self, delta_ms: u32) {
-        self.popups.retain_mut(|p| {
-            let old_steps = p.age_ms / 150;
-            p.age_ms += delta_ms;
-            let new_steps = p.age_ms / 150;
-            if new_steps > old_steps && p.y > 0 {
-                p.y = p.y.saturating_sub(1); // Float up smoothly
+    /// Age the score popups; with `float` they also drift upwards.
+    pub fn tick_popups(&mut self, delta_ms: u32, float: bool) {
+        let idle = self.idle_board.as_mut().map(|board| &mut board.popups);
+        for popups in std::iter::once(&mut self.popups).chain(idle) {
+            popups.retain_mut(|p| {
+                let old_steps = p.age_ms / 150;
+                p.age_ms += delta_ms;
+                let new_steps = p.age_ms / 150;
+                if float && new_steps > old_steps && p.y > 0 {
+                    p.y = p.y.saturating_sub(1); // Float up smoothly
+                }
+                p.age_ms < 1500 // Last for 1.5s
+            });
+        }
+        if let Some(end) = &mut self.combo_end {
+            end.age_ms += delta_ms;
+            if end.age_ms >= COMBO_BANNER_MS {
+                self.combo_end = None;
             }
-            p.age_ms < 1500 // Last for 1.5s
-        });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `--start-clean-streak` leaves the first bag two short; the queue's bag divider must
+    /// still count it as a bag of five.
+    #[test]
+    fn short_first_bag_is_counted() {
+        let mut bag = Bag::new(7, crate::PieceSet::default());
+        bag.skip_first_s_z();
+        let dealt: Vec<usize> = (0..12)
+            .map(|_| {
+                bag.next();
+                bag.dealt_from_current()
+            })
+            .collect();
+        assert_eq!(dealt, [1, 2, 3, 4, 0, 1, 2, 3, 4, 5, 6, 0]);
     }
 }
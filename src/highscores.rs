@@ -1,29 +1,19 @@
-//! Persist high scores to disk (XDG config or ~/.config/setrixtui).
+//! Persist high scores to disk (XDG config or ~/.config/setrixtui; %APPDATA%\setrixtui on Windows).
 
 use anyhow::Result;
+use std::collections::BTreeMap;
 use std::fs;
 use std::io::{BufRead, BufReader, Write};
 use std::path::PathBuf;
 
 const FILENAME: &str = "highscores";
 
+/// Custom mode bests: one `name<TAB>score` line per mode.
+const CUSTOM_FILENAME: &str = "custom_highscores";
+
 /// Returns the path to the high scores file (config dir / setrixtui / highscores).
 fn config_path() -> Result<PathBuf> {
-    let base = if let Ok(xdg) = std::env::var("XDG_CONFIG_HOME") {
-        if xdg.is_empty() {
-            std::env::var("HOME")
-                .map(PathBuf::from)
-                .unwrap_or_else(|_| PathBuf::from("."))
-                .join(".config")
-        } else {
-            PathBuf::from(xdg)
-        }
-    } else {
-        std::env::var("HOME")
-            .map(|h| PathBuf::from(h).join(".config"))
-            .unwrap_or_else(|_| PathBuf::from("."))
-    };
-    Ok(base.join("setrixtui").join(FILENAME))
+    Ok(crate::config::config_dir().join(FILENAME))
 }
 
 /// Load high scores from disk. Returns (endless, timed, clear); 0 on missing/parse error.
@@ -67,3 +57,30 @@ pub fn save_high_scores(endless: u32, timed: u32, clear: u32) -> Result<()> {
     writeln!(f, "{}", clear)?;
     Ok(())
 }
+
+/// Load the custom mode high scores, by mode name. Empty on missing file; bad lines are skipped.
+pub fn load_custom_high_scores() -> BTreeMap<String, u32> {
+    let Ok(content) = fs::read_to_string(crate::config::config_dir().join(CUSTOM_FILENAME)) else {
+        return BTreeMap::new();
+    };
+    content
+        .lines()
+        .filter_map(|line| {
+            let (name, score) = line.rsplit_once('\t')?;
+            Some((name.to_string(), score.trim().parse().ok()?))
+        })
+        .collect()
+}
+
+/// Save the custom mode high scores. Creates config directory if needed.
+pub fn save_custom_high_scores(scores: &BTreeMap<String, u32>) -> Result<()> {
+    let path = crate::config::config_dir().join(CUSTOM_FILENAME);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut f = fs::File::create(path)?;
+    for (name, score) in scores {
+        writeln!(f, "{name}\t{score}")?;
+    }
+    Ok(())
+}
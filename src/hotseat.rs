@@ -0,0 +1,65 @@
+//! Hot seat (`--hot-seat`): players take turns at one keyboard, one game each, and the
+//! standings screen between games adds up their scores round by round.
+
+/// One player's name and the score of each game played.
+#[derive(Debug, Clone)]
+pub struct Player {
+    pub name: String,
+    pub scores: Vec<u32>,
+}
+
+impl Player {
+    pub fn total(&self) -> u32 {
+        self.scores.iter().sum()
+    }
+}
+
+/// Players in turn order and whose turn it is.
+#[derive(Debug, Clone)]
+pub struct HotSeat {
+    pub players: Vec<Player>,
+    turn: usize,
+}
+
+impl HotSeat {
+    pub fn new(names: &[String]) -> Self {
+        Self {
+            players: names
+                .iter()
+                .map(|name| Player {
+                    name: name.trim().to_string(),
+                    scores: Vec::new(),
+                })
+                .collect(),
+            turn: 0,
+        }
+    }
+
+    /// The player at the keyboard (or next up, between games).
+    pub fn current(&self) -> &str {
+        &self.players[self.turn].name
+    }
+
+    /// True when the next game opens a round (everyone has played the same number of games).
+    pub const fn round_start(&self) -> bool {
+        self.turn == 0
+    }
+
+    /// The round being played, from 1.
+    pub fn round(&self) -> usize {
+        self.players[0].scores.len() + usize::from(self.turn == 0)
+    }
+
+    /// The current player's game ended with `score`: pass the keyboard on.
+    pub fn record(&mut self, score: u32) {
+        self.players[self.turn].scores.push(score);
+        self.turn = (self.turn + 1) % self.players.len();
+    }
+
+    /// Players by total score, best first (turn order among equals).
+    pub fn standings(&self) -> Vec<&Player> {
+        let mut standings: Vec<&Player> = self.players.iter().collect();
+        standings.sort_by_key(|player| std::cmp::Reverse(player.total()));
+        standings
+    }
+}
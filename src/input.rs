@@ -1,6 +1,13 @@
-//! Key bindings: normal and vim-style.
+//! Key bindings: menu keys (normal and vim-style) and the in-game profiles with per-key overrides.
 
+use crate::KeyProfile;
+use anyhow::{Result, bail};
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// Presses of both rotate keys closer together than this form a chord.
+const CHORD_WINDOW_MS: u64 = 50;
 
 /// Action from a key press.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -11,17 +18,331 @@ pub enum Action {
     RotateCcw,
     SoftDrop,
     HardDrop,
+    /// Swap the falling piece for the next one, for points.
+    Reroll,
+    /// Spend a full focus meter on slow motion.
+    Focus,
+    /// Spend a full zone meter on stopped time.
+    Zone,
     Pause,
     Quit,
+    /// Start over at once with a fresh seed.
+    Restart,
+    /// Start over at once with the same seed.
+    Retry,
     None,
 }
 
-/// Map key event to game action. Supports both normal (arrows, space) and vim (hjkl, etc.).
+/// Which actions auto-repeat while held, and how often (ms between repeats; None = no repeat).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RepeatPolicy {
+    /// Left / Right (ARR).
+    pub movement: Option<u64>,
+    /// Soft drop.
+    pub soft_drop: Option<u64>,
+    /// Rotate CW / CCW.
+    pub rotation: Option<u64>,
+}
+
+impl Default for RepeatPolicy {
+    fn default() -> Self {
+        Self {
+            movement: Some(38),
+            soft_drop: Some(38),
+            rotation: None,
+        }
+    }
+}
+
+impl RepeatPolicy {
+    /// Repeat interval for `action`, or None if it fires once per press.
+    pub const fn interval_ms(&self, action: Action) -> Option<u64> {
+        match action {
+            Action::MoveLeft | Action::MoveRight => self.movement,
+            Action::SoftDrop => self.soft_drop,
+            Action::RotateCw | Action::RotateCcw => self.rotation,
+            Action::HardDrop
+            | Action::Reroll
+            | Action::Focus
+            | Action::Zone
+            | Action::Pause
+            | Action::Quit
+            | Action::Restart
+            | Action::Retry
+            | Action::None => None,
+        }
+    }
+}
+
+/// Two in-game keys pressed together (see `ChordDetector`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Chord {
+    /// Both rotate keys at once: the second press rotates again in the first one's direction
+    /// instead of undoing it, 180° in total.
+    Rotate180(Action),
+    /// Left and Right held together: stop shifting (cancels DAS) until one is pressed again.
+    CancelShift,
+}
+
+/// Chord detection between key presses and `Action`s. Fed every in-game press; the press that
+/// completes a chord is replaced by the chord.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ChordDetector {
+    /// Last rotate press, waiting for the other rotate key.
+    last_rotate: Option<(Action, Instant)>,
+}
+
+impl ChordDetector {
+    /// The chord `action` completes, if any. `held` is the direction key currently held down.
+    pub fn press(&mut self, action: Action, now: Instant, held: Option<Action>) -> Option<Chord> {
+        match action {
+            Action::RotateCw | Action::RotateCcw => {
+                let window = Duration::from_millis(CHORD_WINDOW_MS);
+                match self.last_rotate.take() {
+                    Some((first, at)) if first != action && now.duration_since(at) <= window => {
+                        Some(Chord::Rotate180(first))
+                    }
+                    _ => {
+                        self.last_rotate = Some((action, now));
+                        None
+                    }
+                }
+            }
+            Action::MoveLeft | Action::MoveRight => held
+                .is_some_and(|h| h != action && matches!(h, Action::MoveLeft | Action::MoveRight))
+                .then_some(Chord::CancelShift),
+            _ => None,
+        }
+    }
+}
+
+/// In-game key bindings: a profile plus per-key overrides from the config file.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Keymap {
+    pub profile: KeyProfile,
+    /// Override keys (lowercased chars, with no modifier, Ctrl or Ctrl+Shift) checked before
+    /// the profile and the Ctrl bindings; `Action::None` unbinds.
+    overrides: HashMap<(KeyModifiers, KeyCode), Action>,
+}
+
+impl Keymap {
+    /// Build from a profile and config `"bind"` entries (key name → action name).
+    pub fn new(profile: KeyProfile, bind: &HashMap<String, String>) -> Result<Self> {
+        let mut overrides = HashMap::new();
+        for (key, action) in bind {
+            let Some(key_code) = parse_key(key) else {
+                bail!("unknown key {key:?}");
+            };
+            let Some(action) = parse_action(action) else {
+                bail!("unknown action {action:?} for key {key:?}");
+            };
+            overrides.insert(key_code, action);
+        }
+        Ok(Self { profile, overrides })
+    }
+
+    /// Map a key event during play. Shift is ignored (so Shift+Z is Z); Ctrl pauses and restarts.
+    pub fn action(&self, key: KeyEvent) -> Action {
+        let KeyEvent {
+            code, modifiers, ..
+        } = key;
+        // Windows reports AltGr characters (e.g. `,` or `.` on some layouts) as Ctrl+Alt
+        let altgr = KeyModifiers::CONTROL | KeyModifiers::ALT;
+        let modifiers = if cfg!(windows) && modifiers == altgr && matches!(code, KeyCode::Char(_)) {
+            KeyModifiers::NONE
+        } else {
+            modifiers
+        };
+        let code = match code {
+            KeyCode::Char(c) => KeyCode::Char(c.to_ascii_lowercase()),
+            other => other,
+        };
+        let ctrl_shift = KeyModifiers::CONTROL | KeyModifiers::SHIFT;
+        if modifiers == KeyModifiers::CONTROL || modifiers == ctrl_shift {
+            return self
+                .overrides
+                .get(&(modifiers, code))
+                .copied()
+                .unwrap_or_else(|| key_to_action(key));
+        }
+        if !modifiers.is_empty() && modifiers != KeyModifiers::SHIFT {
+            return Action::None;
+        }
+        if let Some(&action) = self.overrides.get(&(KeyModifiers::NONE, code)) {
+            return action;
+        }
+        profile_action(self.profile, code)
+    }
+
+    /// Name of a key bound to `action` (the first found among the usual keys), for hints.
+    pub fn key_name(&self, action: Action) -> String {
+        let named = [
+            (KeyCode::Left, "←"),
+            (KeyCode::Right, "→"),
+            (KeyCode::Up, "↑"),
+            (KeyCode::Down, "↓"),
+            (KeyCode::Char(' '), "Space"),
+            (KeyCode::Enter, "Enter"),
+            (KeyCode::Tab, "Tab"),
+            (KeyCode::Esc, "Esc"),
+        ];
+        let chars = "abcdefghijklmnopqrstuvwxyz,./".chars().map(|c| {
+            let name = c.to_ascii_uppercase().to_string();
+            (KeyCode::Char(c), name)
+        });
+        named
+            .into_iter()
+            .map(|(code, name)| (code, name.to_string()))
+            .chain(chars)
+            .find(|&(code, _)| {
+                self.overrides
+                    .get(&(KeyModifiers::NONE, code))
+                    .copied()
+                    .unwrap_or_else(|| profile_action(self.profile, code))
+                    == action
+            })
+            .map_or_else(|| "(unbound)".to_string(), |(_, name)| name)
+    }
+}
+
+/// Profile bindings. Esc quits, P pauses, C rerolls, F focuses and Tab enters the zone in every
+/// profile.
+const fn profile_action(profile: KeyProfile, code: KeyCode) -> Action {
+    match (profile, code) {
+        (_, KeyCode::Esc) => Action::Quit,
+        (_, KeyCode::Char('p')) => Action::Pause,
+        (_, KeyCode::Char('c')) => Action::Reroll,
+        (_, KeyCode::Char('f')) => Action::Focus,
+        (_, KeyCode::Tab) => Action::Zone,
+        (KeyProfile::Classic, _) => match code {
+            KeyCode::Char('q') => Action::Quit,
+            KeyCode::Left | KeyCode::Char('h') => Action::MoveLeft,
+            KeyCode::Right | KeyCode::Char('l') => Action::MoveRight,
+            KeyCode::Up | KeyCode::Char('k' | 'i') => Action::RotateCw,
+            KeyCode::Char('u') => Action::RotateCcw,
+            KeyCode::Down | KeyCode::Char('j') => Action::SoftDrop,
+            KeyCode::Enter | KeyCode::Char(' ') => Action::HardDrop,
+            _ => Action::None,
+        },
+        (KeyProfile::Guideline, _) => match code {
+            KeyCode::Char('q') => Action::Quit,
+            KeyCode::Left => Action::MoveLeft,
+            KeyCode::Right => Action::MoveRight,
+            KeyCode::Up | KeyCode::Char('x') => Action::RotateCw,
+            KeyCode::Char('z') => Action::RotateCcw,
+            KeyCode::Down => Action::SoftDrop,
+            KeyCode::Char(' ') => Action::HardDrop,
+            _ => Action::None,
+        },
+        (KeyProfile::Vim, _) => match code {
+            KeyCode::Char('q') => Action::Quit,
+            KeyCode::Char('h') => Action::MoveLeft,
+            KeyCode::Char('l') => Action::MoveRight,
+            KeyCode::Char('k') => Action::RotateCw,
+            KeyCode::Char('u') => Action::RotateCcw,
+            KeyCode::Char('j') => Action::SoftDrop,
+            KeyCode::Char(' ') | KeyCode::Enter => Action::HardDrop,
+            _ => Action::None,
+        },
+        (KeyProfile::Wasd, _) => match code {
+            KeyCode::Char('a') => Action::MoveLeft,
+            KeyCode::Char('d') => Action::MoveRight,
+            KeyCode::Char('e') => Action::RotateCw,
+            KeyCode::Char('q') => Action::RotateCcw,
+            KeyCode::Char('s') => Action::SoftDrop,
+            KeyCode::Char('w' | ' ') => Action::HardDrop,
+            _ => Action::None,
+        },
+        (KeyProfile::LeftHanded, _) => match code {
+            KeyCode::Char('a') => Action::MoveLeft,
+            KeyCode::Char('d') => Action::MoveRight,
+            KeyCode::Char('.') => Action::RotateCw,
+            KeyCode::Char(',') => Action::RotateCcw,
+            KeyCode::Char('s') => Action::SoftDrop,
+            KeyCode::Char(' ') => Action::HardDrop,
+            KeyCode::Char('q') => Action::Quit,
+            _ => Action::None,
+        },
+    }
+}
+
+/// Co-op: player 2's fixed keys, checked before the keymap. Arrows move, rotate (Up) and soft
+/// drop (Down), `/` rotates counter-clockwise and Enter hard drops.
+pub const fn partner_action(code: KeyCode) -> Option<Action> {
+    Some(match code {
+        KeyCode::Left => Action::MoveLeft,
+        KeyCode::Right => Action::MoveRight,
+        KeyCode::Up => Action::RotateCw,
+        KeyCode::Char('/') => Action::RotateCcw,
+        KeyCode::Down => Action::SoftDrop,
+        KeyCode::Enter => Action::HardDrop,
+        _ => return None,
+    })
+}
+
+/// Key name from the config file: a single character or left/right/up/down/space/enter/tab/backspace,
+/// optionally after `ctrl-` or `ctrl-shift-`.
+fn parse_key(name: &str) -> Option<(KeyModifiers, KeyCode)> {
+    let lower = name.to_ascii_lowercase();
+    let (modifiers, name) = if let Some(rest) = lower.strip_prefix("ctrl-shift-") {
+        (KeyModifiers::CONTROL | KeyModifiers::SHIFT, rest)
+    } else if let Some(rest) = lower.strip_prefix("ctrl-") {
+        (KeyModifiers::CONTROL, rest)
+    } else {
+        (KeyModifiers::NONE, lower.as_str())
+    };
+    let mut chars = name.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Some((modifiers, KeyCode::Char(c)));
+    }
+    let code = match name {
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "space" => KeyCode::Char(' '),
+        "enter" => KeyCode::Enter,
+        "tab" => KeyCode::Tab,
+        "backspace" => KeyCode::Backspace,
+        _ => return None,
+    };
+    Some((modifiers, code))
+}
+
+/// Action name from the config file; "none" unbinds the key.
+pub fn parse_action(name: &str) -> Option<Action> {
+    Some(match name.to_ascii_lowercase().replace('_', "-").as_str() {
+        "left" | "move-left" => Action::MoveLeft,
+        "right" | "move-right" => Action::MoveRight,
+        "rotate-cw" => Action::RotateCw,
+        "rotate-ccw" => Action::RotateCcw,
+        "soft-drop" => Action::SoftDrop,
+        "hard-drop" => Action::HardDrop,
+        "reroll" => Action::Reroll,
+        "focus" => Action::Focus,
+        "zone" => Action::Zone,
+        "pause" => Action::Pause,
+        "quit" => Action::Quit,
+        "restart" => Action::Restart,
+        "retry" => Action::Retry,
+        "none" => Action::None,
+        _ => return None,
+    })
+}
+
+/// Map key event to a menu action. Supports both normal (arrows, space) and vim (hjkl, etc.).
+/// Also the in-game Ctrl bindings.
 pub fn key_to_action(key: KeyEvent) -> Action {
     let KeyEvent {
         code, modifiers, ..
     } = key;
     let no_mod = modifiers.is_empty() || modifiers == KeyModifiers::SHIFT;
+    if modifiers == KeyModifiers::CONTROL | KeyModifiers::SHIFT {
+        return match code {
+            KeyCode::Char('r' | 'R') => Action::Retry,
+            _ => Action::None,
+        };
+    }
     if !no_mod && modifiers != KeyModifiers::CONTROL {
         return Action::None;
     }
@@ -29,6 +350,7 @@ pub fn key_to_action(key: KeyEvent) -> Action {
         KeyCode::Char('q') | KeyCode::Esc if no_mod => Action::Quit,
         KeyCode::Char('p' | ' ') if modifiers == KeyModifiers::CONTROL => Action::Pause,
         KeyCode::Char('p') if no_mod => Action::Pause,
+        KeyCode::Char('r') if modifiers == KeyModifiers::CONTROL => Action::Restart,
         KeyCode::Left | KeyCode::Char('h') if no_mod => Action::MoveLeft,
         KeyCode::Right | KeyCode::Char('l') if no_mod => Action::MoveRight,
         KeyCode::Up | KeyCode::Char('k') if no_mod => Action::RotateCw,
@@ -0,0 +1,103 @@
+//! `--debug-invariants`: grain counts around every sand tick, lock and clear. Sand that appears or
+//! vanishes without a lock or clear to account for it stops the game with a dump of the cells
+//! that changed, so a grain loss can be reported from the game where it happened. The column
+//! tops that hard drops trust (`Playfield::column_top`) are checked against the board too.
+
+use crate::game::{Cell, FrozenGrain, Playfield};
+use std::fmt::Write;
+
+/// Changed cells listed in the dump; the rest are only counted.
+const DUMP_CELLS: usize = 48;
+
+/// The board (sand and frozen grains) before one step of the game, to compare with after it.
+#[derive(Debug, Clone)]
+pub struct GrainCheck {
+    step: &'static str,
+    playfield: Playfield,
+    frozen: Vec<FrozenGrain>,
+}
+
+impl GrainCheck {
+    pub fn new(step: &'static str, playfield: &Playfield, frozen: &[FrozenGrain]) -> Self {
+        Self {
+            step,
+            playfield: playfield.clone(),
+            frozen: frozen.to_vec(),
+        }
+    }
+
+    /// The step should have brought in `added` grains and taken `removed` away, and left every
+    /// column top right. Panics with the dump otherwise (the app's panic hook restores the
+    /// terminal first).
+    pub fn finish(
+        self,
+        playfield: &Playfield,
+        frozen: &[FrozenGrain],
+        added: usize,
+        removed: usize,
+    ) {
+        let before = self.playfield.grain_count() + self.frozen.len();
+        let after = playfield.grain_count() + frozen.len();
+        assert!(
+            before + added == after + removed,
+            "{}",
+            self.dump(playfield, frozen, added, removed)
+        );
+        let (gw, gh) = playfield.grain_dims();
+        for x in 0..gw {
+            let top = (0..gh).find(|&y| playfield.get(x, y).is_some_and(Cell::is_solid));
+            assert!(
+                playfield.column_top(x) == top,
+                "column top of grain column {x} out of date after {}: kept {:?}, board {top:?}",
+                self.step,
+                playfield.column_top(x),
+            );
+        }
+    }
+
+    fn dump(
+        &self,
+        playfield: &Playfield,
+        frozen: &[FrozenGrain],
+        added: usize,
+        removed: usize,
+    ) -> String {
+        let before = self.playfield.grain_count() + self.frozen.len();
+        let after = playfield.grain_count() + frozen.len();
+        let mut out = format!(
+            "grain count broken by {} (sand tick {}): {before} grains + {added} - {removed} \
+             should leave {}, found {after}\n",
+            self.step,
+            playfield.tick_count,
+            (before + added).saturating_sub(removed),
+        );
+        let buried = self
+            .frozen
+            .iter()
+            .filter(|fg| self.playfield.get(fg.x, fg.y).is_some_and(Cell::is_solid))
+            .count();
+        let _ = writeln!(
+            out,
+            "frozen grains: {} -> {} ({buried} sharing a cell with sand before)",
+            self.frozen.len(),
+            frozen.len()
+        );
+        let (gw, gh) = playfield.grain_dims();
+        let changed: Vec<(usize, usize, Cell, Cell)> = (0..gh)
+            .flat_map(|y| (0..gw).map(move |x| (x, y)))
+            .filter_map(|(x, y)| {
+                let was = self.playfield.get(x, y).unwrap_or(Cell::Empty);
+                let is = playfield.get(x, y).unwrap_or(Cell::Empty);
+                (was != is).then_some((x, y, was, is))
+            })
+            .collect();
+        let _ = writeln!(out, "changed cells (x, y): {}", changed.len());
+        for (x, y, was, is) in changed.iter().take(DUMP_CELLS) {
+            let _ = writeln!(out, "  ({x}, {y}) {was:?} -> {is:?}");
+        }
+        if changed.len() > DUMP_CELLS {
+            let _ = writeln!(out, "  ... {} more", changed.len() - DUMP_CELLS);
+        }
+        out
+    }
+}
@@ -0,0 +1,931 @@
+//! Setrixtui — Setris/Sandtrix-style falling-sand puzzle game in the terminal. The game lives
+//! in this library; `main.rs` parses the command line and runs it. `sim::simulate` plays
+//! headless games for bots, tools and tests.
+
+mod a11y;
+pub mod app;
+mod audio;
+pub mod clipboard;
+pub mod config;
+mod custom;
+mod diagnostics;
+mod difficulty;
+pub mod doctor;
+pub mod dump;
+mod feed;
+mod game;
+mod highscores;
+mod hotseat;
+mod input;
+mod invariants;
+pub mod logging;
+mod missions;
+mod records;
+mod remote;
+mod rewind;
+mod screenshot;
+pub mod sim;
+mod splits;
+mod stream;
+pub mod theme;
+mod title;
+mod tutorial;
+mod ui;
+mod view;
+mod autoplay;
+
+use clap::{ArgAction, Parser, Subcommand, ValueEnum};
+
+/// Options derived from CLI that affect game behaviour (spawn delay, lock delay, sand settle, etc.).
+#[derive(Debug, Clone)]
+pub struct GameConfig {
+    pub spawn_delay_ms: u64,
+    pub initial_level: u32,
+    pub lock_delay_ms: u64,
+    /// Instant gravity: pieces spawn resting on the stack.
+    pub twenty_g: bool,
+    /// Settled sand fades out a second after it last moved.
+    pub invisible: bool,
+    /// Pieces and spawn positions flipped left to right (`--mirror`).
+    pub mirror: bool,
+    /// The board's left and right edges join (`--cylinder`).
+    pub cylinder: bool,
+    /// Two players share the board, each with a falling piece, against a rising garbage tide.
+    pub coop: bool,
+    /// Two narrow boards side by side, the pieces taking turns on them (`--dual`).
+    pub dual: bool,
+    /// Seed every game starts from (`--seed`, the menu); None = a fresh random one each game.
+    pub seed: Option<u64>,
+    pub sand_settle: bool,
+    pub relaxed: bool,
+    /// Sand colours (`--colors`, `--high-color`); None = the difficulty's.
+    pub colors: Option<game::ColorScheme>,
+    pub two_tone: bool,
+    pub rainbow: bool,
+    pub bombs: bool,
+    pub converters: bool,
+    /// Piece set; None = by difficulty (`PieceSet::default_for`).
+    pub pieces: Option<PieceSet>,
+    /// No kind goes more than `game::DROUGHT_LIMIT` pieces without being dealt.
+    pub anti_drought: bool,
+    /// Main piece colours dealt evenly, with short same-colour streaks.
+    pub balance_colors: bool,
+    /// How queued pieces get their main colour (`--color-assign`).
+    pub color_assign: ColorAssign,
+    /// Block rows of stone at the bottom at game start (dig mode); 0 = none.
+    pub dig_rows: u16,
+    /// Block rows of random sand at game start, above any stone (`--start-garbage`).
+    pub start_garbage: u16,
+    /// The first bag deals no S or Z (`--start-clean-streak`).
+    pub start_clean_streak: bool,
+    /// Sand material (`--sand-type`); None = the difficulty's.
+    pub sand_type: Option<SandType>,
+    /// Sand settles without randomness (`--reduced-motion`, see `game::PhysicsParams::steady`).
+    pub steady_sand: bool,
+    /// Check the grain count around every sand tick, lock and clear (`--debug-invariants`).
+    pub debug_invariants: bool,
+    /// Global speed multiplier (`--speed`): scales gravity and sand; the lock and spawn delays
+    /// above are already stretched by its inverse.
+    pub speed: f64,
+    pub difficulty: Difficulty,
+    /// Built-in mode; Endless plays breathers (see `game::BREATHER_TICKS`).
+    pub mode: GameMode,
+    /// Difficulty presets, with the config file's overrides.
+    pub profiles: difficulty::DifficultyProfiles,
+    /// Custom mode being played (picked on the menu); None = the built-in mode's rules.
+    pub custom: Option<custom::CustomMode>,
+    /// Custom modes saved in the config file, for the menu.
+    pub custom_modes: Vec<custom::CustomMode>,
+    pub combo: ComboConfig,
+    pub scoring: ScoringConfig,
+    /// Sound effect volume in percent (0 = muted). Only used with the `audio` feature.
+    pub volume: u8,
+    /// Background music (`audio` feature).
+    pub music: MusicTrack,
+    /// In-game key bindings (profile plus config overrides).
+    pub keys: input::Keymap,
+    /// Points a reroll (swap the piece for the next one) costs.
+    pub reroll_cost: u32,
+    /// Guard against accidental hard drops from the keyboard.
+    pub hard_drop_safety: HardDropSafety,
+    /// Key repeat: DAS and the per-action repeat policy.
+    pub handling: HandlingConfig,
+}
+
+impl GameConfig {
+    /// Game options from the command line and the config file (flags win).
+    pub fn new(args: &Args, file_config: config::FileConfig) -> Self {
+        let default_combo = ComboConfig::default();
+        let combo = ComboConfig {
+            timer_ticks: args
+                .combo_ticks
+                .or(file_config.combo.timer_ticks)
+                .unwrap_or(default_combo.timer_ticks)
+                .max(if args.tutorial {
+                    tutorial::COMBO_TICKS
+                } else {
+                    1
+                }),
+            max_multiplier: args
+                .combo_max
+                .or(file_config.combo.max_multiplier)
+                .unwrap_or(default_combo.max_multiplier)
+                .max(1),
+            decay: args
+                .combo_decay
+                .or(file_config.combo.decay)
+                .unwrap_or(default_combo.decay),
+        };
+        let default_scoring = ScoringConfig::default();
+        let file_scoring = &file_config.scoring;
+        let scoring = ScoringConfig {
+            grain_points: file_scoring
+                .grain_points
+                .unwrap_or(default_scoring.grain_points),
+            soft_drop_points: file_scoring
+                .soft_drop_points
+                .unwrap_or(default_scoring.soft_drop_points),
+            hard_drop_points: file_scoring
+                .hard_drop_points
+                .unwrap_or(default_scoring.hard_drop_points),
+            combo_step: file_scoring
+                .combo_step
+                .unwrap_or(default_scoring.combo_step),
+            lines_per_level: file_scoring
+                .lines_per_level
+                .unwrap_or(default_scoring.lines_per_level)
+                .max(1),
+        };
+        let default_handling = HandlingConfig::default();
+        let file_repeat = &file_config.handling.repeat;
+        let handling = HandlingConfig {
+            das_ms: args
+                .das
+                .or(file_config.handling.das_ms)
+                .unwrap_or(default_handling.das_ms),
+            repeat: input::RepeatPolicy {
+                movement: args.arr.map(Some).unwrap_or_else(|| {
+                    config::RepeatSetting::resolve(
+                        file_repeat.movement,
+                        file_config.handling.arr_ms,
+                        default_handling.repeat.movement,
+                    )
+                }),
+                soft_drop: args.soft_drop_ms.map(Some).unwrap_or_else(|| {
+                    config::RepeatSetting::resolve(
+                        file_repeat.soft_drop,
+                        file_config.handling.soft_drop_ms,
+                        default_handling.repeat.soft_drop,
+                    )
+                }),
+                rotation: config::RepeatSetting::resolve(
+                    file_repeat.rotation,
+                    None,
+                    default_handling.repeat.rotation,
+                ),
+            },
+        };
+        // Co-op: the arrows belong to player 2
+        let key_profile = args
+            .keys
+            .or(if args.coop {
+                Some(KeyProfile::Wasd)
+            } else {
+                file_config.keys.profile
+            })
+            .unwrap_or_default();
+        let keys = input::Keymap::new(key_profile, &file_config.keys.bind).unwrap_or_else(|e| {
+            eprintln!("setrixtui: ignoring key bindings from config file: {e:#}");
+            input::Keymap::new(key_profile, &std::collections::HashMap::new()).unwrap_or_default()
+        });
+        let speed = args
+            .speed
+            .unwrap_or(if args.assist { ASSIST_SPEED } else { 1.0 });
+        // Timers run slower (longer) at lower speeds
+        let scale_ms = |ms: u64| (ms as f64 / speed).round() as u64;
+        Self {
+            spawn_delay_ms: scale_ms(args.spawn_delay_ms.unwrap_or(0)),
+            initial_level: args.initial_level,
+            lock_delay_ms: scale_ms(args.lock_delay_ms.unwrap_or(if args.assist {
+                ASSIST_LOCK_DELAY_MS
+            } else {
+                120
+            })),
+            twenty_g: args.twenty_g,
+            invisible: args.invisible,
+            mirror: args.mirror,
+            cylinder: args.cylinder,
+            coop: args.coop,
+            dual: args.dual,
+            seed: args.seed,
+            sand_settle: args.sand_settle,
+            relaxed: args.relaxed,
+            colors: args
+                .colors
+                .or_else(|| args.high_color.then_some(6))
+                .or_else(|| args.assist.then_some(4))
+                .map(game::ColorScheme::new),
+            two_tone: args.two_tone,
+            rainbow: args.rainbow,
+            bombs: args.bombs,
+            converters: args.converters,
+            dig_rows: args.dig_rows,
+            start_garbage: args.start_garbage,
+            start_clean_streak: args.start_clean_streak,
+            pieces: args.pieces,
+            anti_drought: args.anti_drought,
+            balance_colors: args.balance_colors,
+            color_assign: args.color_assign,
+            sand_type: args.sand_type,
+            steady_sand: args.reduced_motion,
+            debug_invariants: args.debug_invariants,
+            speed,
+            difficulty: args.difficulty,
+            mode: args.mode,
+            profiles: difficulty::DifficultyProfiles::from_file(&file_config.difficulty),
+            custom: None,
+            custom_modes: file_config.custom_modes,
+            combo,
+            scoring,
+            volume: if args.mute {
+                0
+            } else {
+                args.volume
+                    .or(file_config.audio.volume)
+                    .unwrap_or(70)
+                    .min(100)
+            },
+            music: args.music.or(file_config.audio.music).unwrap_or_default(),
+            keys,
+            hard_drop_safety: args.hard_drop_safety,
+            reroll_cost: args.reroll_cost,
+            handling,
+        }
+    }
+
+    /// Preset for the selected difficulty.
+    pub const fn profile(&self) -> &difficulty::DifficultyProfile {
+        self.profiles.get(self.difficulty)
+    }
+
+    /// Sand colours in play: the custom mode's, else `--colors`, else the difficulty's.
+    pub fn colors(&self) -> game::ColorScheme {
+        match &self.custom {
+            Some(custom) => game::ColorScheme::new(custom.colors),
+            None => self
+                .colors
+                .unwrap_or_else(|| game::ColorScheme::new(self.profile().colors)),
+        }
+    }
+
+    /// Block rows of random sand at the start: the custom mode's, else `--start-garbage`.
+    pub fn start_garbage(&self) -> u16 {
+        self.custom
+            .as_ref()
+            .map_or(self.start_garbage, |custom| custom.start_garbage)
+    }
+
+    /// The first bag skips S and Z: the custom mode's choice, else `--start-clean-streak`.
+    pub fn start_clean_streak(&self) -> bool {
+        self.custom
+            .as_ref()
+            .map_or(self.start_clean_streak, |custom| custom.start_clean_streak)
+    }
+
+    /// Sand material: the custom mode's, else `--sand-type`, else the difficulty's.
+    pub fn sand(&self) -> SandType {
+        self.custom.as_ref().map_or_else(
+            || self.sand_type.unwrap_or_else(|| self.profile().sand),
+            |custom| custom.sand,
+        )
+    }
+}
+
+/// Key handling: how held keys repeat. All values in ms.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HandlingConfig {
+    /// DAS (Delayed Auto-Shift): delay before a held key starts repeating.
+    pub das_ms: u64,
+    /// Which actions repeat after DAS and how fast (ARR for movement).
+    pub repeat: input::RepeatPolicy,
+}
+
+impl Default for HandlingConfig {
+    fn default() -> Self {
+        Self {
+            das_ms: 80,
+            repeat: input::RepeatPolicy::default(),
+        }
+    }
+}
+
+impl HandlingConfig {
+    /// Labels of the values shown on the settings screen, in `field` order.
+    pub const FIELDS: [&'static str; 4] = ["DAS", "ARR", "Soft drop", "Rotate"];
+    /// Settings screen: Left/Right step and upper bound (ms).
+    const STEP_MS: u64 = 5;
+    const MAX_MS: u64 = 500;
+
+    /// Value for settings row `i` (see `FIELDS`); None = that action doesn't repeat.
+    pub const fn field(&self, i: usize) -> Option<u64> {
+        match i {
+            0 => Some(self.das_ms),
+            1 => self.repeat.movement,
+            2 => self.repeat.soft_drop,
+            _ => self.repeat.rotation,
+        }
+    }
+
+    /// Step settings row `i` up or down. Repeat rows turn off below 0 and back on above it.
+    pub fn adjust(&mut self, i: usize, up: bool) {
+        let step = |v: Option<u64>| match (v, up) {
+            (Some(ms), true) => Some((ms + Self::STEP_MS).min(Self::MAX_MS)),
+            (Some(0) | None, false) => None,
+            (Some(ms), false) => Some(ms.saturating_sub(Self::STEP_MS)),
+            (None, true) => Some(0),
+        };
+        match i {
+            0 => self.das_ms = step(Some(self.das_ms)).unwrap_or(0),
+            1 => self.repeat.movement = step(self.repeat.movement),
+            2 => self.repeat.soft_drop = step(self.repeat.soft_drop),
+            _ => self.repeat.rotation = step(self.repeat.rotation),
+        }
+    }
+}
+
+/// Scoring rules (config file `scoring` table).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScoringConfig {
+    /// Points per cleared grain, before the combo multiplier.
+    pub grain_points: u32,
+    /// Points per grain a soft drop moves the piece.
+    pub soft_drop_points: u32,
+    /// Points per block row a hard drop skips.
+    pub hard_drop_points: u32,
+    /// How much each clear raises the combo multiplier.
+    pub combo_step: u32,
+    /// Lines per level.
+    pub lines_per_level: u32,
+}
+
+impl Default for ScoringConfig {
+    fn default() -> Self {
+        Self {
+            grain_points: 1,
+            soft_drop_points: 1,
+            hard_drop_points: 2,
+            combo_step: 1,
+            lines_per_level: 10,
+        }
+    }
+}
+
+/// Combo tuning: how long the combo window stays open, the multiplier cap, and what happens when it expires.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ComboConfig {
+    /// Ticks the combo window stays open after a clear.
+    pub timer_ticks: u32,
+    /// Highest combo multiplier.
+    pub max_multiplier: u32,
+    pub decay: ComboDecay,
+}
+
+impl Default for ComboConfig {
+    fn default() -> Self {
+        Self {
+            timer_ticks: 90,
+            max_multiplier: 10,
+            decay: ComboDecay::Reset,
+        }
+    }
+}
+
+/// Assist preset (`--assist`): speed multiplier and lock delay (before scaling by the speed).
+const ASSIST_SPEED: f64 = 0.5;
+const ASSIST_LOCK_DELAY_MS: u64 = 300;
+
+/// `--speed` parser: a multiplier in 0.25..=2.0.
+fn parse_speed(s: &str) -> Result<f64, String> {
+    let speed: f64 = s.parse().map_err(|e| format!("{e}"))?;
+    if (0.25..=2.0).contains(&speed) {
+        Ok(speed)
+    } else {
+        Err(format!("{speed} is not in 0.25..=2.0"))
+    }
+}
+
+/// Seed parser (`--seed`, the menu, the clipboard): up to 16 hex digits.
+pub fn parse_seed(s: &str) -> Result<u64, String> {
+    let s = s.trim();
+    let s = s.strip_prefix("0x").unwrap_or(s);
+    u64::from_str_radix(s, 16).map_err(|e| format!("{s:?} is not a hex seed: {e}"))
+}
+
+/// Setris/Sandtrix-style puzzle game in the terminal.
+#[derive(Debug, Parser)]
+#[command(
+    name = "setrixtui",
+    version,
+    about = "Setris/Sandtrix-style falling-sand puzzle in the terminal. Blocks turn into sand; clear full horizontal lines to score.",
+    long_about = "Setrixtui is a terminal puzzle game inspired by Setris and Sandtrix.\n\n\
+        Place falling coloured blocks. When they lock, they turn into sand. Clear horizontal \
+        lines (one colour edge-to-edge) to score; remaining sand falls with gravity.\n\n\
+        CONTROLS (normal):\n  Left/Right  Move    Up        Rotate CW   Down       Soft drop\n  Enter/Space Hard drop   P          Pause      Q / Esc    Quit\n\n\
+        CONTROLS (vim):\n  h/l         Move    k or i     Rotate CW   u          Rotate CCW\n  j           Soft drop  Space      Hard drop  p          Pause   q  Quit\n\n\
+        Hold a movement key to keep the piece moving. Use --theme to load a btop-style theme (e.g. onedark.theme).",
+    disable_help_flag = true,
+)]
+pub struct Args {
+    /// Game mode: endless (play until game over), timed (score in time limit), or clear40 (clear 40 lines then keep going until fail).
+    #[arg(short, long, default_value = "endless")]
+    pub mode: GameMode,
+
+    /// Difficulty: easy (normal speed), medium (faster), hard (fast + narrower). Affects gravity and playfield (medium −1 column; hard −2 columns, −2 rows).
+    #[arg(short, long, default_value = "easy")]
+    pub difficulty: Difficulty,
+
+    /// Path to theme file (btop-style theme[key]=\"value\"). Uses One Dark if not set.
+    #[arg(short, long, value_name = "FILE")]
+    pub theme: Option<std::path::PathBuf>,
+
+    /// Playfield width in columns (grid cells). Defaulting to 10 for 1080p compatibility.
+    #[arg(long, default_value = "10", value_name = "COLS")]
+    pub width: u16,
+
+    /// Playfield height in rows (grid cells).
+    #[arg(long, default_value = "24", value_name = "ROWS")]
+    pub height: u16,
+
+    /// In mode 'clear40': goal lines (reach this then keep going until fail). Default 40.
+    #[arg(long, default_value = "40", value_name = "N")]
+    pub clear_lines: u32,
+
+    /// In mode 'timed': time limit in seconds.
+    #[arg(long, default_value = "180", value_name = "SECS")]
+    pub time_limit: u32,
+
+    /// Disable line-clear animation (instant clear + gravity).
+    #[arg(long)]
+    pub no_animation: bool,
+
+    /// Game logic ticks per second (gravity, lock delay).
+    #[arg(long, default_value = "18.0", value_name = "RATE")]
+    pub tick_rate: f64,
+
+    /// Target render frames per second.
+    #[arg(long, default_value = "25.0", value_name = "RATE")]
+    pub frame_rate: f64,
+
+    /// Keep full quality when rendering keeps missing the frame rate budget. By default the game
+    /// then draws sand flat, skips score popups and halves the sand rate until it keeps up again.
+    #[arg(long)]
+    pub no_adaptive_quality: bool,
+
+    /// For high-latency SSH sessions: at most 10 frames a second (more right after a key press),
+    /// flat sand, no animations or popups, and no dynamic background, so each frame sends
+    /// little.
+    #[arg(long)]
+    pub low_bandwidth: bool,
+
+    /// Skip the title screen and main menu and start game immediately.
+    #[arg(long)]
+    pub no_menu: bool,
+
+    /// Guided first game: step by step through moving, rotating, dropping, clearing a
+    /// ready-made bridge and chaining a combo. Each step waits until you have done it.
+    #[arg(long)]
+    pub tutorial: bool,
+
+    /// Missions on top of the chosen mode: one objective at a time (clear bridges of a colour,
+    /// reach a combo, keep the stack low for a while), each worth a bonus that grows with every
+    /// mission done.
+    #[arg(long)]
+    pub missions: bool,
+
+    /// Assist: after topping out, the game over screen offers U to rewind 3 pieces (twice a
+    /// run). A rewound run sets no high scores or records.
+    #[arg(long)]
+    pub rewind: bool,
+
+    /// Hot seat party play: two or more comma-separated player names who take turns at the
+    /// keyboard, one game each. Between games a standings screen shows the scores so far and
+    /// whose turn is next. Players in the same round play the same seed.
+    #[arg(long, value_name = "NAMES", value_delimiter = ',')]
+    pub hot_seat: Vec<String>,
+
+    /// Spawn delay in ms: piece is not controllable and gravity does not apply until after this delay (prevents instant lock on spawn).
+    #[arg(long, value_name = "MS")]
+    pub spawn_delay_ms: Option<u64>,
+
+    /// Relaxed mode: gravity speed does not increase with level (fixed speed).
+    #[arg(long)]
+    pub relaxed: bool,
+
+    /// Initial level (e.g. for custom / practice). Affects starting speed when not relaxed.
+    #[arg(long, default_value = "1", value_name = "N")]
+    pub initial_level: u32,
+
+    /// In-game key profile: classic, guideline, vim, wasd or left-handed (default classic).
+    /// Per-key overrides from the config file apply on top.
+    #[arg(long, value_enum, value_name = "PROFILE")]
+    pub keys: Option<KeyProfile>,
+
+    /// Points it costs to reroll (C: swap the falling piece for the next one). 0 = free.
+    #[arg(long, default_value = "200", value_name = "POINTS")]
+    pub reroll_cost: u32,
+
+    /// Hard drop safety: off, double-tap (press the hard-drop key twice within 300 ms) or hold
+    /// (keep it down for 100 ms). Guards against accidental drops.
+    #[arg(long, value_enum, default_value = "off", value_name = "MODE")]
+    pub hard_drop_safety: HardDropSafety,
+
+    /// DAS in ms: how long a movement key must be held before it starts repeating (default 80).
+    #[arg(long, value_name = "MS")]
+    pub das: Option<u64>,
+
+    /// ARR in ms: time between repeated moves while a movement key is held (default 38; 0 = every frame).
+    #[arg(long, value_name = "MS")]
+    pub arr: Option<u64>,
+
+    /// Soft-drop repeat in ms while Down is held (default 38).
+    #[arg(long, value_name = "MS")]
+    pub soft_drop_ms: Option<u64>,
+
+    /// Lock delay in ms when piece lands (before it locks); moving or rotating restarts it (up to 15 times). Default 120 ms; 0 locks on contact.
+    #[arg(long, value_name = "MS")]
+    pub lock_delay_ms: Option<u64>,
+
+    /// 20G challenge: instant gravity. Pieces spawn resting on the stack and drop after every move; only the lock delay gives you time.
+    #[arg(long = "twenty-g")]
+    pub twenty_g: bool,
+
+    /// Invisible sand (memory challenge): settled sand is shown for a second after it moves, then fades into the background. The falling piece and clears stay visible.
+    #[arg(long)]
+    pub invisible: bool,
+
+    /// Mirrored practice: every piece is flipped left to right (S plays as Z, J as L) and spawns
+    /// over the mirror image of its usual columns, for training the other hand.
+    #[arg(long)]
+    pub mirror: bool,
+
+    /// Cylinder board: the left and right edges join, so pieces and sand leaving one side come
+    /// back on the other. With no walls to reach, a clear has to go all the way round, through
+    /// every column.
+    #[arg(long)]
+    pub cylinder: bool,
+
+    /// Local co-op: two players share a board twice as wide, each with their own falling piece,
+    /// and must out-clear a garbage tide that rises from the bottom faster every level. Player 1
+    /// uses the key profile (WASD unless --keys is given), player 2 the arrows, / and Enter.
+    #[arg(long)]
+    pub coop: bool,
+
+    /// Dual boards (expert): two narrow boards side by side share one piece queue, and each
+    /// new piece falls on the other board from the last. Clears on either board count, and
+    /// either board topping out ends the game.
+    #[arg(long, conflicts_with = "coop")]
+    pub dual: bool,
+
+    /// Play the piece sequence of a shared seed (hex, as shown on the game over screen) in every
+    /// game, to challenge someone on the same pieces.
+    #[arg(long, value_name = "HEX", value_parser = parse_seed)]
+    pub seed: Option<u64>,
+
+    /// Like --seed, with the seed read from the system clipboard.
+    #[arg(long, conflicts_with = "seed")]
+    pub seed_from_clipboard: bool,
+
+    /// Sound effect volume, 0–100 (default 70). Needs a build with the `audio` feature.
+    #[arg(long, value_name = "PERCENT", value_parser = clap::value_parser!(u8).range(0..=100))]
+    pub volume: Option<u8>,
+
+    /// No sound.
+    #[arg(long)]
+    pub mute: bool,
+
+    /// Accessibility: announce game events (piece, column, stack height, clears, score) in a
+    /// status line for screen readers, and draw the board as high-contrast characters.
+    #[arg(long)]
+    pub a11y: bool,
+
+    /// Animated board background: the empty board shades red as the stack rises and gold during
+    /// combos, with faint dust drifting down.
+    #[arg(long)]
+    pub dynamic_background: bool,
+
+    /// Event feed in the sidebar: the last five clears (with their points), combos, chain
+    /// bonuses and level-ups.
+    #[arg(long)]
+    pub event_log: bool,
+
+    /// Reduced motion: no menu slide-in, line-clear fade or floating popups, and sand settles
+    /// without random jitter. For motion sensitivity or slow links.
+    #[arg(long)]
+    pub reduced_motion: bool,
+
+    /// Reduce flashing (photosensitivity): clearing sand greys out and fades slowly instead of
+    /// flashing white (or orange for bombs).
+    #[arg(long)]
+    pub reduce_flashing: bool,
+
+    /// Colour symbols for colourblind or monochrome terminals: each sand colour gets its own
+    /// character (· x + o # %), drawn on every other cell of the board.
+    #[arg(long)]
+    pub symbols: bool,
+
+    /// Blend sand colours where two colours touch: boundary grains are drawn a little toward
+    /// their neighbour's colour, for a softer dune look. Display only; spans are unaffected.
+    #[arg(long)]
+    pub blend_sand: bool,
+
+    /// Save a screenshot of the board and sidebar when quitting (ANSI text, plus PNG in builds
+    /// with the `png` feature) under the config directory. F12 takes one at any time in a game.
+    #[arg(long)]
+    pub screenshot_on_exit: bool,
+
+    /// Show the last few actions as icons under the board, fading out (for streams and
+    /// recordings).
+    #[arg(long)]
+    pub show_inputs: bool,
+
+    /// Speed multiplier, 0.25–2.0: scales gravity and sand; lock and spawn delays stretch to
+    /// match. Below 1 is slower.
+    #[arg(long, value_name = "FACTOR", value_parser = parse_speed)]
+    pub speed: Option<f64>,
+
+    /// Assist preset for slower reaction times: speed 0.5 and a generous lock delay (300 ms before
+    /// scaling, so 600 ms). Four colours unless --colors / --high-color say otherwise.
+    /// --speed and --lock-delay-ms still override.
+    #[arg(long)]
+    pub assist: bool,
+
+    /// Accessibility: terminal bell cues (1 beep piece landed, 2 clear, 3 stack entering the
+    /// danger zone). Works without the audio feature.
+    #[arg(long)]
+    pub bell_cues: bool,
+
+    /// Count the grains around every sand tick, piece lock and clear, and stop with a dump of the
+    /// changed cells if sand appears or vanishes unaccounted for (for grain loss bug reports).
+    #[arg(long)]
+    pub debug_invariants: bool,
+
+    /// Play on from a state dump saved with F10, with the flags and menu picks of the dumped game
+    /// (the other flags given are ignored).
+    #[arg(long, value_name = "FILE")]
+    pub load_state: Option<std::path::PathBuf>,
+
+    /// Log tick timing, game events and input latency to FILE, for performance reports (needs
+    /// the `logging` feature).
+    #[arg(long, value_name = "FILE")]
+    pub log_file: Option<std::path::PathBuf>,
+
+    /// Write newline-delimited JSON game snapshots (score, level, combo, board summary, events)
+    /// once per tick to this path: an existing named pipe, or a UNIX socket created here that
+    /// overlays and bots can connect to.
+    #[arg(long, value_name = "PATH")]
+    pub state_socket: Option<std::path::PathBuf>,
+
+    /// Read actions (left, right, rotate-cw, rotate-ccw, soft-drop, hard-drop, pause, quit), one
+    /// per line, from this path alongside the keyboard: an existing named pipe, or a UNIX socket
+    /// created here.
+    #[arg(long, value_name = "PATH")]
+    pub input_fifo: Option<std::path::PathBuf>,
+
+    /// Background music track: off, pulse, march or drift (default pulse; `audio` feature).
+    #[arg(long, value_enum, value_name = "TRACK")]
+    pub music: Option<MusicTrack>,
+
+    /// Disable mouse control (keeps the terminal's own text selection working).
+    #[arg(long)]
+    pub no_mouse: bool,
+
+    /// Disable key chords (both rotate keys within 50 ms rotate 180°; Left+Right together
+    /// stop the piece shifting).
+    #[arg(long)]
+    pub no_chords: bool,
+
+    /// Sand settling: after lock, sand can fall sideways (down-left/down-right) when directly below is blocked.
+    #[arg(long)]
+    pub sand_settle: bool,
+
+    /// High color mode: use 6 colors (red, blue, yellow, green, magenta, cyan) instead of 4 (red, blue, yellow, green). Same as --colors 6.
+    #[arg(long, conflicts_with = "colors")]
+    pub high_color: bool,
+
+    /// Number of sand colours in play (2–6; default: the difficulty's, 4). Fewer colours make
+    /// bridges easier.
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u8).range(2..=6))]
+    pub colors: Option<u8>,
+
+    /// Piece set: tetromino (classic 7), pentomino (the 12 five-cell pieces), mixed (both in one bag), or small (1–3 cell beginner pieces). Default: small on easy, tetromino otherwise.
+    #[arg(long, value_name = "SET")]
+    pub pieces: Option<PieceSet>,
+
+    /// Anti-drought: no piece kind goes more than 12 pieces without coming up (matters for the bigger piece sets). The sidebar shows the longest current wait.
+    #[arg(long)]
+    pub anti_drought: bool,
+
+    /// Two-tone pieces: the four cells of a piece can carry different colours (mostly two, Setris-style).
+    #[arg(long)]
+    pub two_tone: bool,
+
+    /// Balance colours: deal the pieces' colours evenly (every colour twice per round) and never more than three of one colour in a row, for steadier bridge building.
+    #[arg(long)]
+    pub balance_colors: bool,
+
+    /// Piece colours: kind (each kind its own colour, sometimes repeating the last), random (any colour), or balanced (the colour with the least sand on the board, to even it out).
+    #[arg(long, default_value = "kind", value_name = "HOW")]
+    pub color_assign: ColorAssign,
+
+    /// Rainbow grains: pieces occasionally carry a wildcard cell that counts as any colour; reaching a x3 combo awards one.
+    #[arg(long)]
+    pub rainbow: bool,
+
+    /// Bombs: occasionally a 2x2 bomb replaces a piece and blasts the sand around it on lock; reaching a x5 combo awards one.
+    #[arg(long)]
+    pub bombs: bool,
+
+    /// Colour converters: reaching a x7 combo gives a piece a converter cell that, once settled, recolours the neighbouring sand to its own colour.
+    #[arg(long)]
+    pub converters: bool,
+
+    /// Sand material: dry (classic), wet (slower, steeper piles), sticky (clumps, barely slides), liquid (levels out sideways). Default: the difficulty's (dry).
+    #[arg(long, value_name = "TYPE")]
+    pub sand_type: Option<SandType>,
+
+    /// Dig mode: start with N rows of stone at the bottom. Stone never matches a colour; clears next to it break it.
+    #[arg(long, default_value = "0", value_name = "N")]
+    pub dig_rows: u16,
+
+    /// Start with N block rows of random sand on the board (above any --dig-rows stone), laid so none of it clears at once.
+    #[arg(long, default_value = "0", value_name = "N")]
+    pub start_garbage: u16,
+
+    /// Clean opening: the first bag deals no S or Z piece.
+    #[arg(long)]
+    pub start_clean_streak: bool,
+
+    /// Colour palette: normal (theme), high-contrast, or colorblind.
+    #[arg(long, default_value = "normal")]
+    pub palette: Palette,
+
+    /// Combo window in game ticks after a clear (default 90). Also settable in config.json.
+    #[arg(long, value_name = "TICKS", value_parser = clap::value_parser!(u32).range(1..))]
+    pub combo_ticks: Option<u32>,
+
+    /// Maximum combo multiplier (default 10; 1 disables combos). Also settable in config.json.
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u32).range(1..))]
+    pub combo_max: Option<u32>,
+
+    /// What happens when the combo window expires: reset (back to x1) or step (drop one level and restart the window).
+    #[arg(long, value_name = "MODE")]
+    pub combo_decay: Option<ComboDecay>,
+
+    /// Toggle Autoplay mode (AI plays the game).
+    #[arg(long)]
+    pub autoplay: bool,
+
+    /// Auto-restart on game over (useful for autoplay).
+    #[arg(long, short = 'r', visible_alias = "ar")]
+    pub auto_restart: bool,
+
+    /// Print short help.
+    #[arg(short = 'h', long = "h", action = ArgAction::SetTrue)]
+    pub help_short: bool,
+
+    /// List the built-in palettes and the theme files in the config themes directory, with
+    /// colour swatches, then exit.
+    #[arg(long)]
+    pub list_themes: bool,
+
+    /// Print full tree-style help.
+    #[arg(long = "help", long = "Help", action = ArgAction::SetTrue)]
+    pub help_long: bool,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Subcommand)]
+pub enum Command {
+    /// Measure terminal throughput, colour support and keyboard protocol support, and print a
+    /// capability report to attach to bug reports.
+    Doctor,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum Palette {
+    #[default]
+    Normal,
+
+    #[value(alias = "highcontrast", alias = "contrast")]
+    HighContrast,
+
+    #[value(alias = "colourblind")]
+    Colorblind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum GameMode {
+    #[default]
+    Endless,
+    Timed,
+    #[value(name = "clear40")]
+    Clear,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum Difficulty {
+    #[default]
+    Easy,
+    Medium,
+    Hard,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PieceSet {
+    #[default]
+    Tetromino,
+    Pentomino,
+    Mixed,
+    /// Beginner pieces: monomino, domino and the two trominoes.
+    Small,
+}
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum, serde::Serialize, serde::Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum SandType {
+    #[default]
+    Dry,
+    Wet,
+    Sticky,
+    Liquid,
+}
+
+/// How a queued piece gets its main colour (`--color-assign`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum ColorAssign {
+    /// Each kind has its colour; the difficulty's repeat chance reuses the previous one.
+    #[default]
+    Kind,
+    /// Any colour in play, at random.
+    Random,
+    /// The colour with the least sand on the board.
+    Balanced,
+}
+
+/// Background music loop (`audio` feature). Tempo rises with the level.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MusicTrack {
+    Off,
+    /// Minor-key arpeggios.
+    #[default]
+    Pulse,
+    /// Dotted march rhythm.
+    March,
+    /// Slow pentatonic line.
+    Drift,
+}
+
+/// In-game key binding preset (see README for the keys).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum KeyProfile {
+    /// Arrows and hjkl, Space/Enter hard drop (the original bindings).
+    #[default]
+    Classic,
+    /// Arrows, Z/X rotate, Space hard drop.
+    Guideline,
+    /// hjkl only, u rotates counter-clockwise.
+    Vim,
+    /// A/D move, S soft drop, W hard drop, Q/E rotate.
+    Wasd,
+    /// A/D/S on the left hand, rotation on , and . for the right.
+    LeftHanded,
+}
+
+/// When a hard-drop key press actually drops (`--hard-drop-safety`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum HardDropSafety {
+    /// Every press drops.
+    #[default]
+    Off,
+    /// A second press within 300 ms drops.
+    DoubleTap,
+    /// The key must be held for 100 ms (double tap on terminals without key release events).
+    Hold,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ComboDecay {
+    /// Multiplier drops straight back to x1.
+    #[default]
+    Reset,
+    /// Multiplier drops by one and the window restarts, until x1.
+    Step,
+}
@@ -0,0 +1,96 @@
+//! `--log-file` (`logging` feature): tick timing, game events and input latency in a file.
+//!
+//! They are written with `tracing`, for performance reports from terminals we can't try
+//! ourselves. Nothing goes to stdout or stderr, which belong to the alternate screen while the
+//! game runs.
+//!
+//! Built without the feature, these calls do nothing and `--log-file` is refused.
+
+use crate::game::GameEvent;
+use crate::input::Action;
+use anyhow::Result;
+use std::path::Path;
+use std::time::Duration;
+
+/// Start logging to `path` (truncated first). Without a path nothing is logged.
+pub fn init(path: Option<&Path>) -> Result<()> {
+    let Some(path) = path else {
+        return Ok(());
+    };
+    #[cfg(feature = "logging")]
+    {
+        use anyhow::Context;
+        let file =
+            std::fs::File::create(path).with_context(|| format!("creating {}", path.display()))?;
+        // Unbuffered, so the lines before a crash or a kill are on disk
+        tracing_subscriber::fmt()
+            .with_writer(std::sync::Mutex::new(file))
+            .with_ansi(false)
+            .with_max_level(tracing::Level::TRACE)
+            .try_init()
+            .map_err(anyhow::Error::msg)?;
+        tracing::info!(version = env!("CARGO_PKG_VERSION"), "logging started");
+        Ok(())
+    }
+    #[cfg(not(feature = "logging"))]
+    anyhow::bail!(
+        "--log-file {}: setrixtui was built without the `logging` feature",
+        path.display()
+    )
+}
+
+/// The terminal the game starts in: `$TERM`, size, and whether it reports key releases.
+pub fn terminal(cols: u16, rows: u16, key_releases: bool) {
+    #[cfg(feature = "logging")]
+    tracing::info!(
+        term = std::env::var("TERM").unwrap_or_default(),
+        term_program = std::env::var("TERM_PROGRAM").unwrap_or_default(),
+        cols,
+        rows,
+        key_releases,
+        "terminal"
+    );
+    #[cfg(not(feature = "logging"))]
+    let _ = (cols, rows, key_releases);
+}
+
+/// One logic tick: how long after its due time it ran, and `physics` spent on `steps` sand
+/// steps.
+pub fn tick(late: Duration, physics: Duration, steps: u32) {
+    #[cfg(feature = "logging")]
+    tracing::trace!(?late, ?physics, steps, "tick");
+    #[cfg(not(feature = "logging"))]
+    let _ = (late, physics, steps);
+}
+
+/// One frame, which took `draw` to render and write to the terminal.
+pub fn frame(draw: Duration) {
+    #[cfg(feature = "logging")]
+    tracing::trace!(?draw, "frame");
+    #[cfg(not(feature = "logging"))]
+    let _ = draw;
+}
+
+/// Rendering fell behind and effects were turned down (`low`), or it caught up again.
+pub fn quality(low: bool) {
+    #[cfg(feature = "logging")]
+    tracing::info!(low, "adaptive quality");
+    #[cfg(not(feature = "logging"))]
+    let _ = low;
+}
+
+/// Something that happened in the game (lock, clear, level up...).
+pub fn game_event(event: GameEvent) {
+    #[cfg(feature = "logging")]
+    tracing::debug!(?event, "game event");
+    #[cfg(not(feature = "logging"))]
+    let _ = event;
+}
+
+/// A key press in play, `latency` from reading it to the end of the first frame drawn after.
+pub fn input(action: Action, latency: Duration) {
+    #[cfg(feature = "logging")]
+    tracing::debug!(?action, ?latency, "input");
+    #[cfg(not(feature = "logging"))]
+    let _ = (action, latency);
+}
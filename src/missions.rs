@@ -0,0 +1,140 @@
+//! `--missions`: one objective at a time on top of the chosen mode ("clear 3 bridges of blue",
+//! "reach combo x5", "stay below row 12 for 60 s"). Completing one scores a bonus and deals the
+//! next, in turn and a little harder each round. Dealt from the game's seed, so a seed replays
+//! the same missions.
+
+use crate::game::{GameEvent, GameState};
+use fastrand::Rng;
+use std::time::{Duration, Instant};
+
+/// Bonus for the first mission; the nth completed scores n times this.
+const MISSION_BONUS: u32 = 250;
+
+/// Most time one update counts towards a survive objective, so a pause doesn't.
+const MAX_STEP: Duration = Duration::from_millis(100);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Objective {
+    /// Clear `count` bridges with sand of colour index `color` in them.
+    ClearColor { color: u8, count: u32 },
+    /// Reach this combo multiplier.
+    Combo(u32),
+    /// Keep the stack at or below block row `row` (from the bottom) for `secs` seconds in a row.
+    Survive { secs: u32, row: usize },
+}
+
+/// The current objective for the sidebar card.
+#[derive(Debug, Clone)]
+pub struct MissionCard {
+    /// 1 for the first mission.
+    pub number: u32,
+    pub text: String,
+    /// Clear objectives: the colour to clear, shown after `text`.
+    pub color: Option<u8>,
+    pub progress: String,
+}
+
+/// Missions progress over one game.
+#[derive(Debug, Clone)]
+pub struct Missions {
+    objective: Objective,
+    /// Clear objectives: bridges cleared so far.
+    cleared: u32,
+    /// Survive objectives: time the stack has stayed low.
+    survived: Duration,
+    last_update: Option<Instant>,
+    completed: u32,
+    rng: Rng,
+}
+
+impl Missions {
+    /// The first mission for `state`, dealt from its seed.
+    pub fn new(state: &GameState) -> Self {
+        let mut missions = Self {
+            objective: Objective::Combo(0),
+            cleared: 0,
+            survived: Duration::ZERO,
+            last_update: None,
+            completed: 0,
+            rng: Rng::with_seed(state.seed),
+        };
+        missions.deal(state);
+        missions
+    }
+
+    /// Count one frame's `events` and the board towards the objective. Returns the bonus when
+    /// it is done; the next one is dealt straight away.
+    pub fn update(&mut self, state: &GameState, events: &[GameEvent], now: Instant) -> Option<u32> {
+        let step = self.last_update.map_or(Duration::ZERO, |last| {
+            now.duration_since(last).min(MAX_STEP)
+        });
+        self.last_update = Some(now);
+        let done = match self.objective {
+            Objective::ClearColor { color, count } => {
+                if events.contains(&GameEvent::Clear) && state.last_clear_colors.contains(&color) {
+                    self.cleared += 1;
+                }
+                self.cleared >= count
+            }
+            // The combo reached after the mission was dealt
+            Objective::Combo(target) => events
+                .iter()
+                .any(|&event| matches!(event, GameEvent::Combo(n) if n >= target)),
+            Objective::Survive { secs, row } => {
+                if state.stack_height() > row {
+                    self.survived = Duration::ZERO;
+                } else {
+                    self.survived += step;
+                }
+                self.survived >= Duration::from_secs(secs.into())
+            }
+        };
+        if !done {
+            return None;
+        }
+        self.completed += 1;
+        let bonus = MISSION_BONUS * self.completed;
+        self.deal(state);
+        Some(bonus)
+    }
+
+    /// Next objective: clear, combo and survive in turn, asking more every round of three.
+    fn deal(&mut self, state: &GameState) {
+        let round = self.completed / 3;
+        self.objective = match self.completed % 3 {
+            0 => Objective::ClearColor {
+                color: self.rng.u8(..state.colors.count()),
+                count: (2 + round).min(5),
+            },
+            1 => Objective::Combo((3 + round).min(state.combo.max_multiplier.max(2))),
+            _ => Objective::Survive {
+                secs: (30 + 15 * round).min(90),
+                row: state.playfield.height / 2,
+            },
+        };
+        self.cleared = 0;
+        self.survived = Duration::ZERO;
+    }
+
+    pub fn card(&self) -> MissionCard {
+        let (text, color, progress) = match self.objective {
+            Objective::ClearColor { color, count } => (
+                format!("Clear {count} bridges of"),
+                Some(color),
+                format!("{}/{count}", self.cleared),
+            ),
+            Objective::Combo(target) => (format!("Reach combo x{target}"), None, String::new()),
+            Objective::Survive { secs, row } => (
+                format!("Below row {row} for {secs} s"),
+                None,
+                format!("{} s/{secs} s", self.survived.as_secs()),
+            ),
+        };
+        MissionCard {
+            number: self.completed + 1,
+            text,
+            color,
+            progress,
+        }
+    }
+}
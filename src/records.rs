@@ -0,0 +1,98 @@
+//! Lifetime statistics per mode and difficulty (games, lines, time played, best score), shown in
+//! the menu's records panel. Stored next to the high scores, one line per combination.
+
+use crate::{Difficulty, GameMode};
+use anyhow::Result;
+use std::fmt::Write as _;
+use std::fs;
+use std::time::Duration;
+
+const FILENAME: &str = "records";
+
+/// Modes × difficulties.
+const COMBINATIONS: usize = 9;
+
+/// Totals for one mode and difficulty.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Record {
+    pub games: u32,
+    pub lines: u32,
+    pub played: Duration,
+    /// Best score.
+    pub best: u32,
+}
+
+/// Every combination's record, in `index` order.
+#[derive(Debug, Clone, Default)]
+pub struct Records([Record; COMBINATIONS]);
+
+/// Line in the file: endless easy, medium, hard, then timed, then clear.
+const fn index(mode: GameMode, difficulty: Difficulty) -> usize {
+    mode as usize * 3 + difficulty as usize
+}
+
+impl Records {
+    /// Load from disk; missing or unreadable lines count as empty.
+    pub fn load() -> Self {
+        let path = crate::config::config_dir().join(FILENAME);
+        let content = fs::read_to_string(path).unwrap_or_default();
+        let mut records = Self::default();
+        for (record, line) in records.0.iter_mut().zip(content.lines()) {
+            *record = parse_line(line).unwrap_or_default();
+        }
+        records
+    }
+
+    pub const fn get(&self, mode: GameMode, difficulty: Difficulty) -> Record {
+        self.0[index(mode, difficulty)]
+    }
+
+    /// Count a finished game and save.
+    pub fn add_game(
+        &mut self,
+        mode: GameMode,
+        difficulty: Difficulty,
+        score: u32,
+        lines: u32,
+        played: Duration,
+    ) -> Result<()> {
+        let record = &mut self.0[index(mode, difficulty)];
+        record.games += 1;
+        record.lines += lines;
+        record.played += played;
+        record.best = record.best.max(score);
+        self.save()
+    }
+
+    fn save(&self) -> Result<()> {
+        let path = crate::config::config_dir().join(FILENAME);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut content = String::new();
+        for record in &self.0 {
+            let _ = writeln!(
+                content,
+                "{} {} {} {}",
+                record.games,
+                record.lines,
+                record.played.as_secs(),
+                record.best
+            );
+        }
+        fs::write(path, content)?;
+        Ok(())
+    }
+}
+
+/// `games lines seconds best`.
+fn parse_line(line: &str) -> Option<Record> {
+    let mut fields = line.split_whitespace().map(str::parse::<u64>);
+    let mut next = || fields.next()?.ok();
+    Some(Record {
+        games: u32::try_from(next()?).ok()?,
+        lines: u32::try_from(next()?).ok()?,
+        played: Duration::from_secs(next()?),
+        best: u32::try_from(next()?).ok()?,
+    })
+}
@@ -0,0 +1,91 @@
+//! External command input (`--input-fifo PATH`): actions read line by line from a named pipe or
+//! socket and merged with the keyboard, for "chat plays" setups and tests driving the real TUI.
+//!
+//! Each line is one action name as in the config file's `keys.bind` (`left`, `right`,
+//! `rotate-cw`, `rotate-ccw`, `soft-drop`, `hard-drop`, `reroll`, `focus`, `zone`, `pause`,
+//! `quit`), applied like a key tap. Unknown lines are ignored. An existing named pipe (FIFO) is
+//! reopened whenever its writer closes it. Otherwise, on Unix, a socket is created at the path
+//! and any number of clients can connect.
+
+use crate::input::{Action, parse_action};
+use anyhow::{Context, Result};
+use std::collections::VecDeque;
+use std::io::{BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, Sender};
+
+/// Actions arriving from reader threads.
+pub struct RemoteInput {
+    rx: Receiver<Action>,
+}
+
+impl RemoteInput {
+    /// Start reading from the pipe at `path`, or (Unix) listen on a new socket there, replacing
+    /// a stale one.
+    pub fn open(path: &Path) -> Result<Self> {
+        let (tx, rx) = mpsc::channel();
+        Self::spawn_readers(path, tx)
+            .with_context(|| format!("opening input fifo {}", path.display()))?;
+        Ok(Self { rx })
+    }
+
+    #[cfg(unix)]
+    fn spawn_readers(path: &Path, tx: Sender<Action>) -> std::io::Result<()> {
+        use std::os::unix::fs::FileTypeExt;
+        use std::os::unix::net::UnixListener;
+
+        match std::fs::metadata(path) {
+            Ok(meta) if meta.file_type().is_fifo() => {
+                spawn_pipe_reader(path.to_path_buf(), tx);
+                return Ok(());
+            }
+            Ok(meta) if meta.file_type().is_socket() => std::fs::remove_file(path)?,
+            _ => {}
+        }
+        let listener = UnixListener::bind(path)?;
+        std::thread::spawn(move || {
+            for client in listener.incoming().flatten() {
+                let tx = tx.clone();
+                std::thread::spawn(move || read_actions(client, &tx));
+            }
+        });
+        Ok(())
+    }
+
+    #[cfg(not(unix))]
+    fn spawn_readers(path: &Path, tx: Sender<Action>) -> std::io::Result<()> {
+        spawn_pipe_reader(path.to_path_buf(), tx);
+        Ok(())
+    }
+
+    /// Actions received since the last call, oldest first.
+    pub fn drain(&self) -> VecDeque<Action> {
+        self.rx.try_iter().collect()
+    }
+}
+
+/// Read the pipe, reopening it after each writer finishes (opening blocks until one arrives).
+fn spawn_pipe_reader(path: PathBuf, tx: Sender<Action>) {
+    std::thread::spawn(move || {
+        while let Ok(file) = std::fs::File::open(&path) {
+            if !read_actions(file, &tx) {
+                break;
+            }
+        }
+    });
+}
+
+/// Forward every recognised line until EOF. False once the game has stopped listening.
+fn read_actions(reader: impl Read, tx: &Sender<Action>) -> bool {
+    for line in BufReader::new(reader).lines() {
+        let Ok(line) = line else {
+            break;
+        };
+        if let Some(action) = parse_action(line.trim()) {
+            if tx.send(action).is_err() {
+                return false;
+            }
+        }
+    }
+    true
+}
@@ -0,0 +1,86 @@
+//! `--rewind` assist: the game is saved as each piece spawns, and a game that tops out can go
+//! back `REWIND_PIECES` pieces, a few times a run. A rewound run sets no records.
+
+use crate::dump::Timers;
+use crate::game::GameState;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Pieces a rewind goes back.
+pub const REWIND_PIECES: usize = 3;
+
+/// Rewinds in one run.
+const REWIND_USES: u32 = 2;
+
+/// A saved game: the state, its timers and the game time, as a piece spawned.
+#[derive(Debug)]
+struct Snapshot {
+    state: GameState,
+    timers: Timers,
+    elapsed: Duration,
+}
+
+/// Snapshots and rewinds left for one run.
+#[derive(Debug)]
+pub struct Rewind {
+    /// The last `REWIND_PIECES + 1` spawns, oldest first.
+    snapshots: VecDeque<Snapshot>,
+    uses_left: u32,
+    /// This run went back at least once.
+    used: bool,
+}
+
+impl Default for Rewind {
+    fn default() -> Self {
+        Self {
+            snapshots: VecDeque::new(),
+            uses_left: REWIND_USES,
+            used: false,
+        }
+    }
+}
+
+impl Rewind {
+    /// Save the game as a piece spawns.
+    pub fn record(&mut self, state: &GameState, elapsed: Duration, now: Instant) {
+        if self.snapshots.len() > REWIND_PIECES {
+            self.snapshots.pop_front();
+        }
+        self.snapshots.push_back(Snapshot {
+            state: state.clone(),
+            timers: state.timers(now),
+            elapsed,
+        });
+    }
+
+    /// A rewind can be offered: uses left and a spawn to go back to.
+    pub fn available(&self) -> bool {
+        self.uses_left > 0 && !self.snapshots.is_empty()
+    }
+
+    pub const fn uses_left(&self) -> u32 {
+        self.uses_left
+    }
+
+    pub const fn used(&self) -> bool {
+        self.used
+    }
+
+    /// Spend a use: the game as of the oldest snapshot, up to `REWIND_PIECES` pieces back, with
+    /// its game time. Snapshots start over from there.
+    pub fn rewind(&mut self, now: Instant) -> Option<(GameState, Duration)> {
+        if !self.available() {
+            return None;
+        }
+        let Snapshot {
+            mut state,
+            timers,
+            elapsed,
+        } = self.snapshots.pop_front()?;
+        self.snapshots.clear();
+        self.uses_left -= 1;
+        self.used = true;
+        state.restore_timers(&timers, now);
+        Some((state, elapsed))
+    }
+}
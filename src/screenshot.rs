@@ -0,0 +1,141 @@
+//! Board screenshots (F12, `--screenshot-on-exit`): the game area of a rendered frame saved as
+//! an ANSI text file (`cat` it in a terminal), plus a PNG with the `png` feature.
+
+use anyhow::{Context, Result};
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Color;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// PNG pixels per terminal cell; cells are drawn 1:2 like a terminal font.
+#[cfg(feature = "png")]
+const PNG_CELL_W: u32 = 8;
+#[cfg(feature = "png")]
+const PNG_CELL_H: u32 = 16;
+
+/// Save `area` of `buffer` under `<config dir>/screenshots/`. Returns the path of the text file
+/// (the PNG, when built, sits next to it).
+pub fn save(buffer: &Buffer, area: Rect) -> Result<PathBuf> {
+    let dir = crate::config::config_dir().join("screenshots");
+    fs::create_dir_all(&dir).with_context(|| format!("creating {}", dir.display()))?;
+    let stamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_millis());
+    let path = dir.join(format!("setrixtui-{stamp}.ans"));
+    fs::write(&path, to_ansi(buffer, area))
+        .with_context(|| format!("writing {}", path.display()))?;
+    #[cfg(feature = "png")]
+    {
+        let png = path.with_extension("png");
+        to_png(buffer, area)
+            .save(&png)
+            .with_context(|| format!("writing {}", png.display()))?;
+    }
+    Ok(path)
+}
+
+/// The cells as text with 24-bit / 256-colour SGR sequences, one line per row.
+fn to_ansi(buffer: &Buffer, area: Rect) -> String {
+    let area = area.intersection(buffer.area);
+    let mut out = String::new();
+    for y in area.top()..area.bottom() {
+        let mut current = None;
+        for x in area.left()..area.right() {
+            let cell = &buffer[(x, y)];
+            if current != Some((cell.fg, cell.bg)) {
+                let _ = write!(out, "\x1b[{};{}m", sgr(cell.fg, true), sgr(cell.bg, false));
+                current = Some((cell.fg, cell.bg));
+            }
+            out.push_str(cell.symbol());
+        }
+        out.push_str("\x1b[0m\n");
+    }
+    out
+}
+
+/// SGR parameters selecting `color` as the foreground or background.
+fn sgr(color: Color, fg: bool) -> String {
+    let base = if fg { 30 } else { 40 };
+    match color {
+        Color::Reset => format!("{}", base + 9),
+        Color::Rgb(r, g, b) => format!("{};2;{r};{g};{b}", base + 8),
+        Color::Indexed(i) => format!("{};5;{i}", base + 8),
+        Color::Black => format!("{base}"),
+        Color::Red => format!("{}", base + 1),
+        Color::Green => format!("{}", base + 2),
+        Color::Yellow => format!("{}", base + 3),
+        Color::Blue => format!("{}", base + 4),
+        Color::Magenta => format!("{}", base + 5),
+        Color::Cyan => format!("{}", base + 6),
+        Color::Gray => format!("{}", base + 7),
+        Color::DarkGray => format!("{}", base + 60),
+        Color::LightRed => format!("{}", base + 61),
+        Color::LightGreen => format!("{}", base + 62),
+        Color::LightYellow => format!("{}", base + 63),
+        Color::LightBlue => format!("{}", base + 64),
+        Color::LightMagenta => format!("{}", base + 65),
+        Color::LightCyan => format!("{}", base + 66),
+        Color::White => format!("{}", base + 67),
+    }
+}
+
+/// Colour blocks only (no glyphs): half blocks split the cell into fg over bg, full blocks are
+/// fg, anything else shows its background.
+#[cfg(feature = "png")]
+fn to_png(buffer: &Buffer, area: Rect) -> image::RgbImage {
+    let area = area.intersection(buffer.area);
+    let mut img = image::RgbImage::new(
+        u32::from(area.width) * PNG_CELL_W,
+        u32::from(area.height) * PNG_CELL_H,
+    );
+    for y in area.top()..area.bottom() {
+        for x in area.left()..area.right() {
+            let cell = &buffer[(x, y)];
+            let (top, bottom) = match cell.symbol() {
+                "▀" => (cell.fg, cell.bg),
+                "█" => (cell.fg, cell.fg),
+                _ => (cell.bg, cell.bg),
+            };
+            let (px, py) = (
+                u32::from(x - area.x) * PNG_CELL_W,
+                u32::from(y - area.y) * PNG_CELL_H,
+            );
+            for dy in 0..PNG_CELL_H {
+                let color = image::Rgb(rgb(if dy < PNG_CELL_H / 2 { top } else { bottom }));
+                for dx in 0..PNG_CELL_W {
+                    img.put_pixel(px + dx, py + dy, color);
+                }
+            }
+        }
+    }
+    img
+}
+
+/// RGB for a colour, with xterm's defaults for the named ones (Reset as black).
+#[cfg(feature = "png")]
+const fn rgb(color: Color) -> [u8; 3] {
+    match color {
+        Color::Rgb(r, g, b) => [r, g, b],
+        Color::Reset | Color::Black => [0, 0, 0],
+        Color::Red => [205, 0, 0],
+        Color::Green => [0, 205, 0],
+        Color::Yellow => [205, 205, 0],
+        Color::Blue => [0, 0, 238],
+        Color::Magenta => [205, 0, 205],
+        Color::Cyan => [0, 205, 205],
+        Color::Gray => [229, 229, 229],
+        Color::DarkGray => [127, 127, 127],
+        Color::LightRed => [255, 0, 0],
+        Color::LightGreen => [0, 255, 0],
+        Color::LightYellow => [255, 255, 0],
+        Color::LightBlue => [92, 92, 255],
+        Color::LightMagenta => [255, 0, 255],
+        Color::LightCyan => [0, 255, 255],
+        Color::White => [255, 255, 255],
+        // No 256-colour table: mid grey
+        Color::Indexed(_) => [128, 128, 128],
+    }
+}
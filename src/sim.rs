@@ -0,0 +1,186 @@
+//! Headless games for bots, tools and tests.
+//!
+//! `simulate` plays a list of inputs, one per logic tick, on a seeded board with no terminal
+//! and no wall clock, and sums up what happened. The property tests below check physics
+//! invariants with it.
+
+use crate::config::FileConfig;
+use crate::game::{Cell, GRAIN_SCALE, GameEvent, GameState, PieceKind};
+pub use crate::input::Action;
+use crate::theme::Theme;
+use crate::{Args, GameConfig};
+use clap::Parser;
+use std::time::{Duration, Instant};
+
+/// What a simulated game did. Grain counts are in grains (a block cell is `GRAIN_SCALE`²).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GameSummary {
+    pub game_over: bool,
+    pub score: u32,
+    pub lines_cleared: u32,
+    /// Grains on the board at the end, sand and frozen.
+    pub grains: usize,
+    /// Grains brought in by locked pieces.
+    pub grains_locked: usize,
+    /// Grains of a piece that topped out, locked above the board and so never on it.
+    pub grains_off_board: usize,
+    /// Grains taken out by clears.
+    pub grains_cleared: usize,
+    /// Ticks after which the score was lower than before.
+    pub score_drops: u32,
+    /// Clears that left a cleared grain behind or a colour still spanning the board.
+    pub incomplete_clears: u32,
+}
+
+/// Play `inputs` (one per tick; `Action::None` past the end) for up to `ticks` logic ticks.
+///
+/// The game is on the default board, dealt from `seed`. Clears finish at once, as with
+/// `--no-animation`, and `--debug-invariants` is on, so a step that breaks the grain count
+/// panics with its dump.
+pub fn simulate(inputs: &[Action], seed: u64, ticks: u32) -> GameSummary {
+    let args = Args::parse_from(["setrixtui"]);
+    let mut config = GameConfig::new(&args, FileConfig::default());
+    config.seed = Some(seed);
+    config.debug_invariants = true;
+    let profile = *config.profile();
+    let (width, height) = profile.playfield_size(args.width, args.height);
+    let mut state = GameState::new(Theme::default(), width, height, &config);
+    let tick = Duration::from_secs_f64(1.0 / profile.tick_rate);
+    let mut now = Instant::now();
+    let mut summary = GameSummary::default();
+
+    for i in 0..ticks as usize {
+        if state.game_over {
+            break;
+        }
+        now += tick;
+        let score = state.score;
+        let action = inputs.get(i).copied().unwrap_or(Action::None);
+        if state.is_controllable(now) {
+            step(&mut state, &mut summary, |state| apply(state, action, now));
+        }
+        step(&mut state, &mut summary, |state| state.tick_gravity(now));
+        step(&mut state, &mut summary, GameState::tick_sand);
+        step(&mut state, &mut summary, |state| state.check_lock(now));
+        if state.score < score {
+            summary.score_drops += 1;
+        }
+    }
+
+    summary.game_over = state.game_over;
+    summary.score = state.score;
+    summary.lines_cleared = state.lines_cleared;
+    summary.grains = state.playfield.grain_count() + state.frozen_grains.len();
+    summary
+}
+
+/// One player input, as the app applies it.
+fn apply(state: &mut GameState, action: Action, now: Instant) {
+    match action {
+        Action::MoveLeft => state.move_left(now),
+        Action::MoveRight => state.move_right(now),
+        Action::RotateCw => state.rotate_cw(now),
+        Action::RotateCcw => state.rotate_ccw(now),
+        Action::SoftDrop => state.soft_drop(now),
+        Action::HardDrop => state.hard_drop(now),
+        Action::Reroll => {
+            state.reroll(now);
+        }
+        Action::Focus => {
+            state.activate_focus();
+        }
+        Action::Zone => {
+            state.activate_zone();
+        }
+        Action::Pause | Action::Quit | Action::Restart | Action::Retry | Action::None => {}
+    }
+    if matches!(
+        action,
+        Action::MoveLeft | Action::MoveRight | Action::RotateCw | Action::RotateCcw
+    ) {
+        state.on_move_or_rotate(now);
+    }
+}
+
+/// Run one part of a tick, count what it locked, and finish any clear it started.
+fn step(state: &mut GameState, summary: &mut GameSummary, part: impl FnOnce(&mut GameState)) {
+    let kind = state.piece.as_ref().map(|piece| piece.kind);
+    let frozen = state.frozen_grains.len();
+    part(state);
+    if state.drain_events().contains(&GameEvent::Lock) {
+        // A bomb blasts instead of turning to sand
+        if let Some(kind) = kind.filter(|&kind| kind != PieceKind::Bomb) {
+            let grains = kind.cells().len() * GRAIN_SCALE * GRAIN_SCALE;
+            summary.grains_locked += grains;
+            // Whatever didn't freeze was above the board (the lock's own grain check, on here,
+            // allows nothing else)
+            summary.grains_off_board += grains - (state.frozen_grains.len() - frozen);
+        }
+    }
+    if state.line_clear_in_progress {
+        let cleared = state.line_clear_cells.clone();
+        summary.grains_cleared += cleared.len();
+        state.finish_line_clear();
+        let left = cleared
+            .iter()
+            .any(|&(x, y)| state.playfield.get(x, y).is_some_and(Cell::is_solid));
+        if left || state.playfield.find_spanning_components().0 > 0 {
+            summary.incomplete_clears += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    /// Long enough for several pieces to lock, short enough to rarely top out.
+    const TICKS: u32 = 600;
+
+    /// Inputs that neither spend points (reroll) nor hold clears back (zone). Hard drops are
+    /// rare so most games stay alive for the whole run.
+    fn inputs() -> impl Strategy<Value = Vec<Action>> {
+        proptest::collection::vec(
+            prop_oneof![
+                8 => Just(Action::None),
+                3 => Just(Action::MoveLeft),
+                3 => Just(Action::MoveRight),
+                2 => Just(Action::RotateCw),
+                2 => Just(Action::RotateCcw),
+                2 => Just(Action::SoftDrop),
+                1 => Just(Action::HardDrop),
+                1 => Just(Action::Focus),
+            ],
+            0..TICKS as usize,
+        )
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(16))]
+
+        #[test]
+        fn no_grain_is_lost(inputs in inputs(), seed in any::<u64>()) {
+            let summary = simulate(&inputs, seed, TICKS);
+            prop_assert_eq!(
+                summary.grains + summary.grains_cleared + summary.grains_off_board,
+                summary.grains_locked
+            );
+        }
+
+        #[test]
+        fn score_never_drops(inputs in inputs(), seed in any::<u64>()) {
+            let summary = simulate(&inputs, seed, TICKS);
+            prop_assert_eq!(summary.score_drops, 0);
+            // Every cleared grain scores at least a point
+            prop_assert!(summary.score as usize >= summary.grains_cleared);
+        }
+
+        #[test]
+        fn clears_remove_every_spanning_grain(inputs in inputs(), seed in any::<u64>()) {
+            let summary = simulate(&inputs, seed, TICKS);
+            prop_assert_eq!(summary.incomplete_clears, 0);
+            prop_assert!(summary.lines_cleared == 0 || summary.grains_cleared > 0);
+        }
+    }
+}
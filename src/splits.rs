@@ -0,0 +1,139 @@
+//! Clear mode splits: the time at every 10 lines and at the target, with the best run's splits
+//! persisted next to the high scores for pace comparison.
+
+use anyhow::Result;
+use std::fmt::Write as _;
+use std::fs;
+use std::time::Duration;
+
+const FILENAME: &str = "splits";
+
+/// A split every this many lines.
+const SPLIT_LINES: u32 = 10;
+
+/// Splits of the run in progress and of the best finished run for the same target.
+#[derive(Debug, Clone)]
+pub struct Splits {
+    target: u32,
+    /// Line counts with a split, ending at the target.
+    marks: Vec<u32>,
+    /// This run's split times.
+    current: Vec<Duration>,
+    /// The best run's split times (empty before the first finish).
+    best: Vec<Duration>,
+}
+
+impl Splits {
+    /// Splits for a `target`-line run, with the best run loaded from disk.
+    pub fn new(target: u32) -> Self {
+        let mut marks: Vec<u32> = (SPLIT_LINES..target)
+            .step_by(SPLIT_LINES as usize)
+            .collect();
+        marks.push(target);
+        Self {
+            target,
+            marks,
+            current: Vec::new(),
+            best: load_best(target),
+        }
+    }
+
+    /// Forget the run in progress.
+    pub fn reset(&mut self) {
+        self.current.clear();
+    }
+
+    /// Record every split passed at `lines` cleared. Returns true once the target is reached.
+    pub fn update(&mut self, lines: u32, elapsed: Duration) -> bool {
+        while let Some(&mark) = self.marks.get(self.current.len()) {
+            if lines < mark {
+                break;
+            }
+            self.current.push(elapsed);
+        }
+        self.current.len() == self.marks.len()
+    }
+
+    /// `(lines, time, seconds ahead (-) or behind (+) the best run)` for each split so far.
+    pub fn rows(&self) -> impl Iterator<Item = (u32, Duration, Option<f64>)> + '_ {
+        self.current.iter().enumerate().map(|(i, &time)| {
+            let delta = self
+                .best
+                .get(i)
+                .map(|best| time.as_secs_f64() - best.as_secs_f64());
+            (self.marks[i], time, delta)
+        })
+    }
+
+    /// The best finished run's time to the target.
+    pub fn best_time(&self) -> Option<Duration> {
+        self.best.last().copied()
+    }
+
+    /// The latest split's difference to the best run, in seconds.
+    pub fn last_delta(&self) -> Option<f64> {
+        self.rows().last().and_then(|(_, _, delta)| delta)
+    }
+
+    /// After reaching the target: keep this run as the best if it finished sooner, and save it.
+    /// Returns true if it was a new best.
+    pub fn save_if_best(&mut self) -> Result<bool> {
+        let finished = self.current.len() == self.marks.len();
+        let faster = match (self.current.last(), self.best.last()) {
+            (Some(time), Some(best)) => time < best,
+            (Some(_), None) => true,
+            (None, _) => false,
+        };
+        if !finished || !faster {
+            return Ok(false);
+        }
+        self.best.clone_from(&self.current);
+        save_best(self.target, &self.best)?;
+        Ok(true)
+    }
+}
+
+/// One line per target: the line count, then each split in milliseconds.
+fn parse_line(line: &str) -> Option<(u32, Vec<Duration>)> {
+    let mut fields = line.split_whitespace();
+    let target = fields.next()?.parse().ok()?;
+    let splits = fields
+        .map(|ms| ms.parse().ok().map(Duration::from_millis))
+        .collect::<Option<Vec<_>>>()?;
+    Some((target, splits))
+}
+
+fn load_best(target: u32) -> Vec<Duration> {
+    let path = crate::config::config_dir().join(FILENAME);
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|content| {
+            content
+                .lines()
+                .filter_map(parse_line)
+                .find(|(t, _)| *t == target)
+        })
+        .map(|(_, splits)| splits)
+        .unwrap_or_default()
+}
+
+/// Replace the line for `target`, keeping the other targets' bests.
+fn save_best(target: u32, splits: &[Duration]) -> Result<()> {
+    let path = crate::config::config_dir().join(FILENAME);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut content = String::new();
+    for line in fs::read_to_string(&path).unwrap_or_default().lines() {
+        if parse_line(line).is_some_and(|(t, _)| t != target) {
+            let _ = writeln!(content, "{line}");
+        }
+    }
+    let _ = write!(content, "{target}");
+    for split in splits {
+        let _ = write!(content, " {}", split.as_millis());
+    }
+    content.push('\n');
+    fs::write(path, content)?;
+    Ok(())
+}
@@ -0,0 +1,226 @@
+//! External state stream (`--state-socket PATH`): newline-delimited JSON snapshots of the game
+//! for stream overlays and bots.
+//!
+//! An existing named pipe (FIFO) at the path is opened for writing; its reader must be running,
+//! and a slow reader slows the game down. Otherwise, on Unix, a socket is created at the path
+//! and any number of clients can connect and disconnect at any time. A client that can't keep up
+//! loses the rest of its current line and is dropped.
+
+use crate::game::{GameEvent, GameState};
+use crate::view::BoardView;
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// One line of the stream.
+#[derive(Debug, Serialize)]
+struct Snapshot<'a> {
+    /// Snapshots sent so far (counts up from 0).
+    seq: u64,
+    /// Sand ticks since the game started.
+    tick: u32,
+    /// `GameState::state_hash` in hex; compare two streams to find where they desynced.
+    hash: String,
+    /// "menu", "playing", "paused", "game-over" or "quit-menu".
+    status: &'a str,
+    score: u32,
+    level: u32,
+    lines: u32,
+    /// Current combo multiplier (1 = no combo).
+    combo: u32,
+    /// Falling piece kind, None between pieces.
+    piece: Option<String>,
+    /// Block column the piece is centred on.
+    column: Option<i32>,
+    next: Vec<String>,
+    board: Board,
+    /// Game events since the previous snapshot.
+    events: &'a [Event],
+}
+
+#[derive(Debug, Serialize)]
+struct Board {
+    /// In block cells.
+    width: usize,
+    height: usize,
+    /// Highest sand, in block rows from the floor.
+    stack_height: usize,
+    /// Sand height of each block column.
+    columns: Vec<usize>,
+    /// Empty grains under the top of the stack, over every column.
+    holes: usize,
+    /// Sand reaches the top quarter.
+    danger: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct Event {
+    /// "spawn", "move", "rotate", "lock", "clear", "combo", "level-up" or "game-over".
+    kind: &'static str,
+    /// Piece kind for spawn, multiplier for combo, peak and bonus for combo-end, new level for
+    /// level-up.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    value: Option<String>,
+}
+
+impl From<GameEvent> for Event {
+    fn from(event: GameEvent) -> Self {
+        let (kind, value) = match event {
+            GameEvent::Spawn(kind) => ("spawn", Some(format!("{kind:?}"))),
+            GameEvent::Move => ("move", None),
+            GameEvent::Rotate => ("rotate", None),
+            GameEvent::Lock => ("lock", None),
+            GameEvent::Clear => ("clear", None),
+            GameEvent::Combo(n) => ("combo", Some(n.to_string())),
+            GameEvent::ComboEnd { peak, bonus } => ("combo-end", Some(format!("{peak} {bonus}"))),
+            GameEvent::LevelUp(level) => ("level-up", Some(level.to_string())),
+            GameEvent::Breather => ("breather", None),
+            GameEvent::Mission { bonus } => ("mission", Some(bonus.to_string())),
+            GameEvent::GameOver => ("game-over", None),
+        };
+        Self { kind, value }
+    }
+}
+
+enum Sink {
+    Pipe(Option<File>),
+    #[cfg(unix)]
+    Socket {
+        listener: std::os::unix::net::UnixListener,
+        clients: Vec<std::os::unix::net::UnixStream>,
+    },
+}
+
+/// Writer for `--state-socket`: collects events every frame, sends a snapshot every `interval`.
+pub struct StateStream {
+    sink: Sink,
+    interval: Duration,
+    last_sent: Option<Instant>,
+    seq: u64,
+    events: Vec<Event>,
+}
+
+impl StateStream {
+    /// Open the pipe at `path`, or (Unix) listen on a new socket there, replacing a stale one.
+    pub fn open(path: &Path, interval: Duration) -> Result<Self> {
+        let sink = Self::open_sink(path)
+            .with_context(|| format!("opening state socket {}", path.display()))?;
+        Ok(Self {
+            sink,
+            interval,
+            last_sent: None,
+            seq: 0,
+            events: Vec::new(),
+        })
+    }
+
+    #[cfg(unix)]
+    fn open_sink(path: &Path) -> std::io::Result<Sink> {
+        use std::os::unix::fs::FileTypeExt;
+
+        match std::fs::metadata(path) {
+            Ok(meta) if meta.file_type().is_fifo() => {
+                return Ok(Sink::Pipe(Some(File::options().write(true).open(path)?)));
+            }
+            Ok(meta) if meta.file_type().is_socket() => std::fs::remove_file(path)?,
+            _ => {}
+        }
+        let listener = std::os::unix::net::UnixListener::bind(path)?;
+        listener.set_nonblocking(true)?;
+        Ok(Sink::Socket {
+            listener,
+            clients: Vec::new(),
+        })
+    }
+
+    #[cfg(not(unix))]
+    fn open_sink(path: &Path) -> std::io::Result<Sink> {
+        Ok(Sink::Pipe(Some(File::options().write(true).open(path)?)))
+    }
+
+    /// Record this frame's events and send a snapshot if one is due (or the game just ended).
+    /// `view` is `state`'s board, up to date.
+    pub fn update(
+        &mut self,
+        state: &GameState,
+        view: &BoardView,
+        status: &str,
+        events: &[GameEvent],
+        now: Instant,
+    ) {
+        self.events.extend(events.iter().map(|&e| Event::from(e)));
+        let game_over = events.contains(&GameEvent::GameOver);
+        if !game_over
+            && self
+                .last_sent
+                .is_some_and(|at| now.duration_since(at) < self.interval)
+        {
+            return;
+        }
+        self.last_sent = Some(now);
+        let mut line = serde_json::to_vec(&snapshot(state, view, status, self.seq, &self.events))
+            .unwrap_or_default();
+        line.push(b'\n');
+        self.seq += 1;
+        self.events.clear();
+        self.send(&line);
+    }
+
+    fn send(&mut self, line: &[u8]) {
+        match &mut self.sink {
+            // The reader went away: stop writing
+            Sink::Pipe(file) => {
+                if file.as_mut().is_some_and(|f| f.write_all(line).is_err()) {
+                    *file = None;
+                }
+            }
+            #[cfg(unix)]
+            Sink::Socket { listener, clients } => {
+                while let Ok((client, _)) = listener.accept() {
+                    if client.set_nonblocking(true).is_ok() {
+                        clients.push(client);
+                    }
+                }
+                clients.retain_mut(|client| client.write_all(line).is_ok());
+            }
+        }
+    }
+}
+
+fn snapshot<'a>(
+    state: &GameState,
+    view: &BoardView,
+    status: &'a str,
+    seq: u64,
+    events: &'a [Event],
+) -> Snapshot<'a> {
+    Snapshot {
+        seq,
+        tick: state.playfield.tick_count,
+        hash: format!("{:016x}", state.state_hash()),
+        status,
+        score: state.score,
+        level: state.level,
+        lines: state.lines_cleared,
+        combo: state.combo_multiplier,
+        piece: state.piece.as_ref().map(|p| format!("{:?}", p.kind)),
+        column: state.piece_column(),
+        next: state
+            .next_pieces
+            .iter()
+            .map(|n| format!("{:?}", n.kind))
+            .collect(),
+        board: Board {
+            width: state.playfield.width,
+            height: state.playfield.height,
+            stack_height: state.stack_height(),
+            columns: view.block_heights(),
+            holes: view.hole_count(),
+            danger: state.playfield.in_danger_zone(),
+        },
+        events,
+    }
+}
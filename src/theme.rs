@@ -2,7 +2,7 @@
 
 use ratatui::style::Color;
 use std::collections::HashMap;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use thiserror::Error;
 
 /// One Dark palette and UI colours loaded from a theme file.
@@ -18,8 +18,7 @@ pub struct Theme {
     pub main_fg: Color,
     /// Highlight / titles.
     pub title: Color,
-    /// Inactive / secondary text. Reserved for future UI (e.g. pause menu).
-    #[allow(dead_code)]
+    /// Inactive / secondary text; also the colour of stone grains.
     pub inactive_fg: Color,
 }
 
@@ -73,7 +72,7 @@ impl Theme {
     }
 
     /// Default theme for a palette when no file is loaded.
-    fn default_for_palette(palette: crate::Palette) -> Self {
+    pub fn default_for_palette(palette: crate::Palette) -> Self {
         let mut t = Self::onedark_default();
         t.apply_palette(palette);
         t
@@ -146,6 +145,24 @@ impl Theme {
     }
 }
 
+/// Where `--list-themes` looks for theme files (config dir / themes).
+pub fn themes_dir() -> PathBuf {
+    crate::config::config_dir().join("themes")
+}
+
+/// `*.theme` files directly in `dir`, sorted by name. Empty if the directory is missing.
+pub fn theme_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = std::fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "theme"))
+        .collect();
+    files.sort();
+    files
+}
+
 /// Parse btop-style theme file into key -> value map.
 fn parse_theme_file(s: &str) -> HashMap<String, String> {
     let mut map = HashMap::new();
@@ -218,6 +235,22 @@ mod tests {
         assert!(matches!(c, Color::Rgb(255, 255, 255)));
     }
 
+    #[test]
+    fn test_theme_files_sorted_and_filtered() {
+        let dir = std::env::temp_dir().join(format!("setrixtui-themes-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        for name in ["b.theme", "a.theme", "notes.txt"] {
+            std::fs::write(dir.join(name), "").unwrap();
+        }
+        let names: Vec<_> = theme_files(&dir)
+            .iter()
+            .map(|p| p.file_name().unwrap().to_string_lossy().into_owned())
+            .collect();
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(names, ["a.theme", "b.theme"]);
+        assert!(theme_files(&dir).is_empty());
+    }
+
     #[test]
     fn test_parse_theme_line() {
         let map = parse_theme_file(r##"theme[meter_bg]="#31353F""##);
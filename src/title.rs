@@ -0,0 +1,164 @@
+//! Title screen: the "SETRIXTUI" logo, assembled from sand grains dropped onto a scratch
+//! playfield and run through the game's own physics. Each grain freezes into place when it
+//! falls onto the lowest missing pixel of its column; strays pile up on the floor below.
+
+use crate::game::{Cell, PhysicsParams, Playfield};
+use fastrand::Rng;
+use std::time::{Duration, Instant};
+
+/// Logo glyphs, 5 x 5, in the order of `LOGO`.
+const GLYPHS: [(char, [&str; 5]); 7] = [
+    ('S', [".####", "#....", ".###.", "....#", "####."]),
+    ('E', ["#####", "#....", "####.", "#....", "#####"]),
+    ('T', ["#####", "..#..", "..#..", "..#..", "..#.."]),
+    ('R', ["####.", "#...#", "####.", "#..#.", "#...#"]),
+    ('I', ["#####", "..#..", "..#..", "..#..", "#####"]),
+    ('X', ["#...#", ".#.#.", "..#..", ".#.#.", "#...#"]),
+    ('U', ["#...#", "#...#", "#...#", "#...#", ".###."]),
+];
+
+const LOGO: &str = "SETRIXTUI";
+
+/// Grains per glyph pixel, vertically: two grains make one terminal cell.
+const PIXEL_GRAINS: usize = 2;
+
+/// Grain columns per letter: five of glyph and one of gap.
+const LETTER_GRAINS: usize = 6;
+
+/// Scratch playfield size in block cells: 54 grains, one letter per block column.
+const FIELD_SIZE: (u16, u16) = (9, 5);
+
+/// Grain rows left under the logo for strays to pile up in.
+const FLOOR_ROWS: usize = 4;
+
+/// Grains dropped per physics step.
+const DROPS_PER_TICK: usize = 3;
+
+/// Physics step length: faster than play, so the logo forms in about two seconds.
+const TICK: Duration = Duration::from_millis(16);
+
+/// The title screen's scratch playfield and logo progress.
+#[derive(Debug, Clone)]
+pub struct Title {
+    playfield: Playfield,
+    /// Per grain column: logo pixels still to fill, (row, colour), top first.
+    missing: Vec<Vec<(usize, u8)>>,
+    /// Per grain column: grains still to drop.
+    to_drop: Vec<usize>,
+    /// Logo pixels in place: (x, y, colour).
+    pub settled: Vec<(usize, usize, u8)>,
+    rng: Rng,
+    last_tick: Instant,
+}
+
+impl Title {
+    /// A logo in `colors` sand colours. `instant` (`--reduced-motion`) shows it complete.
+    pub fn new(colors: u8, instant: bool, now: Instant) -> Self {
+        let playfield = Playfield::new(FIELD_SIZE.0, FIELD_SIZE.1);
+        let (gw, gh) = playfield.grain_dims();
+        let top = gh - FLOOR_ROWS - 5 * PIXEL_GRAINS;
+        let mut missing = vec![Vec::new(); gw];
+        for (i, (n, letter)) in (0u8..).zip(LOGO.chars()).enumerate() {
+            let Some((_, rows)) = GLYPHS.iter().find(|(c, _)| *c == letter) else {
+                continue;
+            };
+            let color = n % colors.max(1);
+            for (row, line) in rows.iter().enumerate() {
+                for (col, _) in line.char_indices().filter(|&(_, c)| c == '#') {
+                    for dy in 0..PIXEL_GRAINS {
+                        missing[i * LETTER_GRAINS + col]
+                            .push((top + row * PIXEL_GRAINS + dy, color));
+                    }
+                }
+            }
+        }
+        let to_drop = missing.iter().map(Vec::len).collect();
+        let mut title = Self {
+            playfield,
+            missing,
+            to_drop,
+            settled: Vec::new(),
+            rng: Rng::new(),
+            last_tick: now,
+        };
+        if instant {
+            title.finish();
+        }
+        title
+    }
+
+    /// Grain dimensions of the scratch playfield.
+    pub fn grain_dims(&self) -> (usize, usize) {
+        self.playfield.grain_dims()
+    }
+
+    /// Falling and piled-up grains (not yet part of the logo).
+    pub fn grain(&self, x: usize, y: usize) -> Option<Cell> {
+        self.playfield.get(x, y).filter(|c| c.is_solid())
+    }
+
+    /// True once every logo pixel is in place.
+    pub fn done(&self) -> bool {
+        self.missing.iter().all(Vec::is_empty)
+    }
+
+    /// Run the physics steps due by `now`.
+    pub fn tick(&mut self, now: Instant) {
+        let params = PhysicsParams::default();
+        while now.duration_since(self.last_tick) >= TICK {
+            self.last_tick += TICK;
+            self.drop_grains();
+            let left_first = self.rng.bool();
+            let moved = self
+                .playfield
+                .tick_physics(left_first, &params, &mut self.rng);
+            self.freeze_arrivals();
+            // Strays that slid off their column leave pixels no grain will reach
+            if !moved && self.to_drop.iter().all(|&n| n == 0) {
+                self.finish();
+            }
+        }
+    }
+
+    /// Release up to `DROPS_PER_TICK` grains at the top, in random columns that need them.
+    fn drop_grains(&mut self) {
+        for _ in 0..DROPS_PER_TICK {
+            let columns: Vec<usize> = (0..self.to_drop.len())
+                .filter(|&x| self.to_drop[x] > 0 && self.playfield.get(x, 0) == Some(Cell::Empty))
+                .collect();
+            if columns.is_empty() {
+                return;
+            }
+            let x = columns[self.rng.usize(..columns.len())];
+            let Some(&(_, color)) = self.missing[x].last() else {
+                continue;
+            };
+            self.to_drop[x] -= 1;
+            self.playfield.set(x, 0, Cell::Sand(color, false));
+        }
+    }
+
+    /// Grains that reached the lowest missing pixel of their column become part of the logo,
+    /// and so does the grain right behind one, before it falls through.
+    fn freeze_arrivals(&mut self) {
+        for (x, missing) in self.missing.iter_mut().enumerate() {
+            while let Some(&(y, color)) = missing.last() {
+                if !self.playfield.get(x, y).is_some_and(Cell::is_solid) {
+                    break;
+                }
+                self.playfield.set(x, y, Cell::Empty);
+                self.settled.push((x, y, color));
+                missing.pop();
+            }
+        }
+    }
+
+    /// Put every missing pixel in place at once.
+    fn finish(&mut self) {
+        for (x, missing) in self.missing.iter_mut().enumerate() {
+            self.settled
+                .extend(missing.drain(..).map(|(y, color)| (x, y, color)));
+        }
+        self.to_drop.fill(0);
+    }
+}
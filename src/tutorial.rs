@@ -0,0 +1,127 @@
+//! `--tutorial`: a guided first game. Each step asks for one thing (move, rotate, drop, clear a
+//! bridge, chain a combo) and waits until the player has done it. The clearing steps lay a
+//! near-complete bridge on an empty board (see `GameState::build_bridge`).
+
+use crate::game::GameEvent;
+use crate::input::{Action, Keymap};
+
+/// Combo window while in the tutorial (sand ticks), long enough to line up a second piece.
+pub const COMBO_TICKS: u32 = 360;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Step {
+    Move,
+    Rotate,
+    SoftDrop,
+    HardDrop,
+    Bridge,
+    Combo,
+    Done,
+}
+
+/// Tutorial progress.
+#[derive(Debug, Clone)]
+pub struct Tutorial {
+    step: Step,
+    /// Move step: left and right pressed so far.
+    moved: (bool, bool),
+    /// A bridge is to be laid once the current line clear has finished.
+    pub bridge_due: bool,
+}
+
+impl Default for Tutorial {
+    fn default() -> Self {
+        Self {
+            step: Step::Move,
+            moved: (false, false),
+            bridge_due: false,
+        }
+    }
+}
+
+impl Tutorial {
+    /// A new game started: a clearing step needs its bridge again.
+    pub fn restart(&mut self) {
+        self.bridge_due = matches!(self.step, Step::Bridge | Step::Combo);
+    }
+
+    /// Count a gameplay action towards the current step.
+    pub fn on_action(&mut self, action: Action) {
+        let done = match (self.step, action) {
+            (Step::Move, Action::MoveLeft) => {
+                self.moved.0 = true;
+                self.moved.1
+            }
+            (Step::Move, Action::MoveRight) => {
+                self.moved.1 = true;
+                self.moved.0
+            }
+            (Step::Rotate, Action::RotateCw | Action::RotateCcw)
+            | (Step::SoftDrop, Action::SoftDrop)
+            | (Step::HardDrop, Action::HardDrop) => true,
+            _ => false,
+        };
+        if done {
+            self.advance();
+        }
+    }
+
+    /// Clears complete the bridge steps; a clear without a combo lays the combo bridge again.
+    pub fn on_events(&mut self, events: &[GameEvent]) {
+        let cleared = events.contains(&GameEvent::Clear);
+        match self.step {
+            Step::Bridge if cleared => self.advance(),
+            Step::Combo if events.iter().any(|e| matches!(e, GameEvent::Combo(_))) => {
+                self.advance();
+            }
+            Step::Combo if cleared => self.bridge_due = true,
+            _ => {}
+        }
+    }
+
+    fn advance(&mut self) {
+        self.step = match self.step {
+            Step::Move => Step::Rotate,
+            Step::Rotate => Step::SoftDrop,
+            Step::SoftDrop => Step::HardDrop,
+            Step::HardDrop => Step::Bridge,
+            Step::Bridge => Step::Combo,
+            Step::Combo | Step::Done => Step::Done,
+        };
+        self.restart();
+    }
+
+    /// Instruction for the current step, with the keys from `keys`.
+    pub fn prompt(&self, keys: &Keymap) -> String {
+        let key = |action| keys.key_name(action);
+        let (step, text) = match self.step {
+            Step::Move => (
+                1,
+                format!(
+                    "Move the piece with {} and {}",
+                    key(Action::MoveLeft),
+                    key(Action::MoveRight)
+                ),
+            ),
+            Step::Rotate => (2, format!("Rotate it with {}", key(Action::RotateCw))),
+            Step::SoftDrop => (3, format!("Hold {} to drop faster", key(Action::SoftDrop))),
+            Step::HardDrop => (
+                4,
+                format!("Press {} to drop it at once", key(Action::HardDrop)),
+            ),
+            Step::Bridge => (
+                5,
+                "One colour from wall to wall clears: drop the piece on the bridge's right end"
+                    .to_string(),
+            ),
+            Step::Combo => (
+                6,
+                "Clear again before the combo timer runs out to multiply your points".to_string(),
+            ),
+            Step::Done => {
+                return " Tutorial complete! Keep playing, or press Esc to leave ".to_string();
+            }
+        };
+        format!(" {step}/6  {text} ")
+    }
+}
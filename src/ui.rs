@@ -1,13 +1,20 @@
 //! Layout and drawing: menu, playfield, pause, game over, next preview, colour strip, score.
 
 use crate::GameMode;
-use crate::app::{GameOverReason, MenuState, MenuTab, Screen};
-use crate::game::{Cell, GameState, TetrominoKind};
+use crate::app::{GameOverReason, MenuState, MenuTab, MenuTarget, PauseReason, Screen};
+use crate::game::{
+    Cell, DROUGHT_LIMIT, FOCUS_MAX, FOCUS_TICKS, GRAIN_SCALE, GameState, NextPiece, PieceKind,
+    ZONE_MAX, ZONE_TICKS,
+};
+use crate::input::Action;
+use crate::records::{Record, Records};
+use crate::splits::Splits;
+use clap::ValueEnum;
 use ratatui::Frame;
 use ratatui::layout::{Alignment, Constraint, Direction, Layout, Position, Rect};
 use ratatui::style::{Color, Style};
 use ratatui::text::{Line, Span};
-use ratatui::widgets::{Block, Borders, Gauge, Paragraph, Widget};
+use ratatui::widgets::{Block, Borders, Gauge, Paragraph, Widget, Wrap};
 use std::collections::HashSet;
 use std::time::Instant;
 use tachyonfx::{
@@ -65,25 +72,48 @@ pub fn playfield_size_indicator_color(w: u16, h: u16) -> Color {
 }
 
 const SIDEBAR_WIDTH: u16 = 24;
-/// Combo duration in ticks (must match game.rs process_clears reset).
-const COMBO_TIMER_MAX: u32 = 90;
+
+/// Columns to spare beyond the board and two sidebars before the game screen splits the sidebar
+/// in two, one on each side of the board.
+const WIDESCREEN_SLACK: u16 = 40;
 
 /// Duration of line-clear fade (TachyonFX) in ms (SPEC §14.1: ~30 ms per grain).
 const LINE_CLEAR_FADE_MS: u32 = 400;
 
+/// Line-clear fade with `--reduce-flashing`: slower, from the desaturated colours.
+const LINE_CLEAR_CALM_FADE_MS: u32 = 900;
+
+/// `--symbols`: one character per sand colour index, drawn over that colour.
+const SAND_SYMBOLS: [&str; 6] = ["·", "x", "+", "o", "#", "%"];
+
+/// Bomb blast: flash to hot orange, then dissolve the grains into particles.
+const BLAST_FLASH_MS: u32 = 120;
+const BLAST_DISSOLVE_MS: u32 = 450;
+const BLAST_COLOR: Color = Color::Rgb(255, 140, 40);
+
+/// Focus gauge colour, and the tint of the empty board during focus.
+const FOCUS_TINT: Color = Color::Rgb(80, 120, 255);
+
+/// Board border during an Endless breather.
+const BREATHER_BORDER: Color = Color::Rgb(120, 230, 160);
+
+/// Banked zone rows, in alternating block-row stripes.
+const ZONE_BANK_COLORS: [Color; 2] = [Color::Rgb(235, 225, 255), Color::Rgb(200, 185, 240)];
+
+/// `--dynamic-background`: tints of the empty board as the stack rises and during a combo,
+/// the tint's strength at the bottom from calm to full, and how much a dust speck adds.
+const BACKDROP_DANGER: Color = Color::Rgb(200, 40, 40);
+const BACKDROP_COMBO: Color = Color::Rgb(230, 180, 40);
+const BACKDROP_STRENGTH: (f32, f32) = (0.04, 0.24);
+const BACKDROP_DUST: f32 = 0.12;
+
+/// Invisible sand: grains stay visible this long after they last moved, then fade out.
+const INVISIBLE_SHOW_MS: u128 = 1000;
+const INVISIBLE_FADE_MS: u128 = 400;
+
 /// Playfield inner rect (board only, no border) for given area and state; matches draw_game layout.
 fn playfield_board_rect(area: Rect, state: &GameState) -> Rect {
-    let (pw, ph) =
-        playfield_pixel_size(state.playfield.width as u16, state.playfield.height as u16);
-    let total_w = pw + SIDEBAR_WIDTH;
-    let x = area.x + area.width.saturating_sub(total_w) / 2;
-    let y = area.y + area.height.saturating_sub(ph) / 2;
-    let playfield_outer = Rect {
-        x,
-        y,
-        width: pw.min(area.width),
-        height: ph.min(area.height),
-    };
+    let (playfield_outer, _, _) = game_layout(area, state);
     Rect {
         x: playfield_outer.x + 1,
         y: playfield_outer.y + 1,
@@ -159,21 +189,15 @@ fn apply_shading(color: Color, gx: usize, gy: usize, state: &GameState) -> Color
     let mut final_factor = bevel_factor * edge_darkness;
 
     // Check if this grain is a shadow (either from playfield or piece)
-    let is_shadow = if let Some(crate::game::Cell::Sand(_, s)) = state.playfield.get(gx, gy) {
+    let is_shadow = if let Some(
+        Cell::Sand(_, s) | Cell::Rainbow(s) | Cell::Stone(s) | Cell::Converter(_, s),
+    ) = state.playfield.get(gx, gy)
+    {
         s
-    } else if let Some(ref piece) = state.piece {
-        let origins = state
-            .piece_draw_origins()
-            .unwrap_or_else(|| piece.cell_grain_origins());
+    } else {
         let mut piece_shadow = false;
-        for (pgx, pgy) in origins {
-            if gx as i32 >= pgx
-                && (gx as i32) < pgx + s as i32
-                && gy as i32 >= pgy
-                && (gy as i32) < pgy + s as i32
-            {
-                let dx = gx as i32 - pgx;
-                let dy = gy as i32 - pgy;
+        for origin in state.drawn_pieces().into_iter().flat_map(|(_, origins)| origins) {
+            if let Some((dx, dy)) = state.offset_in_cell(origin, gx, gy) {
                 if dx == s as i32 - 1 || dy == s as i32 - 1 {
                     piece_shadow = true;
                 }
@@ -181,8 +205,6 @@ fn apply_shading(color: Color, gx: usize, gy: usize, state: &GameState) -> Color
             }
         }
         piece_shadow
-    } else {
-        false
     };
 
     if is_shadow {
@@ -219,6 +241,7 @@ fn apply_line_clear_effect(
     line_clear_effect: &mut Option<Effect>,
     line_clear_process_time: &mut Option<Instant>,
     now: Instant,
+    reduce_flashing: bool,
 ) {
     let board_rect = playfield_board_rect(area, state);
     let delta = line_clear_process_time
@@ -234,9 +257,18 @@ fn apply_line_clear_effect(
             clearing_set.contains(&(pos.x, pos.y))
         }));
         let bg = state.theme.bg;
-        let effect = fx::fade_to(bg, bg, (LINE_CLEAR_FADE_MS, Interpolation::Linear))
-            .with_filter(filter)
-            .with_area(board_rect);
+        let effect = if reduce_flashing {
+            fx::fade_to(bg, bg, (LINE_CLEAR_CALM_FADE_MS, Interpolation::SineInOut))
+        } else if state.line_clear_blast {
+            fx::sequence(&[
+                fx::fade_to(BLAST_COLOR, BLAST_COLOR, (BLAST_FLASH_MS, Interpolation::QuadOut)),
+                fx::dissolve((BLAST_DISSOLVE_MS, Interpolation::Linear)),
+            ])
+        } else {
+            fx::fade_to(bg, bg, (LINE_CLEAR_FADE_MS, Interpolation::Linear))
+        }
+        .with_filter(filter)
+        .with_area(board_rect);
         *line_clear_effect = Some(effect);
     }
 
@@ -245,15 +277,79 @@ fn apply_line_clear_effect(
     }
 }
 
+/// Menu attract demo brightness, in percent of the real colours.
+const DEMO_DIM_PERCENT: u16 = 35;
+
 /// Next preview: small grid.
 const NEXT_PREVIEW_COLS: u16 = 4;
 const NEXT_PREVIEW_ROWS: u16 = 2;
+/// Width of a piece's slot in the vertical next queue: an I5 at two columns per cell.
+const NEXT_SLOT_COLS: u16 = 10;
 const NEXT_MINI_CELL_W: u16 = 2;
 const NEXT_MINI_CELL_H: u16 = 1;
 
 /// High scores per mode: (endless, timed, clear).
 pub type HighScores = (u32, u32, u32);
 
+/// How to render, independent of the game state.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RenderOptions<'a> {
+    /// Skip the line clear effect.
+    pub no_animation: bool,
+    /// `--dynamic-background`: see `Backdrop`.
+    pub dynamic_background: bool,
+    /// `--reduced-motion`: no menu slide-in or new record celebration.
+    pub reduced_motion: bool,
+    /// `--reduce-flashing`: clearing grains turn grey and fade slowly instead of flashing.
+    pub reduce_flashing: bool,
+    /// `--symbols`: colour symbols (see `SAND_SYMBOLS`) on every other cell of the board.
+    pub symbols: bool,
+    /// `--blend-sand`: see `blend_boundary`.
+    pub blend_sand: bool,
+    /// `--a11y`: board as characters (see `draw_a11y_board`) in white on black.
+    pub high_contrast_board: bool,
+    /// `--a11y`: announcement for the status line at the bottom (reserved even when empty).
+    pub announcement: Option<&'a str>,
+    /// `--event-log`: recent event lines, oldest first.
+    pub events: Option<&'a [String]>,
+    /// `--show-inputs`: recent actions and when they were pressed, oldest first.
+    pub inputs: &'a [(Action, Instant)],
+    /// F3: diagnostics overlay figures, while shown.
+    pub diagnostics: Option<crate::diagnostics::Stats>,
+    /// Game over: the game's render and logic frame times.
+    pub frame_times: Option<&'a crate::diagnostics::FrameTimes>,
+    /// Rendering keeps missing the frame budget: show `SLOW_HINT` once.
+    pub slow_hint: bool,
+    /// Adaptive quality or `--low-bandwidth`: sand is drawn flat, score popups are skipped and `LOW_QUALITY_MARK`
+    /// shows in the corner.
+    pub low_quality: bool,
+    /// A notice for the top of the screen: where the F10 state dump went, or a failed paste,
+    /// copy or save.
+    pub notice: Option<&'a str>,
+    /// `--tutorial`: the current step's instruction, shown above the board.
+    pub tutorial: Option<&'a str>,
+    /// `--missions`: the current objective, in the sidebar.
+    pub mission: Option<&'a crate::missions::MissionCard>,
+    /// `--rewind`, when the game over screen can offer it.
+    pub rewind: Option<&'a crate::rewind::Rewind>,
+    /// Title screen logo, while on it.
+    pub title: Option<&'a crate::title::Title>,
+    /// Game over: the board is draining away in grey; the popup waits until it has gone.
+    pub draining: bool,
+    /// `--hot-seat`: players and scores for the standings screen.
+    pub hot_seat: Option<&'a crate::hotseat::HotSeat>,
+    /// `--hot-seat`: the player at the keyboard, named in the board title during a game.
+    pub player: Option<&'a str>,
+    /// Custom mode form, while open.
+    pub custom_form: Option<&'a crate::custom::CustomForm>,
+}
+
+const SLOW_HINT: &str = " Rendering is slow here: try --no-animation or a smaller window. \
+                         `setrixtui doctor` reports why ";
+
+/// Bottom-right corner mark while effects are turned down for a slow terminal.
+const LOW_QUALITY_MARK: &str = " reduced effects ";
+
 /// Draw current screen (menu, game, game over), with optional pause overlay and game-over reason.
 /// When `line_clear_in_progress` and !no_animation, applies TachyonFX fade effect and updates
 /// `line_clear_effect` / `line_clear_process_time`.
@@ -262,7 +358,7 @@ pub fn draw(
     frame: &mut Frame,
     screen: Screen,
     state: &GameState,
-    paused: bool,
+    paused: Option<PauseReason>,
     game_over_reason: Option<GameOverReason>,
     mode: GameMode,
     clear_lines: u32,
@@ -273,19 +369,37 @@ pub fn draw(
     line_clear_process_time: &mut Option<Instant>,
     menu_state: &mut MenuState,
     now: Instant,
-    no_animation: bool,
+    options: RenderOptions<'_>,
     quit_selected: Option<crate::app::QuitOption>,
+    settings: Option<(crate::HandlingConfig, crate::KeyProfile, crate::MusicTrack, usize)>,
     menu_playfield_size: Option<(u16, u16)>,
     high_scores: HighScores,
     new_high_score_this_game: bool,
+    celebration: &mut Option<Celebration>,
     time_to_40_secs: Option<u64>,
+    splits: &Splits,
+    records: &Records,
     autoplay: bool,
 ) {
+    let area = if let Some(text) = options.announcement {
+        let [main, status] = Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(area);
+        Paragraph::new(text)
+            .style(Style::default().fg(Color::White).bg(Color::Black))
+            .render(status, frame.buffer_mut());
+        main
+    } else {
+        area
+    };
     match screen {
+        Screen::Title => {
+            if let Some(title) = options.title {
+                draw_title(frame, state, area, title);
+            }
+        }
         Screen::Menu => {
-            // Draw game in background if Autopilot is active
+            // Attract demo: the bot's game, dimmed behind the menu
             if autoplay {
-                 draw_game(
+                draw_game(
                     frame,
                     state,
                     area,
@@ -295,11 +409,38 @@ pub fn draw(
                     now,
                     high_scores,
                     time_to_40_secs,
+                    splits,
                     clear_lines,
                     autoplay,
-                 );
+                    options,
+                );
+                if state.line_clear_in_progress
+                    && !state.line_clear_cells.is_empty()
+                    && !options.no_animation
+                {
+                    apply_line_clear_effect(
+                        frame,
+                        state,
+                        area,
+                        line_clear_effect,
+                        line_clear_process_time,
+                        now,
+                        options.reduce_flashing,
+                    );
+                }
+                dim(frame.buffer_mut(), area);
             }
-            draw_menu(frame, state, menu_state, area, now, menu_playfield_size);
+            draw_menu(
+                frame,
+                state,
+                menu_state,
+                area,
+                now,
+                menu_playfield_size,
+                options.reduced_motion,
+                records.get(menu_state.selected_mode, menu_state.selected_difficulty),
+                splits.best_time(),
+            );
         }
         Screen::Playing => {
             draw_game(
@@ -312,13 +453,18 @@ pub fn draw(
                 now,
                 high_scores,
                 time_to_40_secs,
+                splits,
                 clear_lines,
                 autoplay,
+                options,
             );
-            if paused {
-                draw_pause_overlay(frame, state, area);
+            if let Some(reason) = paused {
+                draw_pause_overlay(frame, state, area, reason);
             }
-            if state.line_clear_in_progress && !state.line_clear_cells.is_empty() && !no_animation {
+            if state.line_clear_in_progress
+                && !state.line_clear_cells.is_empty()
+                && !options.no_animation
+            {
                 apply_line_clear_effect(
                     frame,
                     state,
@@ -326,6 +472,7 @@ pub fn draw(
                     line_clear_effect,
                     line_clear_process_time,
                     now,
+                    options.reduce_flashing,
                 );
             }
         }
@@ -340,13 +487,40 @@ pub fn draw(
                 now,
                 high_scores,
                 time_to_40_secs,
+                splits,
                 clear_lines,
                 autoplay,
+                options,
             );
             if let Some(opt) = quit_selected {
                 draw_quit_menu(frame, state, opt);
             }
         }
+        Screen::Settings => {
+            if let Some((handling, profile, music, selected)) = settings {
+                draw_settings(frame, state, area, &handling, profile, music, selected);
+            }
+        }
+        Screen::CustomMode => {
+            if let Some(form) = options.custom_form {
+                draw_custom_mode(frame, state, area, form);
+            }
+        }
+        Screen::GameOver if options.draining => draw_game(
+            frame,
+            state,
+            area,
+            mode,
+            time_limit,
+            game_start,
+            now,
+            high_scores,
+            time_to_40_secs,
+            splits,
+            clear_lines,
+            autoplay,
+            options,
+        ),
         Screen::GameOver => draw_game_over(
             frame,
             state,
@@ -357,26 +531,124 @@ pub fn draw(
             game_start,
             area,
             high_scores,
-            new_high_score_this_game,
+            new_high_score_this_game.then_some(celebration),
             time_to_40_secs,
+            splits,
+            now,
+            options.reduced_motion,
+            options.hot_seat.is_some(),
+            options.frame_times,
+            options.rewind,
         ),
+        Screen::Standings => {
+            if let Some(hot_seat) = options.hot_seat {
+                draw_standings(frame, state, area, hot_seat);
+            }
+        }
+    }
+    if let Some(figures) = options.diagnostics {
+        draw_diagnostics(frame, state, area, figures);
+    }
+    if let Some(prompt) = options.tutorial.filter(|_| screen == Screen::Playing) {
+        let width = (prompt.chars().count() as u16).min(area.width);
+        let rect = Rect {
+            x: area.x + (area.width - width) / 2,
+            y: area.y,
+            width,
+            height: 1.min(area.height),
+        };
+        Paragraph::new(prompt)
+            .style(Style::default().fg(Color::Black).bg(Color::Cyan))
+            .render(rect, frame.buffer_mut());
+    }
+    if options.slow_hint {
+        let width = (SLOW_HINT.chars().count() as u16).min(area.width);
+        let rect = Rect {
+            x: area.x + (area.width - width) / 2,
+            y: area.bottom().saturating_sub(1),
+            width,
+            height: 1.min(area.height),
+        };
+        Paragraph::new(SLOW_HINT)
+            .style(Style::default().fg(Color::Black).bg(Color::Yellow))
+            .render(rect, frame.buffer_mut());
+    }
+    if options.low_quality {
+        let width = (LOW_QUALITY_MARK.chars().count() as u16).min(area.width);
+        let rect = Rect {
+            x: area.right().saturating_sub(width),
+            y: area.bottom().saturating_sub(1),
+            width,
+            height: 1.min(area.height),
+        };
+        Paragraph::new(LOW_QUALITY_MARK)
+            .style(Style::default().fg(state.theme.inactive_fg))
+            .render(rect, frame.buffer_mut());
     }
+    if let Some(notice) = options.notice {
+        let width = (notice.chars().count() as u16).min(area.width);
+        let rect = Rect {
+            x: area.x + (area.width - width) / 2,
+            y: area.y,
+            width,
+            height: 1.min(area.height),
+        };
+        Paragraph::new(notice)
+            .style(Style::default().fg(Color::Black).bg(Color::Yellow))
+            .render(rect, frame.buffer_mut());
+    }
+}
+
+/// F3 overlay in the top-left corner, white on black so it reads over any theme.
+fn draw_diagnostics(
+    frame: &mut Frame,
+    state: &GameState,
+    area: Rect,
+    figures: crate::diagnostics::Stats,
+) {
+    let ms = |d: std::time::Duration| d.as_secs_f64() * 1000.0;
+    let lines = vec![
+        Line::from(format!(" FPS {:.1}  TPS {:.1} ", figures.fps, figures.tps)),
+        Line::from(format!(" Physics {:.2} ms/tick ", ms(figures.physics))),
+        Line::from(format!(
+            " Grains {} ",
+            state.playfield.grain_count() + state.frozen_grains.len()
+        )),
+        Line::from(format!(
+            " Loop {:.1} ms (max {:.1}) ",
+            ms(figures.latency),
+            ms(figures.latency_max)
+        )),
+    ];
+    let width = lines.iter().map(Line::width).max().unwrap_or(0) as u16;
+    let rect = Rect {
+        x: area.x,
+        y: area.y,
+        width: width.min(area.width),
+        height: (lines.len() as u16).min(area.height),
+    };
+    Paragraph::new(lines)
+        .style(Style::default().fg(Color::White).bg(Color::Black))
+        .render(rect, frame.buffer_mut());
 }
 
 fn draw_menu(
     frame: &mut Frame,
     state: &GameState,
-    menu_state: &MenuState,
+    menu_state: &mut MenuState,
     area: Rect,
     now: Instant,
     menu_playfield_size: Option<(u16, u16)>,
+    reduced_motion: bool,
+    record: Record,
+    best_time: Option<std::time::Duration>,
 ) {
     let popup_w = 48u16;
     let popup_h = if menu_playfield_size.is_some() {
-        22
+        25
     } else {
-        20
-    };
+        23
+    } + if menu_state.has_mode_options() { 2 } else { 0 };
     let popup = Rect {
         x: area.x + area.width.saturating_sub(popup_w) / 2,
         y: area.y + area.height.saturating_sub(popup_h) / 2,
@@ -465,12 +737,13 @@ fn draw_menu(
         ),
     );
 
+    // Built-in modes show as picked unless Custom is
+    let builtin = |mode| menu_state.custom.is_none() && menu_state.selected_mode == mode;
     let mode_endless = Span::styled(
         " ENDLESS ",
         tab_style(
-            menu_state.current_tab == MenuTab::Mode
-                && menu_state.selected_mode == crate::GameMode::Endless,
-            menu_state.selected_mode == crate::GameMode::Endless,
+            menu_state.current_tab == MenuTab::Mode && builtin(crate::GameMode::Endless),
+            builtin(crate::GameMode::Endless),
             highlight_style,
             selected_style,
             normal_style,
@@ -479,9 +752,8 @@ fn draw_menu(
     let mode_timed = Span::styled(
         " TIMED ",
         tab_style(
-            menu_state.current_tab == MenuTab::Mode
-                && menu_state.selected_mode == crate::GameMode::Timed,
-            menu_state.selected_mode == crate::GameMode::Timed,
+            menu_state.current_tab == MenuTab::Mode && builtin(crate::GameMode::Timed),
+            builtin(crate::GameMode::Timed),
             highlight_style,
             selected_style,
             normal_style,
@@ -490,9 +762,41 @@ fn draw_menu(
     let mode_clear = Span::styled(
         " CLEAR40 ",
         tab_style(
-            menu_state.current_tab == MenuTab::Mode
-                && menu_state.selected_mode == crate::GameMode::Clear,
-            menu_state.selected_mode == crate::GameMode::Clear,
+            menu_state.current_tab == MenuTab::Mode && builtin(crate::GameMode::Clear),
+            builtin(crate::GameMode::Clear),
+            highlight_style,
+            selected_style,
+            normal_style,
+        ),
+    );
+
+    let mode_custom = Span::styled(
+        " CUSTOM ",
+        tab_style(
+            menu_state.current_tab == MenuTab::Mode && menu_state.custom.is_some(),
+            menu_state.custom.is_some(),
+            highlight_style,
+            selected_style,
+            normal_style,
+        ),
+    );
+
+    let handling_btn = if menu_state.current_tab == MenuTab::Handling {
+        Span::styled(" [ HANDLING ] ", highlight_style)
+    } else {
+        Span::styled(" [ HANDLING ] ", normal_style)
+    };
+
+    let seed = if menu_state.seed.is_empty() {
+        "random"
+    } else {
+        &menu_state.seed
+    };
+    let seed_btn = Span::styled(
+        format!(" SEED ‹ {seed} › "),
+        tab_style(
+            menu_state.current_tab == MenuTab::Seed,
+            !menu_state.seed.is_empty(),
             highlight_style,
             selected_style,
             normal_style,
@@ -507,10 +811,28 @@ fn draw_menu(
 
     let playfield_size_line = menu_playfield_size.map(|(w, h)| {
         let color = playfield_size_indicator_color(w, h);
-        Line::from(Span::styled(
+        let mut spans = vec![Span::styled(
             format!(" Playfield {}×{} ", w, h),
             Style::default().fg(color).bold(),
-        ))
+        )];
+        let profile = menu_state.profiles.get(menu_state.selected_difficulty);
+        let (dw, dh) = (profile.narrower, profile.shorter);
+        let adjust: Vec<String> = [(dw, "col"), (dh, "row")]
+            .into_iter()
+            .filter(|&(n, _)| n > 0)
+            .map(|(n, unit)| format!("−{n} {unit}{}", if n == 1 { "" } else { "s" }))
+            .collect();
+        if !adjust.is_empty() {
+            spans.push(Span::styled(
+                format!("({}) ", adjust.join(", ")),
+                Style::default().fg(state.theme.inactive_fg),
+            ));
+        }
+        spans.push(Span::styled(
+            "+/− zoom ",
+            Style::default().fg(state.theme.inactive_fg),
+        ));
+        Line::from(spans)
     });
 
     let mut lines = vec![Line::from(""), title, ratman_tag, Line::from("")];
@@ -518,6 +840,17 @@ fn draw_menu(
         lines.push(line);
         lines.push(Line::from(""));
     }
+    // Clickable spans as (line index, span index, target); placed on screen after layout.
+    let base = lines.len();
+    let mut targets = vec![
+        (base + 2, 0, MenuTarget::Difficulty(crate::Difficulty::Easy)),
+        (base + 2, 2, MenuTarget::Difficulty(crate::Difficulty::Medium)),
+        (base + 2, 4, MenuTarget::Difficulty(crate::Difficulty::Hard)),
+        (base + 5, 0, MenuTarget::Mode(GameMode::Endless)),
+        (base + 5, 2, MenuTarget::Mode(GameMode::Timed)),
+        (base + 5, 4, MenuTarget::Mode(GameMode::Clear)),
+        (base + 5, 6, MenuTarget::Custom),
+    ];
     lines.extend([
         Line::from(""),
         Line::from(Span::styled(
@@ -542,7 +875,48 @@ fn draw_menu(
             mode_timed,
             Span::from("  "),
             mode_clear,
+            Span::from("  "),
+            mode_custom,
         ]),
+    ]);
+    if menu_state.has_mode_options() {
+        let goal = if menu_state.custom.is_some() {
+            let name = menu_state
+                .custom_mode()
+                .map_or("+ new", |custom| &custom.name);
+            format!(" CUSTOM ‹ {name} › ")
+        } else if menu_state.selected_mode == GameMode::Timed {
+            let secs = menu_state.time_limit;
+            format!(" TIME LIMIT ‹ {}:{:02} › ", secs / 60, secs % 60)
+        } else {
+            format!(" TARGET ‹ {} lines › ", menu_state.clear_lines)
+        };
+        let width = menu_state
+            .board_width
+            .map_or_else(|| "auto".to_string(), |w| w.to_string());
+        let row_style = |tab: MenuTab| {
+            tab_style(
+                menu_state.current_tab == tab,
+                false,
+                highlight_style,
+                selected_style,
+                normal_style,
+            )
+        };
+        targets.push((lines.len(), 0, MenuTarget::ModeGoal));
+        targets.push((lines.len() + 1, 0, MenuTarget::BoardWidth));
+        lines.extend([
+            Line::from(Span::styled(goal, row_style(MenuTab::ModeGoal))),
+            Line::from(Span::styled(
+                format!(" BOARD WIDTH ‹ {width} › "),
+                row_style(MenuTab::BoardWidth),
+            )),
+        ]);
+    }
+    let base = lines.len();
+    targets.push((base + 2, 0, MenuTarget::Autoplay));
+    targets.push((base + 2, 3, MenuTarget::AutoRestart));
+    lines.extend([
         Line::from(""),
         Line::from(Span::styled(
             " ─ AUTOPILOT ─ ",
@@ -581,8 +955,14 @@ fn draw_menu(
         ]),
     ]);
 
+    let base = lines.len();
+    targets.push((base + 1, 0, MenuTarget::Handling));
+    targets.push((base + 2, 0, MenuTarget::Seed));
+    targets.push((base + 4, 0, MenuTarget::Start));
     lines.extend([
         Line::from(""),
+        Line::from(handling_btn),
+        Line::from(seed_btn),
         Line::from(""),
         Line::from(start_btn),
         Line::from(""),
@@ -602,16 +982,14 @@ fn draw_menu(
         )),
     ]);
 
-    let p = Paragraph::new(lines).alignment(Alignment::Center).block(
-        Block::default()
-            .borders(Borders::ALL)
-            .border_style(Style::default().fg(state.theme.div_line).bg(state.theme.bg)),
-    );
-
     // Startup animation: slide in from bottom
     let elapsed = now.duration_since(menu_state.animation_start).as_millis() as u32;
     let anim_duration = 500u32;
-    let t = (elapsed as f32 / anim_duration as f32).min(1.0);
+    let t = if reduced_motion {
+        1.0
+    } else {
+        (elapsed as f32 / anim_duration as f32).min(1.0)
+    };
     // Ease out cubic
     let offset_t = 1.0 - (1.0 - t).powi(3);
 
@@ -620,6 +998,29 @@ fn draw_menu(
     anim_popup.y += anim_y_offset;
     anim_popup = anim_popup.intersection(area);
 
+    // Record where the clickable items end up (centred lines inside the border)
+    let inner_w = anim_popup.width.saturating_sub(2);
+    menu_state.click_targets = targets
+        .into_iter()
+        .map(|(li, si, target)| {
+            let line = &lines[li];
+            let offset: usize = line.spans[..si].iter().map(Span::width).sum();
+            let x = anim_popup.x
+                + 1
+                + (inner_w / 2).saturating_sub(line.width() as u16 / 2)
+                + offset as u16;
+            let y = anim_popup.y + 1 + li as u16;
+            let rect = Rect::new(x, y, line.spans[si].width() as u16, 1);
+            (rect.intersection(anim_popup), target)
+        })
+        .collect();
+
+    let p = Paragraph::new(lines).alignment(Alignment::Center).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(state.theme.div_line).bg(state.theme.bg)),
+    );
+
     if t < 1.0 {
         // Fade in effect
         let _alpha = (t * 255.0) as u8;
@@ -632,14 +1033,255 @@ fn draw_menu(
     frame.render_widget(ratatui::widgets::Clear, anim_popup);
 
     p.render(anim_popup, frame.buffer_mut());
+    let best_time = best_time.filter(|_| menu_state.selected_mode == GameMode::Clear);
+    draw_records_panel(frame, state, area, anim_popup, record, best_time);
 
     if !state.game_over && elapsed < anim_duration {
         // Trigger redraw
     }
 }
 
-fn draw_pause_overlay(frame: &mut Frame, state: &GameState, area: Rect) {
-    let popup_w = 28u16;
+/// Records for the highlighted mode and difficulty, right of the menu when there is room.
+fn draw_records_panel(
+    frame: &mut Frame,
+    state: &GameState,
+    area: Rect,
+    menu: Rect,
+    record: Record,
+    best_time: Option<std::time::Duration>,
+) {
+    const PANEL_W: u16 = 24;
+    if menu.right() + 1 + PANEL_W > area.right() {
+        return;
+    }
+    let panel = Rect {
+        x: menu.right() + 1,
+        y: menu.y,
+        width: PANEL_W,
+        height: 8.min(menu.height),
+    };
+    let label = Style::default().fg(state.theme.inactive_fg);
+    let value = Style::default().fg(state.theme.main_fg).bold();
+    let row = |name: &'static str, text: String| {
+        Line::from(vec![
+            Span::styled(format!(" {name:<11}"), label),
+            Span::styled(text, value),
+        ])
+    };
+    let played = record.played.as_secs();
+    let lines = vec![
+        row("High score", record.best.to_string()),
+        row(
+            "Best time",
+            best_time.map_or_else(
+                || "-".to_string(),
+                |t| format!("{}:{:04.1}", t.as_secs() / 60, t.as_secs_f64() % 60.0),
+            ),
+        ),
+        Line::from(""),
+        row("Games", record.games.to_string()),
+        row("Lines", record.lines.to_string()),
+        row("Played", format!("{}h {:02}m", played / 3600, played / 60 % 60)),
+    ];
+    frame.render_widget(ratatui::widgets::Clear, panel);
+    Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(" Records ")
+                .border_style(Style::default().fg(state.theme.div_line).bg(state.theme.bg)),
+        )
+        .render(panel, frame.buffer_mut());
+}
+
+/// Handling settings: one row per `HandlingConfig` value plus the key profile and music,
+/// Left/Right to adjust.
+/// Title screen: the logo's scratch playfield in half blocks, centred, with the key hint below
+/// once the logo is complete.
+fn draw_title(frame: &mut Frame, state: &GameState, area: Rect, title: &crate::title::Title) {
+    let (gw, gh) = title.grain_dims();
+    let (w, h) = (gw as u16, (gh / 2) as u16 + 2);
+    let rect = Rect {
+        x: area.x + area.width.saturating_sub(w) / 2,
+        y: area.y + area.height.saturating_sub(h) / 2,
+        width: w.min(area.width),
+        height: h.min(area.height),
+    };
+    Block::default()
+        .style(Style::default().bg(state.theme.bg))
+        .render(area, frame.buffer_mut());
+
+    let mut logo = vec![None; gw * gh];
+    for &(x, y, color) in &title.settled {
+        logo[y * gw + x] = Some(color);
+    }
+    let color = |x: usize, y: usize| {
+        let sand = match title.grain(x, y) {
+            Some(Cell::Sand(i, _)) => Some(i),
+            _ => logo[y * gw + x],
+        };
+        sand.map_or(state.theme.bg, |i| state.theme.sand_color(i))
+    };
+    let buf = frame.buffer_mut();
+    for y in (0..gh).step_by(2) {
+        for x in 0..gw {
+            let pos = Position::new(rect.x + x as u16, rect.y + (y / 2) as u16);
+            if rect.contains(pos) {
+                buf[pos]
+                    .set_symbol("▀")
+                    .set_style(Style::default().fg(color(x, y)).bg(color(x, y + 1)));
+            }
+        }
+    }
+
+    if title.done() {
+        let hint = Rect {
+            y: rect.y + (gh / 2) as u16 + 1,
+            height: 1,
+            ..rect
+        };
+        if hint.bottom() <= rect.bottom() {
+            Paragraph::new("Press any key")
+                .alignment(Alignment::Center)
+                .style(Style::default().fg(state.theme.inactive_fg))
+                .render(hint, buf);
+        }
+    }
+}
+
+fn draw_settings(
+    frame: &mut Frame,
+    state: &GameState,
+    area: Rect,
+    handling: &crate::HandlingConfig,
+    profile: crate::KeyProfile,
+    music: crate::MusicTrack,
+    selected: usize,
+) {
+    let popup_w = 36u16;
+    let popup_h = 15u16;
+    let popup = Rect {
+        x: area.x + area.width.saturating_sub(popup_w) / 2,
+        y: area.y + area.height.saturating_sub(popup_h) / 2,
+        width: popup_w.min(area.width),
+        height: popup_h.min(area.height),
+    };
+    let highlight_style = Style::default()
+        .fg(Color::Black)
+        .bg(state.theme.sand_color(1))
+        .bold();
+    let normal_style = Style::default().fg(state.theme.main_fg);
+
+    let mut lines = vec![Line::from("")];
+    for (i, label) in crate::HandlingConfig::FIELDS.iter().enumerate() {
+        let style = if i == selected {
+            highlight_style
+        } else {
+            normal_style
+        };
+        lines.push(Line::from(Span::styled(
+            match handling.field(i) {
+                Some(ms) => format!(" {label:<10} ◂ {ms:>3} ms ▸ "),
+                None => format!(" {label:<10} ◂  off   ▸ "),
+            },
+            style,
+        )));
+        lines.push(Line::from(""));
+    }
+    let choices = [
+        ("Keys", profile.to_possible_value()),
+        ("Music", music.to_possible_value()),
+    ];
+    for (i, (label, value)) in choices.into_iter().enumerate() {
+        let name = value.map(|v| v.get_name().to_string()).unwrap_or_default();
+        let style = if selected == crate::HandlingConfig::FIELDS.len() + i {
+            highlight_style
+        } else {
+            normal_style
+        };
+        lines.push(Line::from(Span::styled(
+            format!(" {label:<10} ◂ {name:^11} ▸ "),
+            style,
+        )));
+        lines.push(Line::from(""));
+    }
+    lines.push(Line::from(vec![
+        Span::styled(" ↕ ", Style::default().fg(state.theme.sand_color(3))),
+        Span::from("SELECT   "),
+        Span::styled(" ↔ ", Style::default().fg(state.theme.sand_color(3))),
+        Span::from("ADJUST   "),
+        Span::styled(" ENTER ", Style::default().fg(state.theme.sand_color(3))),
+        Span::from("BACK"),
+    ]));
+
+    let p = Paragraph::new(lines).alignment(Alignment::Center).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(state.theme.div_line).bg(state.theme.bg))
+            .title(Span::styled(" Handling ", state.theme.title)),
+    );
+    frame.render_widget(ratatui::widgets::Clear, popup);
+    p.render(popup, frame.buffer_mut());
+}
+
+/// Custom mode form: one row per setting, the selected one highlighted, then Save.
+fn draw_custom_mode(
+    frame: &mut Frame,
+    state: &GameState,
+    area: Rect,
+    form: &crate::custom::CustomForm,
+) {
+    let popup_w = 40u16;
+    let popup_h = 23u16;
+    let popup = Rect {
+        x: area.x + area.width.saturating_sub(popup_w) / 2,
+        y: area.y + area.height.saturating_sub(popup_h) / 2,
+        width: popup_w.min(area.width),
+        height: popup_h.min(area.height),
+    };
+    let highlight_style = Style::default()
+        .fg(Color::Black)
+        .bg(state.theme.sand_color(1))
+        .bold();
+    let normal_style = Style::default().fg(state.theme.main_fg);
+
+    let mut lines = vec![Line::from("")];
+    let rows = crate::custom::CustomForm::ROWS.iter().zip(form.values());
+    for (i, (label, value)) in rows.enumerate() {
+        let style = if i == form.selected {
+            highlight_style
+        } else {
+            normal_style
+        };
+        let text = match i {
+            0 => format!(" {label:<8} {value:<18} "),
+            crate::custom::CustomForm::SAVE_ROW => " [ SAVE ] ".to_string(),
+            _ => format!(" {label:<8} ◂ {value:^14} ▸ "),
+        };
+        lines.push(Line::from(Span::styled(text, style)));
+        lines.push(Line::from(""));
+    }
+    lines.push(Line::from(vec![
+        Span::styled(" ↕ ", Style::default().fg(state.theme.sand_color(3))),
+        Span::from("SELECT   "),
+        Span::styled(" ↔ ", Style::default().fg(state.theme.sand_color(3))),
+        Span::from("ADJUST   "),
+        Span::styled(" ESC ", Style::default().fg(state.theme.sand_color(3))),
+        Span::from("BACK"),
+    ]));
+
+    let p = Paragraph::new(lines).alignment(Alignment::Center).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(state.theme.div_line).bg(state.theme.bg))
+            .title(Span::styled(" Custom Mode ", state.theme.title)),
+    );
+    frame.render_widget(ratatui::widgets::Clear, popup);
+    p.render(popup, frame.buffer_mut());
+}
+
+fn draw_pause_overlay(frame: &mut Frame, state: &GameState, area: Rect, reason: PauseReason) {
+    let popup_w = 30u16;
     let popup_h = 5u16;
     let popup = Rect {
         x: area.x + area.width.saturating_sub(popup_w) / 2,
@@ -650,7 +1292,10 @@ fn draw_pause_overlay(frame: &mut Frame, state: &GameState, area: Rect) {
     let lines = vec![
         Line::from(""),
         Line::from(Span::styled(
-            " Paused ",
+            match reason {
+                PauseReason::User => " Paused ",
+                PauseReason::FocusLost => " game paused — focus lost ",
+            },
             Style::default().fg(Color::Black).bg(Color::Yellow),
         )),
         Line::from(""),
@@ -677,8 +1322,14 @@ fn draw_game_over(
     game_start: Instant,
     area: Rect,
     high_scores: HighScores,
-    new_high_score_this_game: bool,
+    new_high_score: Option<&mut Option<Celebration>>,
     time_to_40_secs: Option<u64>,
+    splits: &Splits,
+    now: Instant,
+    reduced_motion: bool,
+    hot_seat: bool,
+    frame_times: Option<&crate::diagnostics::FrameTimes>,
+    rewind: Option<&crate::rewind::Rewind>,
 ) {
     let (pw, ph) =
         playfield_pixel_size(state.playfield.width as u16, state.playfield.height as u16);
@@ -693,9 +1344,10 @@ fn draw_game_over(
         height: total_h.min(area.height),
     };
     // Clear40 never ends with "win" at 40; game over is always stack overflow.
-    let title = match reason {
-        Some(GameOverReason::TimeUp) => " Time's up! ",
-        _ => " Game Over ",
+    let (title, title_bg) = match reason {
+        Some(GameOverReason::TimeUp) => (" Time's up! ", Color::Red),
+        Some(GameOverReason::Cleared) => (" Cleared! ", Color::Green),
+        _ => (" Game Over ", Color::Red),
     };
     let (best_endless, best_timed, best_clear) = high_scores;
     let high_score = match mode {
@@ -707,7 +1359,7 @@ fn draw_game_over(
         Line::from(""),
         Line::from(Span::styled(
             title,
-            Style::default().fg(Color::White).bg(Color::Red),
+            Style::default().fg(Color::White).bg(title_bg),
         )),
         Line::from(""),
         Line::from(Span::styled(
@@ -723,7 +1375,10 @@ fn draw_game_over(
             Style::default().fg(state.theme.main_fg),
         )),
     ];
-    if new_high_score_this_game {
+    let record_row = new_high_score
+        .is_some()
+        .then(|| popup.y + 1 + lines.len() as u16);
+    if record_row.is_some() {
         lines.push(Line::from(Span::styled(
             " New record! ",
             Style::default().fg(Color::Yellow).bold(),
@@ -747,10 +1402,81 @@ fn draw_game_over(
                 Style::default().fg(state.theme.main_fg),
             )));
         }
+        // Splits, with the difference to the best run (green when ahead)
+        for (at, time, delta) in splits.rows() {
+            let millis = time.as_millis();
+            let mut spans = vec![Span::styled(
+                format!(
+                    " {at:>3}  {:02}:{:02}.{} ",
+                    millis / 60_000,
+                    millis / 1000 % 60,
+                    millis / 100 % 10
+                ),
+                Style::default().fg(state.theme.main_fg),
+            )];
+            if let Some(delta) = delta {
+                spans.push(Span::styled(
+                    format!("{delta:+.1} "),
+                    Style::default().fg(pace_color(delta)),
+                ));
+            }
+            lines.push(Line::from(spans));
+        }
+    }
+    // Where pieces were locked: one bar per block column, scaled to the busiest
+    let busiest = state.lock_columns.iter().copied().max().unwrap_or(0).max(1);
+    let heatmap: String = state
+        .lock_columns
+        .iter()
+        .map(|&n| HEATMAP_BARS[(n * (HEATMAP_BARS.len() as u32 - 1)).div_ceil(busiest) as usize])
+        .collect();
+    lines.push(Line::from(vec![
+        Span::styled(" Columns ", Style::default().fg(state.theme.main_fg)),
+        Span::styled(heatmap, Style::default().fg(state.theme.title)),
+        Span::raw(" "),
+    ]));
+    if let Some(frame_times) = frame_times.filter(|times| times.render.percentile(50).is_some()) {
+        // Slow rendering is the terminal, slow logic the simulation
+        for (name, histogram) in [
+            ("Render", &frame_times.render),
+            ("Logic ", &frame_times.logic),
+        ] {
+            let [p50, p95, p99] =
+                [50, 95, 99].map(|percent| frame_ms(histogram.percentile(percent)));
+            lines.push(Line::from(Span::styled(
+                format!(" {name} p50/95/99 {p50}/{p95}/{p99} ms "),
+                Style::default().fg(state.theme.inactive_fg),
+            )));
+        }
+        lines.push(Line::from(Span::styled(
+            format!(" Dropped ticks: {} ", frame_times.dropped_ticks),
+            Style::default().fg(state.theme.inactive_fg),
+        )));
+    }
+    lines.push(Line::from(Span::styled(
+        format!(" Seed: {:x} ", state.seed),
+        Style::default().fg(state.theme.inactive_fg),
+    )));
+    if rewind.is_some_and(crate::rewind::Rewind::used) {
+        lines.push(Line::from(Span::styled(
+            " Rewound: no records ",
+            Style::default().fg(state.theme.inactive_fg),
+        )));
     }
     lines.push(Line::from(""));
+    if let Some(rewind) = rewind.filter(|rewind| rewind.available()) {
+        lines.push(Line::from(Span::styled(
+            format!(
+                " U — Rewind {} pieces ({} left) ",
+                crate::rewind::REWIND_PIECES,
+                rewind.uses_left()
+            ),
+            Style::default().fg(state.theme.main_fg),
+        )));
+    }
+    let restart = if hot_seat { "Standings" } else { "Restart" };
     lines.push(Line::from(Span::styled(
-        " R — Restart    Q — Quit ",
+        format!(" R — {restart}    Y — Copy seed    Q — Quit "),
         Style::default().fg(state.theme.main_fg),
     )));
     lines.push(Line::from(""));
@@ -761,25 +1487,211 @@ fn draw_game_over(
             .title(Span::styled(" Setrixtui ", state.theme.title)),
     );
     p.render(popup, frame.buffer_mut());
+    if let (Some(celebration), Some(row)) = (new_high_score, record_row) {
+        if !reduced_motion {
+            celebration
+                .get_or_insert_with(|| Celebration::new(popup, row, now))
+                .render(frame, state, popup, now);
+        }
+    }
 }
 
-/// Draw game: playfield + sidebar; use full area and center the board.
-fn draw_game(
+/// A frame time percentile in ms for the game over screen.
+fn frame_ms(time: Option<std::time::Duration>) -> String {
+    match time {
+        Some(std::time::Duration::MAX) => ">50".to_string(),
+        Some(time) => format!("{:.1}", time.as_secs_f64() * 1000.0),
+        None => "-".to_string(),
+    }
+}
+
+/// Hot seat standings: scores of the last few games shown after each total.
+const STANDINGS_GAMES: usize = 4;
+
+/// `--hot-seat` standings between games: players by total score, their latest games, and who
+/// plays next.
+fn draw_standings(
     frame: &mut Frame,
     state: &GameState,
     area: Rect,
-    mode: GameMode,
-    time_limit: u32,
-    game_start: Instant,
-    now: Instant,
-    high_scores: HighScores,
-    time_to_40_secs: Option<u64>,
-    clear_lines: u32,
-    autoplay: bool,
+    hot_seat: &crate::hotseat::HotSeat,
 ) {
+    let name_width = hot_seat
+        .players
+        .iter()
+        .map(|player| player.name.chars().count())
+        .max()
+        .unwrap_or(0);
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            format!(" Round {} ", hot_seat.round()),
+            Style::default().fg(state.theme.bg).bg(state.theme.title),
+        )),
+        Line::from(""),
+    ];
+    for (rank, player) in (1..).zip(hot_seat.standings()) {
+        let recent = player.scores.len().saturating_sub(STANDINGS_GAMES);
+        let games: Vec<String> = player.scores[recent..].iter().map(u32::to_string).collect();
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!(" {rank}. {:<name_width$} ", player.name),
+                Style::default().fg(state.theme.main_fg),
+            ),
+            Span::styled(
+                format!("{:>7} ", player.total()),
+                Style::default().fg(state.theme.title).bold(),
+            ),
+            Span::styled(
+                format!(" {} ", games.join(" ")),
+                Style::default().fg(state.theme.inactive_fg),
+            ),
+        ]));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        format!(" Next up: {} ", hot_seat.current()),
+        Style::default().fg(Color::Yellow).bold(),
+    )));
+    lines.push(Line::from(Span::styled(
+        " Enter — Play    Q — Quit ",
+        Style::default().fg(state.theme.main_fg),
+    )));
+    lines.push(Line::from(""));
+    let width = lines.iter().map(Line::width).max().unwrap_or(0) as u16 + 2;
+    let height = lines.len() as u16 + 2;
+    let rect = Rect {
+        x: area.x + area.width.saturating_sub(width) / 2,
+        y: area.y + area.height.saturating_sub(height) / 2,
+        width: width.min(area.width),
+        height: height.min(area.height),
+    };
+    Paragraph::new(lines)
+        .alignment(Alignment::Center)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(state.theme.div_line).bg(state.theme.bg))
+                .title(Span::styled(" Standings ", state.theme.title)),
+        )
+        .render(rect, frame.buffer_mut());
+}
+
+/// New record: the game over popup sparkles in over this long.
+const SPARKLE_MS: u32 = 700;
+
+/// New record: one brightening (or dimming) of the "New record!" line.
+const RECORD_PULSE_MS: u32 = 600;
+
+/// New record: confetti falls over the popup for this long.
+const CONFETTI_MS: u32 = 3000;
+
+/// Confetti pieces per celebration.
+const CONFETTI_PIECES: u32 = 28;
+
+const CONFETTI_SYMBOLS: [&str; 5] = ["✦", "✧", "*", "•", "·"];
+
+/// Game over with a new high score: the popup sparkles in and the "New record!" line pulses
+/// (TachyonFX), while confetti in the sand colours falls through the popup's empty cells.
+pub struct Celebration {
+    effect: Effect,
+    started: Instant,
+    processed: Instant,
+}
+
+impl Celebration {
+    fn new(popup: Rect, record_row: u16, now: Instant) -> Self {
+        let row = Rect {
+            y: record_row,
+            height: 1,
+            ..popup
+        };
+        let pulse = fx::repeating(fx::ping_pong(fx::hsl_shift_fg(
+            [0.0, 0.0, 35.0],
+            (RECORD_PULSE_MS, Interpolation::SineInOut),
+        )))
+        .with_filter(CellFilter::Area(row));
+        let sparkle = fx::coalesce((SPARKLE_MS, Interpolation::QuadOut)).with_area(popup);
+        Self {
+            effect: fx::parallel(&[sparkle, pulse]),
+            started: now,
+            processed: now,
+        }
+    }
+
+    fn render(&mut self, frame: &mut Frame, state: &GameState, popup: Rect, now: Instant) {
+        let delta = now.saturating_duration_since(self.processed).as_millis();
+        self.processed = now;
+        frame.render_effect(
+            &mut self.effect,
+            popup,
+            TfxDuration::from_millis(delta.min(u128::from(u32::MAX)) as u32),
+        );
+
+        let elapsed = now.saturating_duration_since(self.started).as_millis() as u32;
+        if elapsed >= CONFETTI_MS {
+            return;
+        }
+        let inner = Rect {
+            x: popup.x + 1,
+            y: popup.y + 1,
+            width: popup.width.saturating_sub(2),
+            height: popup.height.saturating_sub(2),
+        };
+        if inner.is_empty() {
+            return;
+        }
+        let buf = frame.buffer_mut();
+        for i in 0..CONFETTI_PIECES {
+            // Fixed per-piece column, speed (rows per second), delay and symbol
+            let h = i.wrapping_add(1).wrapping_mul(2_654_435_761);
+            let Some(t) = elapsed.checked_sub((h >> 8) & 0x3ff) else {
+                continue;
+            };
+            let speed = 6 + (h >> 20) % 8;
+            let sway = (t / 250 + i) % 4;
+            let x = (h % u32::from(inner.width) + sway.min(4 - sway)) as u16;
+            let y = (t * speed / 1000) as u16;
+            let pos = Position::new(inner.x + x, inner.y + y);
+            if inner.contains(pos) && buf[pos].symbol() == " " {
+                buf[pos]
+                    .set_symbol(CONFETTI_SYMBOLS[(h >> 4) as usize % CONFETTI_SYMBOLS.len()])
+                    .set_style(Style::default().fg(state.theme.sand_color((i % 6) as u8)));
+            }
+        }
+    }
+}
+
+/// `--show-inputs`: an action's icon fades out over this long.
+const INPUT_FADE_MS: u128 = 1500;
+
+/// Game over column heatmap, from no pieces locked to the busiest column.
+const HEATMAP_BARS: [char; 9] = [' ', '▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Split delta colour: green when ahead of (or level with) the best run, red when behind.
+fn pace_color(delta: f64) -> Color {
+    if delta <= 0.0 {
+        Color::Green
+    } else {
+        Color::Red
+    }
+}
+
+/// Playfield and sidebar rects for the game screen (board centred in `area`).
+/// On terminals `WIDESCREEN_SLACK` wider than the board and two sidebars, the third rect is a
+/// left sidebar (see `SidebarPart`) and the board sits in the middle.
+fn game_layout(area: Rect, state: &GameState) -> (Rect, Rect, Option<Rect>) {
     let (pw, ph) =
         playfield_pixel_size(state.playfield.width as u16, state.playfield.height as u16);
-    let total_w = pw + SIDEBAR_WIDTH;
+    // --dual: two boards side by side
+    let boards_w = if state.idle_board.is_some() {
+        pw * 2
+    } else {
+        pw
+    };
+    let widescreen = area.width >= boards_w + 2 * SIDEBAR_WIDTH + WIDESCREEN_SLACK;
+    let left_w = if widescreen { SIDEBAR_WIDTH } else { 0 };
+    let total_w = left_w + boards_w + SIDEBAR_WIDTH;
 
     // Center horizontally
     let horiz_chunks = Layout::default()
@@ -805,13 +1717,85 @@ fn draw_game(
 
     let active_area = vert_chunks[1];
 
-    let (playfield_area, sidebar_area) = {
-        let inner = Layout::default()
-            .direction(Direction::Horizontal)
-            .constraints([Constraint::Length(pw), Constraint::Length(SIDEBAR_WIDTH)])
-            .split(active_area);
-        (inner[0], inner[1])
+    let inner = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Length(left_w),
+            Constraint::Length(boards_w),
+            Constraint::Length(SIDEBAR_WIDTH),
+        ])
+        .split(active_area);
+    let playfield = match &state.idle_board {
+        Some(board) => {
+            let halves = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Length(pw), Constraint::Length(pw)])
+                .split(inner[1]);
+            if board.left { halves[1] } else { halves[0] }
+        }
+        None => inner[1],
+    };
+    (playfield, inner[2], widescreen.then_some(inner[0]))
+}
+
+/// `--dual`: screen rect of the board without the piece, beside `playfield` (the piece's).
+fn idle_board_area(playfield: Rect, state: &GameState) -> Option<Rect> {
+    let board = state.idle_board.as_ref()?;
+    let x = if board.left {
+        playfield.x.saturating_sub(playfield.width)
+    } else {
+        playfield.right()
     };
+    Some(Rect { x, ..playfield })
+}
+
+/// Screen rect of the board and sidebar (screenshots), for a frame of size `area`.
+pub fn game_area(area: Rect, state: &GameState, options: &RenderOptions) -> Rect {
+    let area = if options.announcement.is_some() {
+        // Same split as `draw`: the status line takes the bottom row
+        Rect {
+            height: area.height.saturating_sub(1),
+            ..area
+        }
+    } else {
+        area
+    };
+    let (playfield, sidebar, left) = game_layout(area, state);
+    let playfield =
+        idle_board_area(playfield, state).map_or(playfield, |idle| idle.union(playfield));
+    left.map_or(playfield, |left| left.union(playfield))
+        .union(sidebar)
+}
+
+/// Block column of the playfield under terminal column `x` (mouse steering), clamped to the board.
+pub fn playfield_column_at(area: Rect, state: &GameState, x: u16) -> Option<i32> {
+    let (playfield_area, _, _) = game_layout(area, state);
+    if playfield_area.width < 2 {
+        return None;
+    }
+    let board_x = playfield_area.x + 1;
+    let (gw, _) = state.playfield.grain_dims();
+    let grain = (x.saturating_sub(board_x) as usize).min(gw.saturating_sub(1));
+    Some((grain / crate::game::GRAIN_SCALE) as i32)
+}
+
+/// Draw game: playfield + sidebar; use full area and center the board.
+fn draw_game(
+    frame: &mut Frame,
+    state: &GameState,
+    area: Rect,
+    mode: GameMode,
+    time_limit: u32,
+    game_start: Instant,
+    now: Instant,
+    high_scores: HighScores,
+    time_to_40_secs: Option<u64>,
+    splits: &Splits,
+    clear_lines: u32,
+    autoplay: bool,
+    options: RenderOptions,
+) {
+    let (playfield_area, sidebar_area, left_area) = game_layout(area, state);
 
     draw_playfield(
         frame,
@@ -822,17 +1806,187 @@ fn draw_game(
         game_start,
         now,
         time_to_40_secs,
+        splits,
         clear_lines,
+        options,
     );
+    // --dual: the board without the piece, dimmed
+    if let (Some(idle), Some(idle_area)) = (
+        state.idle_view(),
+        idle_board_area(playfield_area, state).map(|idle| idle.intersection(area)),
+    ) {
+        draw_playfield(
+            frame,
+            &idle,
+            idle_area,
+            mode,
+            time_limit,
+            game_start,
+            now,
+            time_to_40_secs,
+            splits,
+            clear_lines,
+            options,
+        );
+        dim(frame.buffer_mut(), idle_area);
+    }
+    let right = if let Some(left_area) = left_area {
+        draw_sidebar(
+            frame,
+            state,
+            left_area,
+            SidebarPart::Left,
+            mode,
+            high_scores,
+            autoplay,
+            now,
+            options.events,
+            options.mission,
+        );
+        SidebarPart::Right
+    } else {
+        SidebarPart::Full
+    };
     draw_sidebar(
         frame,
         state,
         sidebar_area,
+        right,
         mode,
         high_scores,
         autoplay,
         now,
+        options.events,
+        options.mission,
     );
+    if !options.inputs.is_empty() {
+        draw_inputs(frame, state, playfield_area, area, options.inputs, now);
+    }
+}
+
+/// `--show-inputs`: recent actions as icons centred under the board (on its bottom border when
+/// there's no room below), newest on the right, fading into the background.
+fn draw_inputs(
+    frame: &mut Frame,
+    state: &GameState,
+    board: Rect,
+    area: Rect,
+    inputs: &[(Action, Instant)],
+    now: Instant,
+) {
+    let spans: Vec<Span> = inputs
+        .iter()
+        .filter_map(|&(action, at)| {
+            let age = now.saturating_duration_since(at).as_millis();
+            (age < INPUT_FADE_MS).then(|| {
+                let t = age as f32 / INPUT_FADE_MS as f32;
+                let color = fade_to(state.theme.main_fg, state.theme.bg, t);
+                Span::styled(
+                    format!("{} ", input_icon(action)),
+                    Style::default().fg(color).bg(state.theme.bg),
+                )
+            })
+        })
+        .collect();
+    if spans.is_empty() {
+        return;
+    }
+    let line = Line::from(spans);
+    let width = (line.width() as u16).min(board.width);
+    let y = if board.bottom() < area.bottom() {
+        board.bottom()
+    } else {
+        board.bottom().saturating_sub(1)
+    };
+    let rect = Rect {
+        x: board.x + (board.width - width) / 2,
+        y,
+        width,
+        height: 1,
+    };
+    Paragraph::new(line).render(rect, frame.buffer_mut());
+}
+
+/// Overlay icon for an action.
+const fn input_icon(action: Action) -> char {
+    match action {
+        Action::MoveLeft => '←',
+        Action::MoveRight => '→',
+        Action::RotateCw => '↻',
+        Action::RotateCcw => '↺',
+        Action::SoftDrop => '↓',
+        Action::HardDrop => '⇊',
+        Action::Reroll => '⇄',
+        Action::Focus => '◎',
+        Action::Zone => '◆',
+        Action::Restart | Action::Retry => '⟲',
+        Action::Pause | Action::Quit | Action::None => ' ',
+    }
+}
+
+/// `color` blended toward `bg` by `t` (0 = `color`, 1 = `bg`); non-RGB colours switch at the end.
+fn fade_to(color: Color, bg: Color, t: f32) -> Color {
+    match (color, bg) {
+        (Color::Rgb(r, g, b), Color::Rgb(br, bg, bb)) => {
+            let mix = |from: u8, to: u8| {
+                (f32::from(to) - f32::from(from)).mul_add(t, f32::from(from)) as u8
+            };
+            Color::Rgb(mix(r, br), mix(g, bg), mix(b, bb))
+        }
+        _ if t >= 1.0 => bg,
+        _ => color,
+    }
+}
+
+/// `--dynamic-background`: empty board cells shade towards a tint from the top down, red as the
+/// stack rises or gold while a combo runs (whichever is further along), with faint dust
+/// drifting down, faster in a combo. The dust moves with the sand ticks, so it stops on pause.
+struct Backdrop {
+    tint: Color,
+    /// Tint strength at the bottom row.
+    strength: f32,
+    gh: usize,
+    /// Grains the dust has fallen.
+    drift: usize,
+}
+
+impl Backdrop {
+    fn new(state: &GameState, reduced_motion: bool) -> Self {
+        let (_, gh) = state.playfield.grain_dims();
+        let danger = state.stack_height() as f32 / state.playfield.height.max(1) as f32;
+        let combo = state.combo_multiplier.saturating_sub(1) as f32
+            / state.combo.max_multiplier.saturating_sub(1).max(1) as f32;
+        let (min, max) = BACKDROP_STRENGTH;
+        Self {
+            tint: if combo > danger {
+                BACKDROP_COMBO
+            } else {
+                BACKDROP_DANGER
+            },
+            strength: (max - min).mul_add(danger.max(combo).min(1.0), min),
+            gh: gh.max(1),
+            drift: if reduced_motion {
+                0
+            } else {
+                state.playfield.tick_count as usize * state.combo_multiplier.max(1) as usize / 4
+            },
+        }
+    }
+
+    /// Colour of the empty grain at (x, y) over `bg` (unchanged unless `bg` is RGB).
+    fn color(&self, bg: Color, x: usize, y: usize) -> Color {
+        if !matches!(bg, Color::Rgb(..)) {
+            return bg;
+        }
+        let mut strength = self.strength * y as f32 / self.gh as f32;
+        // A fixed sparse scatter of specks, scrolled down by `drift`
+        let from = ((y + self.gh - self.drift % self.gh) % self.gh) as u32;
+        let hash = (x as u32).wrapping_mul(0x9E37_79B9) ^ from.wrapping_mul(0x85EB_CA6B);
+        if hash.wrapping_mul(0xC2B2_AE35) >> 24 < 3 {
+            strength += BACKDROP_DUST;
+        }
+        fade_to(self.tint, bg, 1.0 - strength.min(1.0))
+    }
 }
 
 fn draw_playfield(
@@ -844,9 +1998,15 @@ fn draw_playfield(
     game_start: Instant,
     now: Instant,
     time_to_40_secs: Option<u64>,
+    splits: &Splits,
     clear_lines: u32,
+    options: RenderOptions,
 ) {
-    let title = if mode == GameMode::Timed {
+    let title = if mode == GameMode::Timed
+        && now.duration_since(game_start).as_secs() >= u64::from(time_limit)
+    {
+        format!(" Setrixtui  OVERTIME  | Clears: {} ", state.clears)
+    } else if mode == GameMode::Timed {
         let elapsed = now.duration_since(game_start).as_secs();
         let remaining = (time_limit as u64).saturating_sub(elapsed);
         format!(
@@ -878,10 +2038,41 @@ fn draw_playfield(
     } else {
         format!(" Setrixtui  | Clears: {} ", state.clears)
     };
+    let border_fg = if state.breather_active() {
+        BREATHER_BORDER
+    } else {
+        state.theme.div_line
+    };
     let block = Block::default()
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(state.theme.div_line).bg(state.theme.bg))
+        .border_style(Style::default().fg(border_fg).bg(state.theme.bg))
         .title(Span::styled(title, state.theme.title));
+    // A cylinder's sides are no walls: dashed
+    let block = if state.playfield.wrap {
+        block.border_set(ratatui::symbols::border::Set {
+            vertical_left: "┆",
+            vertical_right: "┆",
+            ..ratatui::symbols::border::PLAIN
+        })
+    } else {
+        block
+    };
+    let block = match options.player {
+        Some(name) => block.title(Span::styled(format!(" {name} "), state.theme.main_fg)),
+        None => block,
+    };
+    let block = if state.breather_active() {
+        block.title_bottom(Span::styled(" Breather x2 ", BREATHER_BORDER))
+    } else {
+        block
+    };
+    // Clear mode: pace against the best run at the latest 10-line split
+    let block = match splits.last_delta() {
+        Some(delta) if mode == GameMode::Clear && time_to_40_secs.is_none() => block.title(
+            Span::styled(format!(" {delta:+.1}s "), Style::default().fg(pace_color(delta)).bold()),
+        ),
+        _ => block,
+    };
     let inner = block.inner(area);
     block.render(area, frame.buffer_mut());
 
@@ -896,6 +2087,59 @@ fn draw_playfield(
     let clear_set: std::collections::HashSet<(usize, usize)> =
         state.line_clear_cells.iter().copied().collect();
     let flashing = state.line_clear_in_progress && !state.line_clear_cells.is_empty();
+    let flash_color = if state.line_clear_blast {
+        BLAST_COLOR
+    } else {
+        Color::White
+    };
+    // Clearing grain colour: the flash, or with `--reduce-flashing` the grain's own colour greyed
+    let clearing_color = |cell: Option<Cell>, x: usize, y: usize| {
+        if options.reduce_flashing {
+            cell.and_then(|c| cell_color(state, c, x, y))
+                .map_or(state.theme.bg, desaturate)
+        } else {
+            flash_color
+        }
+    };
+
+    // Empty board cells take a blue tint during focus slow motion
+    let empty_bg = if state.focus_active() {
+        focus_tint(state.theme.bg)
+    } else {
+        state.theme.bg
+    };
+    let backdrop = options
+        .dynamic_background
+        .then(|| Backdrop::new(state, options.reduced_motion));
+    let empty = |x: usize, y: usize| {
+        backdrop
+            .as_ref()
+            .map_or(empty_bg, |backdrop| backdrop.color(empty_bg, x, y))
+    };
+
+    let bank_top = gh - state.playfield.banked_rows;
+    let banked = |y: usize| ZONE_BANK_COLORS[(gh - 1 - y) / GRAIN_SCALE % 2];
+    // Game over drain: the sand goes grey
+    let grey = |color, grain: Option<Cell>| {
+        if options.draining && grain.is_some_and(Cell::is_solid) {
+            desaturate(color)
+        } else {
+            color
+        }
+    };
+
+    // Settled sand: grain shading, then with `--blend-sand` the colour boundary blend
+    let shade = |color, x: usize, y: usize| {
+        if options.low_quality {
+            return color;
+        }
+        let color = apply_shading(color, x, y, state);
+        if options.blend_sand {
+            blend_boundary(state, color, x, y)
+        } else {
+            color
+        }
+    };
 
     let buf = frame.buffer_mut();
 
@@ -912,25 +2156,37 @@ fn draw_playfield(
             let top_piece_color = get_piece_at_grain(state, x, y);
             let bot_piece_color = get_piece_at_grain(state, x, y + 1);
 
-            let top_color = if is_top_clearing {
-                Color::White
+            let top_color = if y >= bank_top {
+                banked(y)
+            } else if is_top_clearing {
+                clearing_color(top_grain, x, y)
             } else {
-                top_piece_color.unwrap_or_else(|| match top_grain {
-                    Some(Cell::Sand(i, _)) => apply_shading(state.theme.sand_color(i), x, y, state),
-                    _ => state.theme.bg,
+                top_piece_color.unwrap_or_else(|| {
+                    top_grain
+                        .and_then(|c| cell_color(state, c, x, y))
+                        .map_or_else(
+                            || empty(x, y),
+                            |c| invisible_fade(state, shade(c, x, y), x, y, now),
+                        )
                 })
             };
-            let bot_color = if is_bot_clearing {
-                Color::White
+            let bot_color = if y + 1 >= bank_top {
+                banked(y + 1)
+            } else if is_bot_clearing {
+                clearing_color(bot_grain, x, y + 1)
             } else {
-                bot_piece_color.unwrap_or_else(|| match bot_grain {
-                    Some(Cell::Sand(i, _)) => {
-                        apply_shading(state.theme.sand_color(i), x, y + 1, state)
-                    }
-                    _ => state.theme.bg,
+                bot_piece_color.unwrap_or_else(|| {
+                    bot_grain
+                        .and_then(|c| cell_color(state, c, x, y + 1))
+                        .map_or_else(
+                            || empty(x, y + 1),
+                            |c| invisible_fade(state, shade(c, x, y + 1), x, y + 1, now),
+                        )
                 })
             };
 
+            let (top_color, bot_color) = (grey(top_color, top_grain), grey(bot_color, bot_grain));
+
             let rx = board_rect.x + x as u16;
             let ry = board_rect.y + (y / 2) as u16;
 
@@ -938,6 +2194,22 @@ fn draw_playfield(
                 buf[(rx, ry)]
                     .set_symbol("▀")
                     .set_style(Style::default().fg(top_color).bg(bot_color));
+                // Symbols on a checkerboard of cells, so the half-block detail stays visible
+                // in between. The symbol cell takes the colour of the grain it labels.
+                if options.symbols && (x + y / 2) % 2 == 0 {
+                    let labelled = if is_top_clearing || is_bot_clearing {
+                        None
+                    } else if let Some(i) = grain_color_index(state, x, y) {
+                        Some((i, top_color))
+                    } else {
+                        grain_color_index(state, x, y + 1).map(|i| (i, bot_color))
+                    };
+                    if let Some((i, color)) = labelled {
+                        buf[(rx, ry)]
+                            .set_symbol(SAND_SYMBOLS[i as usize % SAND_SYMBOLS.len()])
+                            .set_style(Style::default().fg(state.theme.bg).bg(color));
+                    }
+                }
             }
         }
     }
@@ -947,41 +2219,217 @@ fn draw_playfield(
         let rx = board_rect.x + (fg.x as u16);
         let ry = board_rect.y + (fg.y as u16 / 2);
         if rx < board_rect.x + board_rect.width && ry < board_rect.y + board_rect.height {
-            let color = apply_shading(state.theme.sand_color(fg.color_index), fg.x, fg.y, state);
+            let base = cell_color(state, fg.cell, fg.x, fg.y).unwrap_or(state.theme.bg);
+            let color = if options.low_quality {
+                base
+            } else {
+                apply_shading(base, fg.x, fg.y, state)
+            };
             let style = Style::default().fg(color).bg(color);
             // Frozen grains use a solid block to look "frozen"
             buf[(rx, ry)].set_symbol("█").set_style(style);
         }
     }
 
-    // Draw Floating Score Popups!
-    for popup in &state.popups {
-        let rx = board_rect.x + (popup.x as u16);
-        let ry = board_rect.y + (popup.y as u16 / 2);
-        if rx < board_rect.x + board_rect.width && ry < board_rect.y + board_rect.height {
-            let label = if popup.multiplier > 1 {
-                format!("+{} (x{})", popup.amount, popup.multiplier)
+    if options.high_contrast_board {
+        draw_a11y_board(buf, state, board_rect, &clear_set);
+    }
+
+    // Draw Floating Score Popups! Several colours stack their shares under the total.
+    let piece_cells: Vec<Rect> = state
+        .drawn_pieces()
+        .into_iter()
+        .flat_map(|(_, origins)| origins)
+        .map(|(x, y)| {
+            let (x, y) = (x.max(0) as u16, y.max(0) as u16 / 2);
+            Rect::new(x, y, GRAIN_SCALE as u16, GRAIN_SCALE as u16 / 2)
+        })
+        .collect();
+    for popup in state.popups.iter().filter(|_| !options.low_quality) {
+        let total = if popup.multiplier > 1 {
+            format!("+{} (x{})", popup.amount, popup.multiplier)
+        } else {
+            format!("+{}", popup.amount)
+        };
+        let mut lines = vec![(total, popup.color)];
+        if popup.breakdown.len() > 1 {
+            lines.extend(
+                popup
+                    .breakdown
+                    .iter()
+                    .map(|&(color, points)| (format!(" +{points}"), state.theme.sand_color(color))),
+            );
+        }
+        // Over a falling piece: move up above it
+        let width = lines.iter().map(|(l, _)| l.len()).max().unwrap_or(0) as u16;
+        let mut label = Rect::new(popup.x as u16, popup.y as u16 / 2, width, lines.len() as u16);
+        if let Some(top) = piece_cells
+            .iter()
+            .filter(|cell| cell.intersects(label))
+            .map(|cell| cell.y)
+            .min()
+        {
+            label.y = top.saturating_sub(label.height);
+        }
+        let rx = board_rect.x + label.x;
+        for (i, (text, color)) in lines.into_iter().enumerate() {
+            let ry = board_rect.y + label.y + i as u16;
+            if rx < board_rect.x + board_rect.width && ry < board_rect.y + board_rect.height {
+                let style = Style::default().fg(color).bg(state.theme.bg).bold();
+                frame.buffer_mut().set_string(rx, ry, text, style);
+            }
+        }
+    }
+    if let Some(end) = state.combo_end {
+        let text = format!(" combo ×{} ended (+{} bonus) ", end.peak, end.bonus);
+        let width = (text.chars().count() as u16).min(board_rect.width);
+        let rect = Rect {
+            x: board_rect.x + (board_rect.width - width) / 2,
+            y: board_rect.y + 1.min(board_rect.height.saturating_sub(1)),
+            width,
+            height: 1.min(board_rect.height),
+        };
+        Paragraph::new(text)
+            .style(Style::default().fg(Color::Yellow).bg(state.theme.bg).bold())
+            .render(rect, frame.buffer_mut());
+    }
+}
+
+/// `--blend-sand`: a sand grain touching sand of another colour (left, right, above or below)
+/// is drawn a quarter of the way to that colour. Render only: the grain keeps its own colour.
+fn blend_boundary(state: &GameState, color: Color, gx: usize, gy: usize) -> Color {
+    let Some(Cell::Sand(own, _)) = state.playfield.get(gx, gy) else {
+        return color;
+    };
+    let neighbours = [
+        gx.checked_sub(1).map(|x| (x, gy)),
+        Some((gx + 1, gy)),
+        gy.checked_sub(1).map(|y| (gx, y)),
+        Some((gx, gy + 1)),
+    ];
+    let other_color = |(x, y)| match state.playfield.get(x, y) {
+        Some(Cell::Sand(c, _)) if c != own => Some(state.theme.sand_color(c)),
+        _ => None,
+    };
+    let other = neighbours.into_iter().flatten().find_map(other_color);
+    match (color, other) {
+        (Color::Rgb(r, g, b), Some(Color::Rgb(or, og, ob))) => {
+            let mix = |from: u8, to: u8| ((u16::from(from) * 3 + u16::from(to)) / 4) as u8;
+            Color::Rgb(mix(r, or), mix(g, og), mix(b, ob))
+        }
+        _ => color,
+    }
+}
+
+/// Background `bg` blended a quarter of the way to `FOCUS_TINT`.
+fn focus_tint(bg: Color) -> Color {
+    match (bg, FOCUS_TINT) {
+        (Color::Rgb(r, g, b), Color::Rgb(tr, tg, tb)) => {
+            let mix = |from: u8, to: u8| ((u16::from(from) * 3 + u16::from(to)) / 4) as u8;
+            Color::Rgb(mix(r, tr), mix(g, tg), mix(b, tb))
+        }
+        _ => Color::Indexed(17),
+    }
+}
+
+/// Menu attract demo: every cell in `area` at `DEMO_DIM_PERCENT` of its brightness.
+fn dim(buf: &mut ratatui::buffer::Buffer, area: Rect) {
+    let dim_color = |color| match color {
+        Color::Rgb(r, g, b) => {
+            let dim = |c: u8| (u16::from(c) * DEMO_DIM_PERCENT / 100) as u8;
+            Color::Rgb(dim(r), dim(g), dim(b))
+        }
+        Color::Reset => Color::Reset,
+        _ => Color::DarkGray,
+    };
+    for y in area.top()..area.bottom() {
+        for x in area.left()..area.right() {
+            let cell = &mut buf[(x, y)];
+            let (fg, bg) = (dim_color(cell.fg), dim_color(cell.bg));
+            cell.set_fg(fg).set_bg(bg);
+        }
+    }
+}
+
+/// Grey of the same brightness, dimmed a little (`--reduce-flashing` clears).
+fn desaturate(color: Color) -> Color {
+    let Color::Rgb(r, g, b) = color else {
+        return Color::DarkGray;
+    };
+    // Rec. 601 luma, then 70%
+    let luma = 299 * u32::from(r) + 587 * u32::from(g) + 114 * u32::from(b);
+    let grey = (luma * 7 / 10_000) as u8;
+    Color::Rgb(grey, grey, grey)
+}
+
+/// `--a11y` board: one character per terminal cell, white on black, so a screen reader can read
+/// it. Piece `@`, clearing `~`, sand by colour (G Y R B M C), rainbow `*`, converter `+`,
+/// stone `%`, empty space.
+fn draw_a11y_board(
+    buf: &mut ratatui::buffer::Buffer,
+    state: &GameState,
+    board_rect: Rect,
+    clear_set: &HashSet<(usize, usize)>,
+) {
+    const SAND: [char; 6] = ['G', 'Y', 'R', 'B', 'M', 'C'];
+    let frozen: std::collections::HashMap<(usize, usize), Cell> = state
+        .frozen_grains
+        .iter()
+        .map(|fg| ((fg.x, fg.y), fg.cell))
+        .collect();
+    let style = Style::default().fg(Color::White).bg(Color::Black);
+    let (gw, gh) = state.playfield.grain_dims();
+    for y in (0..gh).step_by(2) {
+        for x in 0..gw {
+            let rx = board_rect.x + x as u16;
+            let ry = board_rect.y + (y / 2) as u16;
+            if rx >= board_rect.x + board_rect.width || ry >= board_rect.y + board_rect.height {
+                continue;
+            }
+            let grains = [y, y + 1];
+            let glyph = if grains
+                .iter()
+                .any(|&gy| get_piece_at_grain(state, x, gy).is_some())
+            {
+                '@'
+            } else if grains.iter().any(|&gy| clear_set.contains(&(x, gy))) {
+                '~'
             } else {
-                format!("+{}", popup.amount)
+                grains
+                    .iter()
+                    .find_map(|&gy| {
+                        frozen
+                            .get(&(x, gy))
+                            .copied()
+                            .or_else(|| state.playfield.get(x, gy))
+                            .filter(|c| c.is_solid())
+                    })
+                    .map_or(' ', |cell| match cell {
+                        Cell::Sand(i, _) => SAND[i as usize % SAND.len()],
+                        Cell::Rainbow(_) => '*',
+                        Cell::Converter(..) => '+',
+                        Cell::Stone(_) => '%',
+                        Cell::Empty => ' ',
+                    })
             };
-            let style = Style::default().fg(popup.color).bg(state.theme.bg).bold();
-            frame.buffer_mut().set_string(rx, ry, label, style);
+            buf[(rx, ry)].set_char(glyph).set_style(style);
         }
     }
 }
 
 fn get_piece_at_grain(state: &GameState, gx: usize, gy: usize) -> Option<Color> {
-    if let Some(ref piece) = state.piece {
-        let origins = state
-            .piece_draw_origins()
-            .unwrap_or_else(|| piece.cell_grain_origins());
-        for (pgx, pgy) in origins {
-            if gx as i32 >= pgx
-                && (gx as i32) < pgx + crate::game::GRAIN_SCALE as i32
-                && gy as i32 >= pgy
-                && (gy as i32) < pgy + crate::game::GRAIN_SCALE as i32
-            {
-                let color = state.theme.sand_color(piece.color_index);
+    for (piece, origins) in state.drawn_pieces() {
+        for (i, origin) in origins.into_iter().enumerate() {
+            if state.offset_in_cell(origin, gx, gy).is_some() {
+                let color = if piece.kind == PieceKind::Bomb {
+                    bomb_color(state, gx, gy, piece.blast_center())
+                } else if piece.rainbow_cell == Some(i) {
+                    rainbow_color(state, gx, gy)
+                } else if piece.converter_cell == Some(i) {
+                    converter_color(state, piece.cell_colors[i])
+                } else {
+                    state.theme.sand_color(piece.cell_colors[i])
+                };
                 return Some(apply_shading(color, gx, gy, state));
             }
         }
@@ -989,6 +2437,92 @@ fn get_piece_at_grain(state: &GameState, gx: usize, gy: usize) -> Option<Color>
     None
 }
 
+/// Sand colour index shown at a grain (`--symbols`): the falling piece's cell there, else
+/// coloured sand on the board. None for empty, stone, rainbow and bombs, and for settled sand
+/// in invisible mode (the symbol would give it away).
+fn grain_color_index(state: &GameState, gx: usize, gy: usize) -> Option<u8> {
+    for (piece, origins) in state.drawn_pieces() {
+        if let Some(i) = origins
+            .into_iter()
+            .position(|origin| state.offset_in_cell(origin, gx, gy).is_some())
+        {
+            return (piece.kind != PieceKind::Bomb && piece.rainbow_cell != Some(i))
+                .then(|| piece.cell_colors[i]);
+        }
+    }
+    if state.invisible && !state.game_over {
+        return None;
+    }
+    match state.playfield.get(gx, gy)? {
+        Cell::Sand(i, _) | Cell::Converter(i, _) => Some(i),
+        _ => None,
+    }
+}
+
+/// Base colour of a playfield grain; None for empty.
+fn cell_color(state: &GameState, cell: Cell, gx: usize, gy: usize) -> Option<Color> {
+    match cell {
+        Cell::Empty => None,
+        Cell::Sand(i, _) => Some(state.theme.sand_color(i)),
+        Cell::Rainbow(_) => Some(rainbow_color(state, gx, gy)),
+        Cell::Stone(_) => Some(state.theme.inactive_fg),
+        Cell::Converter(i, _) => Some(converter_color(state, i)),
+    }
+}
+
+/// Invisible sand: fade a settled grain into the background once it has been still for
+/// `INVISIBLE_SHOW_MS`. Stone stays visible, and everything is revealed on game over.
+fn invisible_fade(state: &GameState, color: Color, gx: usize, gy: usize, now: Instant) -> Color {
+    if !state.invisible || state.game_over {
+        return color;
+    }
+    if matches!(state.playfield.get(gx, gy), Some(Cell::Stone(_))) {
+        return color;
+    }
+    let Some(moved_at) = state.playfield.moved_at(gx, gy) else {
+        return color;
+    };
+    let age = now.saturating_duration_since(moved_at).as_millis();
+    if age <= INVISIBLE_SHOW_MS {
+        return color;
+    }
+    let t = ((age - INVISIBLE_SHOW_MS) as f32 / INVISIBLE_FADE_MS as f32).min(1.0);
+    match (color, state.theme.bg) {
+        (Color::Rgb(r, g, b), Color::Rgb(br, bg, bb)) => {
+            let mix = |from: u8, to: u8| (to as f32 - from as f32).mul_add(t, from as f32) as u8;
+            Color::Rgb(mix(r, br), mix(g, bg), mix(b, bb))
+        }
+        _ if t >= 1.0 => state.theme.bg,
+        _ => color,
+    }
+}
+
+/// Bomb body: dark shell with a red core that pulses with the physics tick.
+fn bomb_color(state: &GameState, gx: usize, gy: usize, center: (i32, i32)) -> Color {
+    let (dx, dy) = (gx as i32 - center.0, gy as i32 - center.1);
+    let core = dx * dx + dy * dy <= 9;
+    if core && (state.playfield.tick_count / 6) % 2 == 0 {
+        state.theme.sand_color(2)
+    } else {
+        state.theme.inactive_fg
+    }
+}
+
+/// Converter grains blink between their colour and the title colour until they settle.
+fn converter_color(state: &GameState, index: u8) -> Color {
+    if (state.playfield.tick_count / 5) % 2 == 0 {
+        state.theme.sand_color(index)
+    } else {
+        state.theme.title
+    }
+}
+
+/// Rainbow grains shimmer: diagonal stripes through the sand palette, drifting with the physics tick.
+fn rainbow_color(state: &GameState, gx: usize, gy: usize) -> Color {
+    let phase = (gx + gy) / 3 + (state.playfield.tick_count / 4) as usize;
+    state.theme.sand_color((phase % 6) as u8)
+}
+
 fn sidebar_block_style(state: &GameState) -> Style {
     Style::default().fg(state.theme.div_line).bg(state.theme.bg)
 }
@@ -997,10 +2531,13 @@ fn draw_sidebar(
     frame: &mut Frame,
     state: &GameState,
     area: Rect,
+    part: SidebarPart,
     mode: GameMode,
     high_scores: HighScores,
     autoplay: bool,
     now: Instant,
+    events: Option<&[String]>,
+    mission: Option<&crate::missions::MissionCard>,
 ) {
     let title_style = Style::default().fg(state.theme.title);
     let fg_style = Style::default().fg(state.theme.main_fg);
@@ -1012,183 +2549,378 @@ fn draw_sidebar(
         GameMode::Clear => best_clear,
     };
 
+    let (left, right) = (part != SidebarPart::Right, part != SidebarPart::Left);
+    let events = events.filter(|_| right);
+    let mission = mission.filter(|_| right);
+    // A section and the gap after it, or nothing when it's on the other side
+    let shown = |shown: bool, height: u16| if shown { [height, 1] } else { [0, 0] };
+
     // Free-floating sections with their own borders; vertical layout with small gaps
+    let heights = [
+        shown(left, next_box_rows(state)), // Next (border + title + queue)
+        shown(left, 5),                    // Colours (border + title + strip)
+        shown(right, 8), // Stats (border + score, best, level, clears, rerolls, zone)
+        shown(right, 6), // Combo (border + combo number + timer bar + focus + meter)
+        shown(mission.is_some(), 5), // Mission (border + title + objective + progress), with --missions
+        // Pieces (border + spawn counts per kind + the longest wait, with --anti-drought)
+        shown(left, 4 + u16::from(state.bag.longest_drought().is_some())),
+        // Events (border + title + feed), with --event-log
+        shown(events.is_some(), crate::feed::FEED_LEN as u16 + 3),
+    ];
     let chunks = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Length(8), // Next (border + title + preview)
-            Constraint::Length(1), // gap
-            Constraint::Length(5), // Colours (border + title + strip)
-            Constraint::Length(1), // gap
-            Constraint::Length(8), // Stats (border + score, best, level, clears)
-            Constraint::Length(1), // gap
-            Constraint::Length(6), // Combo (border + combo number + timer bar)
-        ])
+        .constraints(heights.concat().into_iter().map(Constraint::Length))
         .split(area);
 
-    // --- Next Piece (own border) ---
-    // If Autoplay is on, indicate it here
-    let next_outer = chunks[0];
-    let next_block = Block::default()
-        .borders(Borders::ALL)
-        .border_style(border_style);
-    let next_inner = next_block.inner(next_outer);
-    next_block.render(next_outer, frame.buffer_mut());
+    if left {
+        // --- Next Piece (own border) ---
+        // If Autoplay is on, indicate it here
+        let next_outer = chunks[0];
+        let next_block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(border_style);
+        let next_inner = next_block.inner(next_outer);
+        next_block.render(next_outer, frame.buffer_mut());
+
+        if autoplay {
+            // Red flashing text
+            let color = if (now.elapsed().as_millis() / 500) % 2 == 0 {
+                Color::Red
+            } else {
+                Color::Yellow
+            };
+            // Centered paragraph
+            let p = Paragraph::new(Span::styled("AUTOPLAY", Style::default().fg(color).bold()))
+                .alignment(Alignment::Center)
+                .block(Block::default().borders(Borders::NONE));
+        
+            // Vertically center inside the box
+            let v_layout = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([
+                    Constraint::Min(0),
+                    Constraint::Length(1), // Text height
+                    Constraint::Min(0),
+                ])
+                .split(next_inner);
+            
+            p.render(v_layout[1], frame.buffer_mut());
+        } else {
+            let next_layout = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Length(1), Constraint::Min(0)])
+                .split(next_inner);
+            Paragraph::new(Line::from(Span::styled("Next", title_style)))
+                .render(next_layout[0], frame.buffer_mut());
+            draw_next_preview(frame, state, next_layout[1]);
+        }
+    }
 
-    if autoplay {
-        // Red flashing text
-        let color = if (now.elapsed().as_millis() / 500) % 2 == 0 {
-            Color::Red
+    if left {
+        // --- Colours (own border) ---
+        let colours_outer = chunks[2];
+        let colours_block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(border_style);
+        let colours_inner = colours_block.inner(colours_outer);
+        colours_block.render(colours_outer, frame.buffer_mut());
+        let colours_layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(1), Constraint::Length(1)])
+            .split(colours_inner);
+        Paragraph::new(Line::from(Span::styled("Colours", title_style)))
+            .render(colours_layout[0], frame.buffer_mut());
+        draw_colour_strip(frame, state, colours_layout[1]);
+    }
+
+    if right {
+        // --- Stats (own border): Score, Level, Clears ---
+        let stats_outer = chunks[4];
+        let stats_block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(border_style);
+        let stats_inner = stats_block.inner(stats_outer);
+        stats_block.render(stats_outer, frame.buffer_mut());
+        let stats_lines = vec![
+            Line::from(vec![
+                Span::styled("Score: ", title_style),
+                Span::styled(state.score.to_string(), fg_style),
+            ]),
+            Line::from(vec![
+                Span::styled("Best: ", title_style),
+                Span::styled(best.to_string(), fg_style),
+            ]),
+            Line::from(vec![
+                Span::styled("Level: ", title_style),
+                Span::styled(state.level.to_string(), fg_style),
+            ]),
+            Line::from(vec![
+                Span::styled("Clears: ", title_style),
+                Span::styled(state.clears.to_string(), fg_style),
+            ]),
+            // Co-op and custom mode garbage: the tide takes the rerolls line
+            if let Some(progress) = state.tide_progress() {
+                Line::from(vec![
+                    Span::styled("Tide: ", title_style),
+                    Span::styled(state.tide_rows.to_string(), fg_style),
+                    Span::styled(
+                        format!(" ({:.0}%)", progress * 100.0),
+                        Style::default().fg(state.theme.inactive_fg),
+                    ),
+                ])
+            } else {
+                Line::from(vec![
+                    Span::styled("Rerolls: ", title_style),
+                    Span::styled(state.rerolls.to_string(), fg_style),
+                    Span::styled(
+                        format!(" (-{})", state.reroll_cost()),
+                        Style::default().fg(state.theme.inactive_fg),
+                    ),
+                ])
+            },
+            Line::from(vec![
+                Span::styled("Zone: ", title_style),
+                Span::styled(zone_status(state), fg_style),
+            ]),
+        ];
+        Paragraph::new(ratatui::text::Text::from(stats_lines))
+            .render(stats_inner, frame.buffer_mut());
+    }
+
+    if right {
+        // --- Combo (own border): combo number above, timer bar below ---
+        let combo_outer = chunks[6];
+        let combo_block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(border_style);
+        let combo_inner = combo_block.inner(combo_outer);
+        combo_block.render(combo_outer, frame.buffer_mut());
+        let combo_layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(1); 4])
+            .split(combo_inner);
+        let combo_ratio = if state.combo.timer_ticks > 0 {
+            (state.combo_timer_ticks as f64 / state.combo.timer_ticks as f64).min(1.0)
         } else {
+            0.0
+        };
+        let combo_label = if state.combo_multiplier > 0 {
+            format!(
+                "Combo x{}/{}",
+                state.combo_multiplier, state.combo.max_multiplier
+            )
+        } else {
+            "Combo".to_string()
+        };
+        Paragraph::new(Line::from(Span::styled(combo_label, title_style)))
+            .render(combo_layout[0], frame.buffer_mut());
+        let bar_color = if combo_ratio > 0.6 {
+            Color::Green
+        } else if combo_ratio > 0.3 {
             Color::Yellow
+        } else {
+            Color::Red
         };
-        // Centered paragraph
-        let p = Paragraph::new(Span::styled("AUTOPLAY", Style::default().fg(color).bold()))
-            .alignment(Alignment::Center)
-            .block(Block::default().borders(Borders::NONE));
-        
-        // Vertically center inside the box
-        let v_layout = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints([
-                Constraint::Min(0),
-                Constraint::Length(1), // Text height
-                Constraint::Min(0),
-            ])
-            .split(next_inner);
-            
-        p.render(v_layout[1], frame.buffer_mut());
-    } else {
-        let next_layout = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints([Constraint::Length(1), Constraint::Length(5)])
-            .split(next_inner);
-        Paragraph::new(Line::from(Span::styled("Next", title_style)))
-            .render(next_layout[0], frame.buffer_mut());
-        draw_next_preview(frame, state, next_layout[1]);
+        let gauge = Gauge::default()
+            .ratio(combo_ratio)
+            .gauge_style(Style::default().fg(bar_color));
+        gauge.render(combo_layout[1], frame.buffer_mut());
+
+        // Focus: the meter while filling, the time left while active
+        let (focus_label, focus_ratio) = if state.focus_active() {
+            (
+                "FOCUS".to_string(),
+                f64::from(state.focus_ticks) / f64::from(FOCUS_TICKS),
+            )
+        } else if state.focus >= FOCUS_MAX {
+            ("Focus ready (F)".to_string(), 1.0)
+        } else {
+            (
+                format!("Focus {}%", state.focus * 100 / FOCUS_MAX),
+                f64::from(state.focus) / f64::from(FOCUS_MAX),
+            )
+        };
+        Paragraph::new(Line::from(Span::styled(focus_label, title_style)))
+            .render(combo_layout[2], frame.buffer_mut());
+        Gauge::default()
+            .ratio(focus_ratio)
+            .gauge_style(Style::default().fg(FOCUS_TINT))
+            .render(combo_layout[3], frame.buffer_mut());
     }
 
-    // --- Colours (own border) ---
-    let colours_outer = chunks[2];
-    let colours_block = Block::default()
-        .borders(Borders::ALL)
-        .border_style(border_style);
-    let colours_inner = colours_block.inner(colours_outer);
-    colours_block.render(colours_outer, frame.buffer_mut());
-    let colours_layout = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([Constraint::Length(1), Constraint::Length(1)])
-        .split(colours_inner);
-    Paragraph::new(Line::from(Span::styled("Colours", title_style)))
-        .render(colours_layout[0], frame.buffer_mut());
-    draw_colour_strip(frame, state, colours_layout[1]);
-
-    // --- Stats (own border): Score, Level, Clears ---
-    let stats_outer = chunks[4];
-    let stats_block = Block::default()
-        .borders(Borders::ALL)
-        .border_style(border_style);
-    let stats_inner = stats_block.inner(stats_outer);
-    stats_block.render(stats_outer, frame.buffer_mut());
-    let stats_lines = vec![
-        Line::from(vec![
-            Span::styled("Score: ", title_style),
-            Span::styled(state.score.to_string(), fg_style),
-        ]),
-        Line::from(vec![
-            Span::styled("Best: ", title_style),
-            Span::styled(best.to_string(), fg_style),
-        ]),
-        Line::from(vec![
-            Span::styled("Level: ", title_style),
-            Span::styled(state.level.to_string(), fg_style),
-        ]),
-        Line::from(vec![
-            Span::styled("Clears: ", title_style),
-            Span::styled(state.clears.to_string(), fg_style),
-        ]),
-    ];
-    Paragraph::new(ratatui::text::Text::from(stats_lines)).render(stats_inner, frame.buffer_mut());
+    // --- Mission (own border): the objective, with a swatch of the colour to clear ---
+    if let Some(mission) = mission {
+        let mission_outer = chunks[8];
+        let mission_block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(border_style);
+        let mission_inner = mission_block.inner(mission_outer);
+        mission_block.render(mission_outer, frame.buffer_mut());
+        let mut objective = vec![Span::styled(mission.text.as_str(), fg_style)];
+        if let Some(color) = mission.color {
+            objective.push(Span::styled(
+                " ██",
+                Style::default().fg(state.theme.sand_color(color)),
+            ));
+        }
+        let lines = vec![
+            Line::from(Span::styled(
+                format!("Mission {}", mission.number),
+                title_style,
+            )),
+            Line::from(objective),
+            Line::from(Span::styled(
+                mission.progress.as_str(),
+                Style::default().fg(state.theme.inactive_fg),
+            )),
+        ];
+        Paragraph::new(lines).render(mission_inner, frame.buffer_mut());
+    }
 
-    // --- Combo (own border): combo number above, timer bar below ---
-    let combo_outer = chunks[6];
-    let combo_block = Block::default()
-        .borders(Borders::ALL)
-        .border_style(border_style);
-    let combo_inner = combo_block.inner(combo_outer);
-    combo_block.render(combo_outer, frame.buffer_mut());
-    let combo_layout = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([Constraint::Length(1), Constraint::Length(1)])
-        .split(combo_inner);
-    let combo_ratio = if COMBO_TIMER_MAX > 0 {
-        (state.combo_timer_ticks as f64 / COMBO_TIMER_MAX as f64).min(1.0)
-    } else {
-        0.0
-    };
-    let combo_label = if state.combo_multiplier > 0 {
-        format!("Combo x{}", state.combo_multiplier)
-    } else {
-        "Combo".to_string()
-    };
-    Paragraph::new(Line::from(Span::styled(combo_label, title_style)))
-        .render(combo_layout[0], frame.buffer_mut());
-    let bar_color = if combo_ratio > 0.6 {
-        Color::Green
-    } else if combo_ratio > 0.3 {
-        Color::Yellow
+    if left {
+        // --- Pieces (own border): how many of each kind spawned, in its colour ---
+        let pieces_outer = chunks[10];
+        let pieces_block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(border_style);
+        let pieces_inner = pieces_block.inner(pieces_outer);
+        pieces_block.render(pieces_outer, frame.buffer_mut());
+        let counts: Vec<Span> = state
+            .spawned
+            .iter()
+            .map(|(&kind, count)| {
+                let color = if kind == PieceKind::Bomb {
+                    state.theme.inactive_fg
+                } else {
+                    state.theme.sand_color(state.colors.color_index(kind))
+                };
+                Span::styled(format!("{kind:?}{count} "), Style::default().fg(color))
+            })
+            .collect();
+        let mut lines = vec![Line::from(counts)];
+        if let Some((kind, n)) = state.bag.longest_drought() {
+            lines.push(Line::from(vec![
+                Span::styled("Longest wait: ", title_style),
+                Span::styled(
+                    format!("{kind:?} {n}/{DROUGHT_LIMIT}"),
+                    Style::default().fg(state.theme.sand_color(state.colors.color_index(kind))),
+                ),
+            ]));
+        }
+        Paragraph::new(lines)
+            .wrap(Wrap { trim: true })
+            .render(pieces_inner, frame.buffer_mut());
+    }
+
+    // --- Events (own border): newest at the bottom, older ones dimmed ---
+    if let Some(events) = events {
+        let events_outer = chunks[12];
+        let events_block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(border_style);
+        let events_inner = events_block.inner(events_outer);
+        events_block.render(events_outer, frame.buffer_mut());
+        let mut lines = vec![Line::from(Span::styled("Events", title_style))];
+        lines.extend(events.iter().enumerate().map(|(i, event)| {
+            let style = if i + 1 == events.len() {
+                fg_style
+            } else {
+                Style::default().fg(state.theme.inactive_fg)
+            };
+            Line::from(Span::styled(event.as_str(), style))
+        }));
+        Paragraph::new(lines).render(events_inner, frame.buffer_mut());
+    }
+}
+
+/// Which boxes a sidebar holds: all of them, or one side of the widescreen split (left: next,
+/// colours and piece counts; right: stats, combo, mission and events).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SidebarPart {
+    Full,
+    Left,
+    Right,
+}
+
+/// Sidebar zone line: the meter, "ready", or time left and clears banked while active.
+fn zone_status(state: &GameState) -> String {
+    if state.zone_active() {
+        let left = state.zone_ticks * 100 / ZONE_TICKS;
+        format!("ON {left}% x{}", state.zone_clears)
+    } else if state.zone >= ZONE_MAX {
+        "ready (Tab)".to_string()
     } else {
-        Color::Red
-    };
-    let gauge = Gauge::default()
-        .ratio(combo_ratio)
-        .gauge_style(Style::default().fg(bar_color));
-    gauge.render(combo_layout[1], frame.buffer_mut());
-    
+        format!("{}%", state.zone * 100 / ZONE_MAX)
+    }
 }
 
-/// Draw next piece as a small block preview (actual shape).
-fn draw_next_preview(frame: &mut Frame, state: &GameState, area: Rect) {
-    let num_previews = match state.difficulty {
-        crate::Difficulty::Easy => 3,
-        crate::Difficulty::Medium => 2,
-        crate::Difficulty::Hard => 1,
+/// Next pieces shown for the difficulty.
+const fn next_preview_count(state: &GameState) -> usize {
+    state.profile.preview
+}
+
+/// Rows per piece in the next queue: enough for the tallest kind in the bag.
+fn next_slot_rows(state: &GameState) -> u16 {
+    let rows = |kind: &PieceKind| {
+        let dys = kind.cells().iter().map(|&(_, dy)| dy);
+        (dys.clone().max().unwrap_or(0) - dys.min().unwrap_or(0) + 1) as u16
     };
+    state
+        .bag
+        .kinds()
+        .iter()
+        .map(rows)
+        .fold(NEXT_PREVIEW_ROWS, u16::max)
+}
+
+/// Next box height: border, title, and the queue with a divider row above each piece and below
+/// the last.
+fn next_box_rows(state: &GameState) -> u16 {
+    let shown = next_preview_count(state) as u16;
+    3 + shown * (next_slot_rows(state) + 1) + 1
+}
 
-    // Side-by-side: each preview gets a fixed width
-    let pw = 7;
-    for i in 0..num_previews {
-        if i >= state.next_pieces.len() {
-            break;
+/// Draw the next queue top to bottom as small block previews (actual shape). The divider row
+/// where a new bag starts (see `GameState::bag_boundary`) is a dashed "bag" line.
+fn draw_next_preview(frame: &mut Frame, state: &GameState, area: Rect) {
+    let shown = next_preview_count(state).min(state.next_pieces.len());
+    let slot = next_slot_rows(state);
+    let boundary = state.bag_boundary().filter(|&at| at <= shown);
+    let buf = frame.buffer_mut();
+    for i in 0..=shown {
+        let y = area.y + i as u16 * (slot + 1);
+        if boundary == Some(i) {
+            let divider = Rect {
+                y,
+                height: 1,
+                ..area
+            }
+            .intersection(area);
+            Paragraph::new(format!("{:╌^1$}", " bag ", area.width as usize))
+                .style(Style::default().fg(state.theme.inactive_fg))
+                .render(divider, buf);
+        }
+        if let Some(next) = state.next_pieces[..shown].get(i) {
+            let sub_area = Rect {
+                x: area.x + area.width.saturating_sub(NEXT_SLOT_COLS) / 2,
+                y: y + 1,
+                width: NEXT_SLOT_COLS.min(area.width),
+                height: slot,
+            }
+            .intersection(area);
+            draw_single_piece_preview(buf, state, sub_area, next);
         }
-        let (kind, color_index) = state.next_pieces[i];
-        let sub_area = Rect {
-            x: area.x + (i as u16 * pw),
-            y: area.y,
-            width: pw,
-            height: area.height,
-        };
-        draw_single_piece_preview(frame, state, sub_area, kind, color_index);
     }
 }
 
 #[allow(clippy::similar_names)]
 fn draw_single_piece_preview(
-    frame: &mut Frame,
+    buf: &mut ratatui::buffer::Buffer,
     state: &GameState,
     area: Rect,
-    kind: TetrominoKind,
-    color_index: u8,
+    next: &NextPiece,
 ) {
-    let inner = Rect {
-        x: area.x,
-        y: area.y,
-        width: area.width.min(NEXT_PREVIEW_COLS * NEXT_MINI_CELL_W),
-        height: area.height.min(NEXT_PREVIEW_ROWS * NEXT_MINI_CELL_H),
-    };
-
-    let color = state.theme.sand_color(color_index);
-    let cells = kind.cells();
+    let cells = next.kind.cells_facing(state.mirror);
     let (dx_lo, dy_lo) = cells
         .iter()
         .fold((i8::MAX, i8::MAX), |(ax, ay): (i8, i8), (dx, dy)| {
@@ -1202,20 +2934,41 @@ fn draw_single_piece_preview(
 
     let bw = (dx_hi - dx_lo + 1) as u16;
     let bh = (dy_hi - dy_lo + 1) as u16;
-    let off_x = (inner.width.saturating_sub(bw * NEXT_MINI_CELL_W)) / 2;
+    // Wide pieces (I, most pentominoes) drop to one column per cell so they fit their slot.
+    let cell_w = if bw * NEXT_MINI_CELL_W <= area.width {
+        NEXT_MINI_CELL_W
+    } else {
+        1
+    };
+    let inner = Rect {
+        x: area.x,
+        y: area.y,
+        width: area.width.min(NEXT_PREVIEW_COLS.max(bw) * cell_w),
+        height: area.height.min(NEXT_PREVIEW_ROWS.max(bh) * NEXT_MINI_CELL_H),
+    };
+    let off_x = (inner.width.saturating_sub(bw * cell_w)) / 2;
     let off_y = (inner.height.saturating_sub(bh * NEXT_MINI_CELL_H)) / 2;
 
-    for (dx, dy) in cells.iter().copied() {
+    for (i, (dx, dy)) in cells.iter().copied().enumerate() {
         let px = (dx - dx_lo) as u16;
         let py = (dy - dy_lo) as u16;
+        let color = if next.kind == PieceKind::Bomb {
+            state.theme.inactive_fg
+        } else if next.rainbow_cell == Some(i) {
+            rainbow_color(state, px as usize * 3, py as usize * 3)
+        } else if next.converter_cell == Some(i) {
+            converter_color(state, next.cell_colors[i])
+        } else {
+            state.theme.sand_color(next.cell_colors[i])
+        };
         let r = Rect {
-            x: inner.x + off_x + px * NEXT_MINI_CELL_W,
+            x: inner.x + off_x + px * cell_w,
             y: inner.y + off_y + py * NEXT_MINI_CELL_H,
-            width: NEXT_MINI_CELL_W,
+            width: cell_w,
             height: NEXT_MINI_CELL_H,
         };
         let p = Paragraph::new("██").style(Style::default().fg(color).bg(color));
-        p.render(r, frame.buffer_mut());
+        p.render(r, buf);
     }
 }
 
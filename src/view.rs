@@ -0,0 +1,81 @@
+//! `BoardView`: a read-only summary of the board for bots and overlays. It holds each grain
+//! column's stack height and holes. `update` keeps a view current frame by frame, rescanning
+//! only the columns whose `Playfield::column_revision` moved since the last update.
+
+use crate::game::{Cell, GRAIN_SCALE, GameState};
+
+/// The board as of the last `update`, in grains. The stack is the sand on the board, settled
+/// or falling; the falling piece and the frozen grains of a locked one don't count yet.
+#[derive(Debug, Clone, Default)]
+pub struct BoardView {
+    /// Board size in grains.
+    pub width: usize,
+    pub height: usize,
+    /// Stack height of each grain column: from the floor to its top grain, 0 when empty.
+    pub heights: Vec<usize>,
+    /// Holes in each grain column: empty grains under its top grain.
+    pub holes: Vec<usize>,
+    /// `Playfield::column_revision` of each column as last scanned.
+    revisions: Vec<u64>,
+}
+
+impl BoardView {
+    /// Catch up with `state`: rescan the columns that changed since the last update (every
+    /// column of a board of another size).
+    pub fn update(&mut self, state: &GameState) {
+        let playfield = &state.playfield;
+        let (gw, gh) = playfield.grain_dims();
+        if (gw, gh) != (self.width, self.height) {
+            // No column is ever at revision 0, so all of them get scanned
+            *self = Self {
+                width: gw,
+                height: gh,
+                heights: vec![0; gw],
+                holes: vec![0; gw],
+                revisions: vec![0; gw],
+            };
+        }
+        for x in 0..gw {
+            let revision = playfield.column_revision(x);
+            if self.revisions[x] != revision {
+                self.revisions[x] = revision;
+                self.heights[x] = playfield.column_height(x);
+                self.holes[x] = (gh - self.heights[x]..gh)
+                    .filter(|&y| !playfield.get(x, y).is_some_and(Cell::is_solid))
+                    .count();
+            }
+        }
+    }
+
+    /// Holes in every column together.
+    pub fn hole_count(&self) -> usize {
+        self.holes.iter().sum()
+    }
+
+    /// Stack height of each block column, in block rows from the floor (a part-filled row
+    /// counts).
+    pub fn block_heights(&self) -> Vec<usize> {
+        self.heights
+            .chunks(GRAIN_SCALE)
+            .map(|column| {
+                column
+                    .iter()
+                    .max()
+                    .copied()
+                    .unwrap_or(0)
+                    .div_ceil(GRAIN_SCALE)
+            })
+            .collect()
+    }
+}
+
+/// Stack height and holes of a grain column `gh` grains tall, where `solid(y)` tells a filled
+/// grain (`y` 0 at the top), by scanning it: for the boards the bot only imagines, where no
+/// column tops are kept.
+pub fn column_profile(gh: usize, solid: impl Fn(usize) -> bool) -> (usize, usize) {
+    let Some(top) = (0..gh).find(|&y| solid(y)) else {
+        return (0, 0);
+    };
+    let holes = (top..gh).filter(|&y| !solid(y)).count();
+    (gh - top, holes)
+}